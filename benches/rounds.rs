@@ -0,0 +1,119 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use gennaro_dkg::*;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use vsss_rs::elliptic_curve::{group::GroupEncoding, Group};
+
+/// Share identifiers are a single byte so 255 is the largest committee
+const SIZES: [usize; 4] = [5, 25, 100, 255];
+
+fn parameters<G: Group + GroupEncoding + Default>(n: usize) -> Parameters<G> {
+    Parameters::new(
+        NonZeroUsize::new(n / 2 + 1).unwrap(),
+        NonZeroUsize::new(n).unwrap(),
+    )
+}
+
+fn participants<G: Group + GroupEncoding + Default>(n: usize) -> Vec<SecretParticipant<G>> {
+    let parameters = parameters::<G>(n);
+    (1..=n)
+        .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap())
+        .collect()
+}
+
+fn bench_curve<G: Group + GroupEncoding + Default>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for n in SIZES {
+        let parameters = parameters::<G>(n);
+        group.bench_with_input(BenchmarkId::new("initialize", n), &n, |b, _| {
+            b.iter(|| SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters))
+        });
+
+        // Run the protocol once and benchmark each round for participant 1
+        let mut ps = participants::<G>(n);
+        let start = ps.clone();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in ps.iter_mut() {
+            let (bdata, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), bdata);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        group.bench_with_input(BenchmarkId::new("round1", n), &n, |b, _| {
+            b.iter_batched(
+                || start[0].clone(),
+                |mut p| p.round1().unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+
+        let round2_input = |id: usize| {
+            let bdata = r1bdata
+                .iter()
+                .filter(|(i, _)| **i != id)
+                .map(|(i, b)| (*i, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2p = r1p2pdata
+                .iter()
+                .filter(|(i, _)| **i != id)
+                .map(|(i, p)| (*i, p[&id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            (bdata, p2p)
+        };
+        let after_round1 = ps[0].clone();
+        let (bdata, p2p) = round2_input(1);
+        group.bench_with_input(BenchmarkId::new("round2", n), &n, |b, _| {
+            b.iter_batched(
+                || (after_round1.clone(), bdata.clone(), p2p.clone()),
+                |(mut p, bdata, p2p)| p.round2(bdata, p2p).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+
+        let mut r2bdata = BTreeMap::new();
+        for p in ps.iter_mut() {
+            let (bdata, p2p) = round2_input(p.get_id());
+            r2bdata.insert(p.get_id(), p.round2(bdata, p2p).unwrap());
+        }
+        let after_round2 = ps[0].clone();
+        group.bench_with_input(BenchmarkId::new("round3", n), &n, |b, _| {
+            b.iter_batched(
+                || after_round2.clone(),
+                |mut p| p.round3(&r2bdata).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+
+        let mut r3bdata = BTreeMap::new();
+        for p in ps.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let after_round3 = ps[0].clone();
+        group.bench_with_input(BenchmarkId::new("round4", n), &n, |b, _| {
+            b.iter_batched(
+                || after_round3.clone(),
+                |mut p| p.round4(&r3bdata).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+
+        let mut r4bdata = BTreeMap::new();
+        for p in ps.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        }
+        group.bench_with_input(BenchmarkId::new("round5", n), &n, |b, _| {
+            b.iter(|| ps[0].round5(&r4bdata).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn rounds(c: &mut Criterion) {
+    bench_curve::<k256::ProjectivePoint>(c, "k256");
+    bench_curve::<blsful::inner_types::G1Projective>(c, "bls12_381_g1");
+}
+
+criterion_group!(benches, rounds);
+criterion_main!(benches);
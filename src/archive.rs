@@ -0,0 +1,53 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// All the broadcast data sent during a run, including the data sent by the
+/// participant replaying it, used by [`Participant::catch_up`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BroadcastArchive<G: Group + GroupEncoding + Default> {
+    /// Round 1 broadcast data keyed by sender
+    #[serde(bound(serialize = "Round1BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round1BroadcastData<G>: Deserialize<'de>"))]
+    #[serde(with = "ordinal::map")]
+    pub round1_broadcast: BTreeMap<usize, Round1BroadcastData<G>>,
+    /// Round 2 echo broadcast data keyed by sender
+    #[serde(with = "ordinal::map")]
+    pub round2_echo: BTreeMap<usize, Round2EchoBroadcastData>,
+    /// Round 3 broadcast data keyed by sender
+    #[serde(bound(serialize = "Round3BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round3BroadcastData<G>: Deserialize<'de>"))]
+    #[serde(with = "ordinal::map")]
+    pub round3_broadcast: BTreeMap<usize, Round3BroadcastData<G>>,
+    /// Round 4 echo broadcast data keyed by sender
+    #[serde(bound(serialize = "Round4EchoBroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round4EchoBroadcastData<G>: Deserialize<'de>"))]
+    #[serde(with = "ordinal::map")]
+    pub round4_echo: BTreeMap<usize, Round4EchoBroadcastData<G>>,
+}
+
+impl<G: Group + GroupEncoding + Default> BroadcastArchive<G> {
+    /// Compare `output` computed by participant `id` with the archived copy.
+    ///
+    /// Returns [`None`] if no copy was archived.
+    pub(crate) fn matches(&self, id: usize, output: &RoundOutput<G>) -> Option<bool> {
+        match output {
+            RoundOutput::Round2(echo) => self
+                .round2_echo
+                .get(&id)
+                .map(|a| a.valid_participant_ids == echo.valid_participant_ids),
+            RoundOutput::Round3(bdata) => self
+                .round3_broadcast
+                .get(&id)
+                .map(|a| a.commitments == bdata.commitments),
+            RoundOutput::Round4(echo) => self
+                .round4_echo
+                .get(&id)
+                .map(|a| a.public_key == echo.public_key),
+            RoundOutput::Round1 { broadcast, .. } => self
+                .round1_broadcast
+                .get(&id)
+                .map(|a| a.pedersen_commitments == broadcast.pedersen_commitments),
+            RoundOutput::Complete => Some(true),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::*;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+
+/// A record that data from `sender` passed all the checks for `round`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// The round that used the data
+    pub round: Round,
+    /// The participant that sent the data
+    pub sender: usize,
+    /// The SHA-256 hash of the serialized data
+    pub hash: [u8; 32],
+}
+
+impl AuditEntry {
+    pub(crate) fn new<T: Serialize>(round: Round, sender: usize, message: &T) -> Self {
+        // Serializing in-memory round data into a vector doesn't fail
+        let bytes = serde_bare::to_vec(message).unwrap_or_default();
+        Self {
+            round,
+            sender,
+            hash: Sha256::digest(&bytes).into(),
+        }
+    }
+}
+
+/// Receives every [`AuditEntry`] as soon as it is recorded
+/// so it can be stored outside the participant
+pub trait AuditSink: Send + Sync {
+    /// Store `entry`
+    fn record(&self, entry: &AuditEntry);
+}
+
+/// The audit entries retained by a participant.
+///
+/// Round 5 only borrows the participant so entries are
+/// appended through a lock.
+#[derive(Debug, Default)]
+pub(crate) struct AuditLog(Mutex<Option<Vec<AuditEntry>>>);
+
+impl AuditLog {
+    pub(crate) fn enable(&self) {
+        if let Ok(mut log) = self.0.lock() {
+            log.get_or_insert_with(Vec::new);
+        }
+    }
+
+    pub(crate) fn push(&self, entry: AuditEntry) {
+        if let Ok(mut log) = self.0.lock() {
+            if let Some(log) = log.as_mut() {
+                log.push(entry);
+            }
+        }
+    }
+
+    pub(crate) fn entries(&self) -> Vec<AuditEntry> {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|log| log.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.0.lock().map(|log| log.is_some()).unwrap_or(false)
+    }
+}
+
+impl Clone for AuditLog {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().ok().and_then(|log| log.clone())))
+    }
+}
+
+impl Serialize for AuditLog {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0
+            .lock()
+            .map_err(|_| serde::ser::Error::custom("audit log lock poisoned"))?
+            .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for AuditLog {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Option::<Vec<AuditEntry>>::deserialize(d).map(|log| Self(Mutex::new(log)))
+    }
+}
+
+/// Wrapper so participants can remain `Clone` and `Debug`
+#[derive(Clone)]
+pub(crate) struct AuditSinkHandle(pub(crate) Arc<dyn AuditSink>);
+
+impl fmt::Debug for AuditSinkHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "AuditSink")
+    }
+}
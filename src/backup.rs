@@ -0,0 +1,108 @@
+use super::*;
+use std::collections::BTreeMap;
+use vsss_rs::elliptic_curve::ff::Field;
+
+const RECOVERY_DST: &[u8] = b"gennaro-dkg recovery data";
+
+/// The minimum length of the seed that encrypts [`RecoveryData`]
+pub const RECOVERY_SEED_LENGTH: usize = 32;
+
+/// A backup of one participant's output that is safe to store anywhere.
+///
+/// The secret share is encrypted with a one-time pad derived from the
+/// participant's long-term seed and the transcript hash, everything else
+/// is public. A participant that loses its state but keeps its seed
+/// recovers its output with [`RecoveryData::recover`] without
+/// running a repair protocol with the others.
+///
+/// Not to be confused with the [`RecoveryShareData`] used to recover
+/// disqualified dealers during the protocol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryData<G: Group + GroupEncoding + Default> {
+    /// The id of the participant
+    pub participant: usize,
+    /// The generator of the public key and public share
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub generator: G,
+    /// The public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The participant's secret share times the generator
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_share: G,
+    /// The report of the ceremony
+    pub report: DkgReport,
+    /// The transcript hash the pad is derived from
+    pub transcript_hash: [u8; 32],
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    ciphertext: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> RecoveryData<G> {
+    /// Decrypt the secret share with `seed` and return the output
+    /// of the participant.
+    ///
+    /// Throws an error if the seed is wrong or the data was modified.
+    pub fn recover(&self, seed: &[u8]) -> DkgResult<DkgOutput<G>> {
+        let share = self.ciphertext - self.pad(seed)?;
+        if self.generator * share != self.public_share {
+            return Err(Error::InvalidParameters(
+                "the seed does not decrypt the recovery data".to_string(),
+            ));
+        }
+        Ok(DkgOutput {
+            public_key: self.public_key,
+            secret_shares: BTreeMap::from([(self.participant, share)]),
+            report: self.report.clone(),
+        })
+    }
+
+    fn pad(&self, seed: &[u8]) -> DkgResult<G::Scalar> {
+        if seed.len() < RECOVERY_SEED_LENGTH {
+            return Err(Error::InvalidParameters(format!(
+                "the seed must be at least {} bytes",
+                RECOVERY_SEED_LENGTH
+            )));
+        }
+        let mut msg = Vec::with_capacity(seed.len() + 48);
+        msg.extend_from_slice(&(seed.len() as u64).to_be_bytes());
+        msg.extend_from_slice(seed);
+        msg.extend_from_slice(&self.transcript_hash);
+        msg.extend_from_slice(&(self.participant as u64).to_be_bytes());
+        msg.extend_from_slice(self.public_share.to_bytes().as_ref());
+        let pad = utils::hash_to_scalar(RECOVERY_DST, &msg);
+        msg.zeroize();
+        pad
+    }
+}
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Encrypt this participant's output under `seed` so it can be
+    /// recovered with [`RecoveryData::recover`].
+    ///
+    /// The seed must be at least [`RECOVERY_SEED_LENGTH`] bytes, kept secret
+    /// and stored apart from the recovery data.
+    ///
+    /// Throws an error if the ceremony is not complete.
+    pub fn recovery_data(&self, seed: &[u8]) -> DkgResult<RecoveryData<G>> {
+        let output = self
+            .output()
+            .ok_or_else(|| self.invalid_round(Round::Five))?;
+        let share = output.secret_shares[&self.get_id()];
+        let generator = self.parameters().message_generator;
+        let mut data = RecoveryData {
+            participant: self.get_id(),
+            generator,
+            public_key: output.public_key,
+            public_share: generator * share,
+            report: output.report.clone(),
+            transcript_hash: self.get_transcript_hash(),
+            ciphertext: G::Scalar::ZERO,
+        };
+        data.ciphertext = share + data.pad(seed)?;
+        Ok(data)
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// A participant generating [`Parameters::secret_count`] independent secrets
+/// in one run of the protocol.
+///
+/// Each round's data is a vector with one entry per secret so all secrets
+/// share the same 5 rounds of communication. Each secret is generated by its
+/// own [`Participant`] and the data from a sender is only used if it has
+/// exactly one entry per secret.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchParticipant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
+    #[serde(bound(serialize = "Participant<I, G>: Serialize"))]
+    #[serde(bound(deserialize = "Participant<I, G>: Deserialize<'de>"))]
+    participants: Vec<Participant<I, G>>,
+}
+
+/// Batch secret participant type
+pub type BatchSecretParticipant<G> = BatchParticipant<SecretParticipantImpl<G>, G>;
+
+impl<I, G> BatchParticipant<I, G>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+{
+    /// Create a new participant to generate `parameters.secret_count()` new secrets
+    pub fn new(id: NonZeroUsize, parameters: Parameters<G>) -> DkgResult<Self> {
+        parameters.validate()?;
+        let participants = (0..parameters.secret_count)
+            .map(|_| Participant::new(id, parameters))
+            .collect::<DkgResult<Vec<_>>>()?;
+        Ok(Self { participants })
+    }
+
+    /// The identifier associated with this participant
+    pub fn get_id(&self) -> usize {
+        self.participants[0].get_id()
+    }
+
+    /// The current round of this participant
+    pub fn get_round(&self) -> Round {
+        self.participants[0].get_round()
+    }
+
+    /// Returns true if this participant is complete
+    pub fn completed(&self) -> bool {
+        self.participants.iter().all(|p| p.completed())
+    }
+
+    /// The number of secrets generated
+    pub fn secret_count(&self) -> usize {
+        self.participants.len()
+    }
+
+    /// The participants generating each secret
+    pub fn participants(&self) -> &[Participant<I, G>] {
+        &self.participants
+    }
+
+    /// The computed public keys, one per secret, or [`None`] until complete
+    pub fn get_public_keys(&self) -> Option<Vec<G>> {
+        self.participants
+            .iter()
+            .map(|p| p.get_public_key())
+            .collect()
+    }
+
+    /// The computed secret shares, one per secret, or [`None`] until round 2 completes
+    pub fn get_secret_shares(&self) -> Option<Vec<G::Scalar>> {
+        self.participants
+            .iter()
+            .map(|p| p.computed_secret_share())
+            .collect()
+    }
+
+    /// Computes round 1 for each secret.
+    ///
+    /// See [`Participant::round1`]
+    pub fn round1(
+        &mut self,
+    ) -> DkgResult<(
+        Vec<Round1BroadcastData<G>>,
+        BTreeMap<usize, Vec<Round1P2PData>>,
+    )> {
+        let mut broadcast = Vec::with_capacity(self.participants.len());
+        let mut p2p = BTreeMap::<usize, Vec<Round1P2PData>>::new();
+        for participant in self.participants.iter_mut() {
+            let (b, p) = participant.round1()?;
+            broadcast.push(b);
+            for (id, data) in p {
+                p2p.entry(id).or_default().push(data);
+            }
+        }
+        Ok((broadcast, p2p))
+    }
+
+    /// Computes round 2 for each secret.
+    ///
+    /// See [`Participant::round2`]
+    pub fn round2(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Vec<Round1BroadcastData<G>>>,
+        p2p_data: BTreeMap<usize, Vec<Round1P2PData>>,
+    ) -> DkgResult<Vec<Round2EchoBroadcastData>> {
+        let broadcast_data = self.split(broadcast_data);
+        let p2p_data = self.split(p2p_data);
+        self.participants
+            .iter_mut()
+            .zip(broadcast_data.into_iter().zip(p2p_data))
+            .map(|(p, (b, d))| p.round2(b, d))
+            .collect()
+    }
+
+    /// Computes round 3 for each secret.
+    ///
+    /// See [`Participant::round3`]
+    pub fn round3(
+        &mut self,
+        echo_data: &BTreeMap<usize, Vec<Round2EchoBroadcastData>>,
+    ) -> DkgResult<Vec<Round3BroadcastData<G>>> {
+        let echo_data = self.split(echo_data.clone());
+        self.participants
+            .iter_mut()
+            .zip(echo_data)
+            .map(|(p, d)| p.round3(&d))
+            .collect()
+    }
+
+    /// Computes round 4 for each secret.
+    ///
+    /// See [`Participant::round4`]
+    pub fn round4(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Vec<Round3BroadcastData<G>>>,
+    ) -> DkgResult<Vec<Round4EchoBroadcastData<G>>> {
+        let broadcast_data = self.split(broadcast_data.clone());
+        self.participants
+            .iter_mut()
+            .zip(broadcast_data)
+            .map(|(p, d)| p.round4(&d))
+            .collect()
+    }
+
+    /// Computes round 5 for each secret.
+    ///
+    /// See [`Participant::round5`]
+    pub fn round5(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Vec<Round4EchoBroadcastData<G>>>,
+    ) -> DkgResult<()> {
+        let broadcast_data = self.split(broadcast_data.clone());
+        self.participants
+            .iter_mut()
+            .zip(broadcast_data)
+            .try_for_each(|(p, d)| p.round5(&d))
+    }
+
+    /// Turn data keyed by sender into one map per secret,
+    /// ignoring senders that didn't send one entry per secret
+    fn split<T>(&self, data: BTreeMap<usize, Vec<T>>) -> Vec<BTreeMap<usize, T>> {
+        let mut split = (0..self.participants.len())
+            .map(|_| BTreeMap::new())
+            .collect::<Vec<_>>();
+        for (id, values) in data {
+            if values.len() != self.participants.len() {
+                self.participants[0]
+                    .message_rejected(self.get_round(), ParticipantError::MismatchedParameters(id));
+                continue;
+            }
+            for (map, value) in split.iter_mut().zip(values) {
+                map.insert(id, value);
+            }
+        }
+        split
+    }
+}
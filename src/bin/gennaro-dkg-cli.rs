@@ -0,0 +1,231 @@
+//! Reference ceremony driver exchanging messages as JSON files.
+//!
+//! Each participant keeps its state in a file and every round reads the
+//! messages from the other participants out of a directory and writes its
+//! own messages into another, so the files can be moved with any transport
+//! and inspected by an auditor.
+//!
+//! ```text
+//! gennaro-dkg-cli init --id 1 --threshold 2 --limit 3 --state p1.json
+//! gennaro-dkg-cli round --state p1.json --in msgs/ --out msgs/   # rounds 1 to 4
+//! gennaro-dkg-cli finish --state p1.json --in msgs/
+//! ```
+//!
+//! The state file holds the participant's secret share unencrypted. It is
+//! created readable only by its owner on unix and should be kept on
+//! trusted storage and deleted once the share is stored elsewhere.
+use clap::{Parser, Subcommand};
+use gennaro_dkg::{vsss_rs::elliptic_curve::group::GroupEncoding, *};
+use k256::ProjectivePoint;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroize;
+
+type CliParticipant = SecretParticipant<ProjectivePoint>;
+
+#[derive(Parser)]
+#[command(version, about = "Run a Gennaro DKG participant over files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new participant
+    Init {
+        /// This participant's identifier, 1 to limit
+        #[arg(long)]
+        id: usize,
+        /// The number of participants required to use the key
+        #[arg(long)]
+        threshold: usize,
+        /// The total number of participants
+        #[arg(long)]
+        limit: usize,
+        /// Where to save the participant state
+        #[arg(long)]
+        state: PathBuf,
+    },
+    /// Compute the next round from the messages in `--in` and write this
+    /// participant's messages to `--out`
+    Round {
+        /// The participant state
+        #[arg(long)]
+        state: PathBuf,
+        /// Directory with the messages from the other participants
+        #[arg(long = "in")]
+        input: PathBuf,
+        /// Directory to write this participant's messages to
+        #[arg(long = "out")]
+        output: PathBuf,
+    },
+    /// Check all participants computed the same public key and print it
+    Finish {
+        /// The participant state
+        #[arg(long)]
+        state: PathBuf,
+        /// Directory with the round 4 messages from the other participants
+        #[arg(long = "in")]
+        input: PathBuf,
+    },
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match Cli::parse().command {
+        Command::Init {
+            id,
+            threshold,
+            limit,
+            state,
+        } => {
+            let id = NonZeroUsize::new(id).ok_or("id cannot be zero")?;
+            let parameters = Parameters::<ProjectivePoint>::builder()
+                .threshold(threshold)
+                .limit(limit)
+                .build()?;
+            let participant = CliParticipant::new(id, parameters)?;
+            write_state(&state, &participant)?;
+        }
+        Command::Round {
+            state,
+            input,
+            output,
+        } => {
+            let mut participant: CliParticipant = read_json(&state)?;
+            let id = participant.get_id();
+            fs::create_dir_all(&output)?;
+            let round = participant.get_round();
+            match round {
+                Round::Zero | Round::One => {
+                    let (broadcast, p2p) = participant.round1()?;
+                    write_json(&output.join(broadcast_name(Round::One, id)), &broadcast)?;
+                    for (to, data) in &p2p {
+                        write_json(
+                            &output.join(format!("round1-p2p-{}-to-{}.json", id, to)),
+                            data,
+                        )?;
+                    }
+                }
+                Round::Two => {
+                    let broadcast = read_broadcasts(&input, Round::One, Some(id))?;
+                    let mut p2p = BTreeMap::new();
+                    for from in broadcast.keys() {
+                        let path = input.join(format!("round1-p2p-{}-to-{}.json", from, id));
+                        if path.exists() {
+                            p2p.insert(*from, read_json(&path)?);
+                        }
+                    }
+                    let echo = participant.round2(broadcast, p2p)?;
+                    write_json(&output.join(broadcast_name(Round::Two, id)), &echo)?;
+                }
+                Round::Three => {
+                    let echo = read_broadcasts(&input, Round::Two, None)?;
+                    let broadcast = participant.round3(&echo)?;
+                    write_json(&output.join(broadcast_name(Round::Three, id)), &broadcast)?;
+                }
+                Round::Four => {
+                    let broadcast = read_broadcasts(&input, Round::Three, None)?;
+                    let echo = participant.round4(&broadcast)?;
+                    write_json(&output.join(broadcast_name(Round::Four, id)), &echo)?;
+                }
+                Round::Five => return Err("all rounds computed, use finish".into()),
+                Round::Aborted => return Err("the ceremony was aborted".into()),
+            }
+            write_state(&state, &participant)?;
+            println!(
+                "participant {} computed round {}",
+                id,
+                round.max(Round::One)
+            );
+        }
+        Command::Finish { state, input } => {
+            let mut participant: CliParticipant = read_json(&state)?;
+            let echo = read_broadcasts(&input, Round::Four, None)?;
+            participant.round5(&echo)?;
+            write_state(&state, &participant)?;
+            let public_key = participant
+                .get_public_key()
+                .ok_or("round 5 has not been computed")?;
+            println!(
+                "public key: {}",
+                data_encoding::HEXLOWER.encode(public_key.to_bytes().as_ref())
+            );
+            println!(
+                "transcript hash: {}",
+                data_encoding::HEXLOWER.encode(&participant.get_transcript_hash())
+            );
+        }
+    }
+    Ok(())
+}
+
+fn broadcast_name(round: Round, id: usize) -> String {
+    format!("round{}-broadcast-{}.json", round, id)
+}
+
+/// Read the broadcast messages for `round` from every participant
+/// except `skip`
+fn read_broadcasts<T: DeserializeOwned>(
+    dir: &Path,
+    round: Round,
+    skip: Option<usize>,
+) -> Result<BTreeMap<usize, T>, Box<dyn std::error::Error>> {
+    let prefix = format!("round{}-broadcast-", round);
+    let mut messages = BTreeMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(id) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix(&prefix))
+            .and_then(|n| n.strip_suffix(".json"))
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if Some(id) != skip {
+            messages.insert(id, read_json(&path)?);
+        }
+    }
+    Ok(messages)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// Write the participant state to a file only its owner can read
+fn write_state(
+    path: &Path,
+    participant: &CliParticipant,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // The mode only applies to new files
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    let mut data = serde_json::to_vec_pretty(participant)?;
+    let written = file.write_all(&data);
+    data.zeroize();
+    written?;
+    eprintln!(
+        "warning: {} holds the secret share unencrypted, keep it private",
+        path.display()
+    );
+    Ok(())
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use super::*;
+
+const OPENING_POINT_DST: &[u8] = b"gennaro-dkg blinder opening point";
+const OPENING_CHALLENGE_DST: &[u8] = b"gennaro-dkg blinder opening challenge";
+
+/// Optional data a dealer sends after round 3 so auditors can confirm its
+/// feldman commitments match the Pedersen commitments it sent in round 1.
+///
+/// Each Pedersen commitment is `message_generator * a_k + blinder_generator * b_k`
+/// and each feldman commitment is `message_generator * a_k`, so their
+/// difference must be `blinder_generator * b_k`. The commitments are
+/// combined at a point `x` derived from all of them into
+/// `blinder_generator * b(x)` where `b` is the blinder polynomial, and this
+/// is a Schnorr proof of knowledge of `b(x)`. A dealer that changed any
+/// coefficient between the rounds can't make the proof without knowing
+/// the discrete log of the blinder generator.
+///
+/// Auditors check it with only the broadcast data, without the shares or
+/// trusting that the transcript hash binds the two sets of commitments.
+/// Dealers only compute it when [`Parameters::with_blinder_opening`] is set.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlinderOpening<G: Group + GroupEncoding + Default> {
+    /// The id of the dealer
+    #[serde(with = "ordinal")]
+    pub dealer: usize,
+    /// The proof nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub r: G,
+    /// The proof response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub s: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> BlinderOpening<G> {
+    /// `blinder_shares` are at least threshold shares of the blinder polynomial
+    /// as pairs of identifier and value
+    pub(crate) fn prove(
+        dealer: usize,
+        blinder_generator: G,
+        pedersen_commitments: &[G],
+        feldman_commitments: &[G],
+        blinder_shares: &[(G::Scalar, G::Scalar)],
+    ) -> DkgResult<Self> {
+        let x = opening_point(
+            dealer,
+            blinder_generator,
+            pedersen_commitments,
+            feldman_commitments,
+        )?;
+        let mut blinder = primitives::interpolate(blinder_shares, x).map_err(|_| {
+            Error::RoundError(Round::Two.into(), "duplicate blinder share".to_string())
+        })?;
+        let combined = combine(pedersen_commitments, feldman_commitments, x);
+        let proof = primitives::prove(blinder_generator, blinder, |r| {
+            challenge(dealer, blinder_generator, combined, r)
+        });
+        utils::zeroize_scalar(&mut blinder);
+        let (r, s) = proof?;
+        Ok(Self { dealer, r, s })
+    }
+
+    /// Check the opening against the dealer's round 1 and round 3 broadcast data
+    pub fn verify(&self, round1: &Round1BroadcastData<G>, round3: &Round3BroadcastData<G>) -> bool {
+        if round1.pedersen_commitments.is_empty()
+            || round1.pedersen_commitments.len() != round3.commitments.len()
+        {
+            return false;
+        }
+        let Ok(x) = opening_point(
+            self.dealer,
+            round1.blinder_generator,
+            &round1.pedersen_commitments,
+            &round3.commitments,
+        ) else {
+            return false;
+        };
+        let combined = combine(&round1.pedersen_commitments, &round3.commitments, x);
+        primitives::verify(round1.blinder_generator, combined, self.r, self.s, |r| {
+            challenge(self.dealer, round1.blinder_generator, combined, r)
+        })
+    }
+}
+
+fn opening_point<G: Group + GroupEncoding + Default>(
+    dealer: usize,
+    blinder_generator: G,
+    pedersen_commitments: &[G],
+    feldman_commitments: &[G],
+) -> DkgResult<G::Scalar> {
+    let mut msg = Vec::new();
+    msg.extend_from_slice(&(dealer as u64).to_be_bytes());
+    for point in std::iter::once(&blinder_generator)
+        .chain(pedersen_commitments)
+        .chain(feldman_commitments)
+    {
+        msg.extend_from_slice(point.to_bytes().as_ref());
+    }
+    utils::hash_to_scalar(OPENING_POINT_DST, &msg)
+}
+
+fn challenge<G: Group + GroupEncoding + Default>(
+    dealer: usize,
+    blinder_generator: G,
+    combined: G,
+    r: G,
+) -> DkgResult<G::Scalar> {
+    primitives::challenge(
+        OPENING_CHALLENGE_DST,
+        &[dealer],
+        &[blinder_generator, combined, r],
+        &[],
+    )
+}
+
+/// The sum of `x^k * (pedersen_k - feldman_k)`
+fn combine<G: Group + GroupEncoding + Default>(
+    pedersen_commitments: &[G],
+    feldman_commitments: &[G],
+    x: G::Scalar,
+) -> G {
+    pedersen_commitments
+        .iter()
+        .zip(feldman_commitments)
+        .rev()
+        .fold(G::identity(), |acc, (p, f)| acc * x + (*p - *f))
+}
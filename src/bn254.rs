@@ -0,0 +1,39 @@
+//! Run the DKG on the BN254 G1 group so keys can be checked with the
+//! Ethereum `ecAdd`, `ecMul` and `ecPairing` precompiles.
+//!
+//! The participants are generic over any group so the points from
+//! `halo2curves` are used directly, this module re-exports the crate,
+//! names the participants and encodes points for the precompiles.
+pub use halo2curves;
+
+use crate::*;
+use halo2curves::{
+    bn256::{G1Affine, G1},
+    ff::PrimeField,
+    group::Curve,
+};
+
+/// A participant generating a new key on BN254 G1
+pub type Bn254SecretParticipant = SecretParticipant<G1>;
+/// A participant refreshing a key on BN254 G1
+pub type Bn254RefreshParticipant = RefreshParticipant<G1>;
+/// The parameters for a ceremony on BN254 G1
+pub type Bn254Parameters = Parameters<G1>;
+
+/// Encode `point` as the precompiles expect it, the big-endian
+/// affine `x` and `y` coordinates. The identity is all zeros.
+pub fn to_precompile_bytes(point: &G1) -> [u8; 64] {
+    let affine: G1Affine = point.to_affine();
+    let mut bytes = [0u8; 64];
+    if bool::from(point.is_identity()) {
+        return bytes;
+    }
+    for (out, coordinate) in bytes.chunks_exact_mut(32).zip([affine.x, affine.y]) {
+        // The field elements are encoded little-endian
+        let repr = coordinate.to_repr();
+        for (o, b) in out.iter_mut().zip(repr.as_ref().iter().rev()) {
+            *o = *b;
+        }
+    }
+    bytes
+}
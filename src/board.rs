@@ -0,0 +1,204 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// Broadcast data posted to a [`BulletinBoard`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum BoardMessage<G: Group + GroupEncoding + Default> {
+    /// Round 1 broadcast data
+    #[serde(bound(serialize = "Round1BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round1BroadcastData<G>: Deserialize<'de>"))]
+    Round1(Round1BroadcastData<G>),
+    /// Round 2 echo broadcast data
+    Round2(Round2EchoBroadcastData),
+    /// Round 3 broadcast data
+    #[serde(bound(serialize = "Round3BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round3BroadcastData<G>: Deserialize<'de>"))]
+    Round3(Round3BroadcastData<G>),
+    /// Round 4 echo broadcast data
+    #[serde(bound(serialize = "Round4EchoBroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round4EchoBroadcastData<G>: Deserialize<'de>"))]
+    Round4(Round4EchoBroadcastData<G>),
+}
+
+impl<G: Group + GroupEncoding + Default> BoardMessage<G> {
+    /// The round that produced this data
+    pub fn round(&self) -> Round {
+        match self {
+            Self::Round1(_) => Round::One,
+            Self::Round2(_) => Round::Two,
+            Self::Round3(_) => Round::Three,
+            Self::Round4(_) => Round::Four,
+        }
+    }
+}
+
+/// A message on a [`BulletinBoard`] at position `index`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BoardEntry<G: Group + GroupEncoding + Default> {
+    /// The position of the entry, starting at 0
+    #[serde(with = "ordinal")]
+    pub index: usize,
+    /// The participant that posted the entry
+    #[serde(with = "ordinal")]
+    pub sender: usize,
+    /// The posted data
+    #[serde(bound(serialize = "BoardMessage<G>: Serialize"))]
+    #[serde(bound(deserialize = "BoardMessage<G>: Deserialize<'de>"))]
+    pub message: BoardMessage<G>,
+}
+
+/// The broadcast channel the protocol assumes.
+///
+/// A board is an append-only log. Implementations must guarantee that all
+/// readers see the same entries in the same order and that a sender
+/// has at most one entry per round, so every participant computes
+/// the echo broadcast rounds from an identical view.
+/// Posting the same data again returns the original index, posting
+/// different data for a round already posted to is rejected with
+/// [`ParticipantError::ConflictingBroadcast`].
+pub trait BulletinBoard<G: Group + GroupEncoding + Default> {
+    /// Append `message` from `sender` and return its index
+    fn append(&mut self, sender: usize, message: BoardMessage<G>) -> DkgResult<usize>;
+
+    /// Read all entries at or after `index` in order
+    fn read_since(&self, index: usize) -> DkgResult<Vec<BoardEntry<G>>>;
+
+    /// Collect all the entries into the round inputs
+    fn archive(&self) -> DkgResult<BroadcastArchive<G>> {
+        let mut archive = BroadcastArchive::default();
+        for entry in self.read_since(0)? {
+            match entry.message {
+                BoardMessage::Round1(data) => {
+                    archive.round1_broadcast.insert(entry.sender, data);
+                }
+                BoardMessage::Round2(data) => {
+                    archive.round2_echo.insert(entry.sender, data);
+                }
+                BoardMessage::Round3(data) => {
+                    archive.round3_broadcast.insert(entry.sender, data);
+                }
+                BoardMessage::Round4(data) => {
+                    archive.round4_echo.insert(entry.sender, data);
+                }
+            }
+        }
+        Ok(archive)
+    }
+}
+
+/// A [`BulletinBoard`] kept in memory for ceremonies within one process
+#[derive(Clone, Debug, Default)]
+pub struct MemoryBulletinBoard<G: Group + GroupEncoding + Default> {
+    entries: Vec<BoardEntry<G>>,
+    posted: BTreeMap<(usize, Round), usize>,
+}
+
+impl<G: Group + GroupEncoding + Default> MemoryBulletinBoard<G> {
+    /// Create an empty board
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            posted: BTreeMap::new(),
+        }
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> BulletinBoard<G> for MemoryBulletinBoard<G> {
+    fn append(&mut self, sender: usize, message: BoardMessage<G>) -> DkgResult<usize> {
+        let round = message.round();
+        if let Some(index) = self.posted.get(&(sender, round)) {
+            let posted = &self.entries[*index].message;
+            if serialize_message(posted)? == serialize_message(&message)? {
+                return Ok(*index);
+            }
+            return Err(Error::SenderError {
+                round: round.into(),
+                error: ParticipantError::ConflictingBroadcast(sender),
+            });
+        }
+        let index = self.entries.len();
+        self.entries.push(BoardEntry {
+            index,
+            sender,
+            message,
+        });
+        self.posted.insert((sender, round), index);
+        Ok(index)
+    }
+
+    fn read_since(&self, index: usize) -> DkgResult<Vec<BoardEntry<G>>> {
+        Ok(self.entries.iter().skip(index).cloned().collect())
+    }
+}
+
+fn serialize_message<G: Group + GroupEncoding + Default>(
+    message: &BoardMessage<G>,
+) -> DkgResult<Vec<u8>> {
+    serde_bare::to_vec(message).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+/// A [`BulletinBoard`] served over HTTP.
+///
+/// The server must provide the guarantees of a [`BulletinBoard`] with two endpoints:
+/// - `POST {url}/entries` with a JSON body `{"sender": usize, "message": BoardMessage}`
+///   appends the message and responds with the JSON index, or `409 Conflict`
+///   if the sender posted different data for the round.
+/// - `GET {url}/entries?since={index}` responds with the JSON array of entries
+///   at or after `index`.
+#[cfg(feature = "http")]
+#[derive(Clone, Debug)]
+pub struct HttpBulletinBoard {
+    url: String,
+}
+
+#[cfg(feature = "http")]
+impl HttpBulletinBoard {
+    /// Create a board served at `url`
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+#[derive(Serialize)]
+struct HttpAppend<'a, G: Group + GroupEncoding + Default> {
+    #[serde(with = "ordinal")]
+    sender: usize,
+    #[serde(bound(serialize = "BoardMessage<G>: Serialize"))]
+    message: &'a BoardMessage<G>,
+}
+
+#[cfg(feature = "http")]
+impl<G: Group + GroupEncoding + Default> BulletinBoard<G> for HttpBulletinBoard {
+    fn append(&mut self, sender: usize, message: BoardMessage<G>) -> DkgResult<usize> {
+        let round = message.round();
+        let response = ureq::post(&format!("{}/entries", self.url)).send_json(HttpAppend {
+            sender,
+            message: &message,
+        });
+        match response {
+            Ok(response) => response.into_json::<usize>().map_err(Error::IoError),
+            Err(ureq::Error::Status(409, _)) => Err(Error::SenderError {
+                round: round.into(),
+                error: ParticipantError::ConflictingBroadcast(sender),
+            }),
+            Err(e) => Err(http_error(e)),
+        }
+    }
+
+    fn read_since(&self, index: usize) -> DkgResult<Vec<BoardEntry<G>>> {
+        ureq::get(&format!("{}/entries", self.url))
+            .query("since", &index.to_string())
+            .call()
+            .map_err(http_error)?
+            .into_json()
+            .map_err(Error::IoError)
+    }
+}
+
+#[cfg(feature = "http")]
+fn http_error(e: ureq::Error) -> Error {
+    Error::IoError(std::io::Error::new(std::io::ErrorKind::Other, e))
+}
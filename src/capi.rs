@@ -0,0 +1,353 @@
+//! C API for running a secp256k1 participant from C/C++.
+//!
+//! Build with `cargo build --release --features capi`, which produces
+//! both a shared and a static library, and generate the header with `cbindgen`.
+//!
+//! Every payload is encoded with serde_bare. Data received from several
+//! participants is passed as one encoded `BTreeMap<usize, Vec<u8>>` from
+//! sender id to that sender's message.
+//!
+//! Functions return [`GENNARO_OK`] on success, [`GENNARO_NULL_POINTER`]
+//! if a required pointer is null, [`GENNARO_NOT_AVAILABLE`] if the
+//! requested output isn't computed yet, otherwise `100 +` the [`crate::ErrorKind`]
+//! of the error.
+use crate::encoded::*;
+use crate::{Error, ErrorKind};
+use std::ptr;
+use std::slice;
+use zeroize::Zeroize;
+
+/// Success
+pub const GENNARO_OK: i32 = 0;
+/// A required pointer was null
+pub const GENNARO_NULL_POINTER: i32 = -1;
+/// The requested value is not available yet
+pub const GENNARO_NOT_AVAILABLE: i32 = -2;
+
+/// An opaque secp256k1 participant
+pub struct GennaroParticipant(EncodedParticipant);
+
+/// Bytes allocated by this library, free with [`gennaro_buffer_free`]
+#[repr(C)]
+pub struct GennaroBuffer {
+    /// The bytes
+    pub data: *mut u8,
+    /// The number of bytes
+    pub len: usize,
+}
+
+impl GennaroBuffer {
+    fn from_vec(value: Vec<u8>) -> Self {
+        let len = value.len();
+        let data = Box::into_raw(value.into_boxed_slice()) as *mut u8;
+        Self { data, len }
+    }
+}
+
+fn error_code(e: &Error) -> i32 {
+    100 + match e.kind() {
+        ErrorKind::Format => 0,
+        ErrorKind::Io => 1,
+        ErrorKind::Vsss => 2,
+        ErrorKind::Initialization => 3,
+        ErrorKind::InvalidParameters => 4,
+        ErrorKind::DuplicateShareId => 5,
+        ErrorKind::InvalidRound => 6,
+        ErrorKind::InvalidSender => 7,
+        ErrorKind::InsufficientData => 8,
+        ErrorKind::UnknownSession => 9,
+        ErrorKind::Serialization => 10,
+        ErrorKind::Round => 11,
+        ErrorKind::Aborted => 12,
+        ErrorKind::UnknownRound => 13,
+    }
+}
+
+unsafe fn input<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if data.is_null() {
+        if len == 0 {
+            Some(&[])
+        } else {
+            None
+        }
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn write_output(out: *mut GennaroBuffer, value: Vec<u8>) {
+    out.write(GennaroBuffer::from_vec(value));
+}
+
+/// Create a new participant, returns null if the parameters are invalid.
+/// Free with [`gennaro_participant_free`].
+#[no_mangle]
+pub extern "C" fn gennaro_participant_new(
+    id: usize,
+    threshold: usize,
+    limit: usize,
+) -> *mut GennaroParticipant {
+    match EncodedParticipant::new(id, threshold, limit) {
+        Ok(p) => Box::into_raw(Box::new(GennaroParticipant(p))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Restore a participant saved with [`gennaro_participant_to_bytes`],
+/// returns null if the bytes are invalid.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_from_bytes(
+    data: *const u8,
+    len: usize,
+) -> *mut GennaroParticipant {
+    match input(data, len).map(EncodedParticipant::from_bytes) {
+        Some(Ok(p)) => Box::into_raw(Box::new(GennaroParticipant(p))),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Free a participant
+///
+/// # Safety
+/// `participant` must be null or returned by this library and not freed already.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_free(participant: *mut GennaroParticipant) {
+    if !participant.is_null() {
+        drop(Box::from_raw(participant));
+    }
+}
+
+/// Zeroize and free bytes returned by this library
+///
+/// # Safety
+/// `buffer` must have been returned by this library and not freed already.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_buffer_free(buffer: GennaroBuffer) {
+    if !buffer.data.is_null() {
+        let mut bytes = Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len));
+        // Buffers can hold serialized participants with their shares
+        bytes.zeroize();
+    }
+}
+
+/// The participant's identifier, 0 if `participant` is null
+///
+/// # Safety
+/// `participant` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_id(participant: *const GennaroParticipant) -> usize {
+    participant.as_ref().map_or(0, |p| p.0.id())
+}
+
+/// The round the participant is waiting to compute, 0 if `participant` is null
+///
+/// # Safety
+/// `participant` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round(participant: *const GennaroParticipant) -> u8 {
+    participant.as_ref().map_or(0, |p| p.0.round().into())
+}
+
+/// Save the participant state, this contains secret data
+///
+/// # Safety
+/// `participant` must be valid and `out` writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_to_bytes(
+    participant: *const GennaroParticipant,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), false) = (participant.as_ref(), out.is_null()) else {
+        return GENNARO_NULL_POINTER;
+    };
+    match p.0.to_bytes() {
+        Ok(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Compute round 1. `broadcast` receives the message for all other participants
+/// and `p2p` the encoded map of messages to send to each participant by id.
+///
+/// # Safety
+/// `participant` must be valid and `broadcast` and `p2p` writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round1(
+    participant: *mut GennaroParticipant,
+    broadcast: *mut GennaroBuffer,
+    p2p: *mut GennaroBuffer,
+) -> i32 {
+    let Some(p) = participant.as_mut() else {
+        return GENNARO_NULL_POINTER;
+    };
+    if broadcast.is_null() || p2p.is_null() {
+        return GENNARO_NULL_POINTER;
+    }
+    let output = match p.0.round1() {
+        Ok(output) => output,
+        Err(e) => return error_code(&e),
+    };
+    match encode_map(&output.p2p) {
+        Ok(map) => {
+            write_output(broadcast, output.broadcast);
+            write_output(p2p, map);
+            GENNARO_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Compute round 2 with the encoded maps of round 1 broadcast and
+/// peer-to-peer messages, `out` receives the echo broadcast.
+///
+/// # Safety
+/// `participant` must be valid, the inputs must point to readable bytes
+/// of the given lengths and `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round2(
+    participant: *mut GennaroParticipant,
+    broadcast: *const u8,
+    broadcast_len: usize,
+    p2p: *const u8,
+    p2p_len: usize,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), Some(broadcast), Some(p2p), false) = (
+        participant.as_mut(),
+        input(broadcast, broadcast_len),
+        input(p2p, p2p_len),
+        out.is_null(),
+    ) else {
+        return GENNARO_NULL_POINTER;
+    };
+    let result = decode_map(broadcast).and_then(|broadcast| {
+        let p2p = decode_map(p2p)?;
+        p.0.round2(&broadcast, &p2p)
+    });
+    match result {
+        Ok(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Compute round 3 with the encoded map of round 2 echo broadcasts,
+/// `out` receives the broadcast.
+///
+/// # Safety
+/// `participant` must be valid, `data` must point to `len` readable bytes
+/// and `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round3(
+    participant: *mut GennaroParticipant,
+    data: *const u8,
+    len: usize,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), Some(data), false) = (participant.as_mut(), input(data, len), out.is_null())
+    else {
+        return GENNARO_NULL_POINTER;
+    };
+    match decode_map(data).and_then(|echo| p.0.round3(&echo)) {
+        Ok(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Compute round 4 with the encoded map of round 3 broadcasts,
+/// `out` receives the echo broadcast.
+///
+/// # Safety
+/// `participant` must be valid, `data` must point to `len` readable bytes
+/// and `out` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round4(
+    participant: *mut GennaroParticipant,
+    data: *const u8,
+    len: usize,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), Some(data), false) = (participant.as_mut(), input(data, len), out.is_null())
+    else {
+        return GENNARO_NULL_POINTER;
+    };
+    match decode_map(data).and_then(|broadcast| p.0.round4(&broadcast)) {
+        Ok(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Check the encoded map of round 4 echo broadcasts all computed the same public key
+///
+/// # Safety
+/// `participant` must be valid and `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_round5(
+    participant: *mut GennaroParticipant,
+    data: *const u8,
+    len: usize,
+) -> i32 {
+    let (Some(p), Some(data)) = (participant.as_mut(), input(data, len)) else {
+        return GENNARO_NULL_POINTER;
+    };
+    match decode_map(data).and_then(|echo| p.0.round5(&echo)) {
+        Ok(()) => GENNARO_OK,
+        Err(e) => error_code(&e),
+    }
+}
+
+/// Write the secret share to `out`, available after round 2
+///
+/// # Safety
+/// `participant` must be valid and `out` writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_secret_share(
+    participant: *const GennaroParticipant,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), false) = (participant.as_ref(), out.is_null()) else {
+        return GENNARO_NULL_POINTER;
+    };
+    match p.0.secret_share() {
+        Some(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        None => GENNARO_NOT_AVAILABLE,
+    }
+}
+
+/// Write the compressed public key to `out`, available after round 5
+///
+/// # Safety
+/// `participant` must be valid and `out` writable.
+#[no_mangle]
+pub unsafe extern "C" fn gennaro_participant_public_key(
+    participant: *const GennaroParticipant,
+    out: *mut GennaroBuffer,
+) -> i32 {
+    let (Some(p), false) = (participant.as_ref(), out.is_null()) else {
+        return GENNARO_NULL_POINTER;
+    };
+    match p.0.public_key() {
+        Some(bytes) => {
+            write_output(out, bytes);
+            GENNARO_OK
+        }
+        None => GENNARO_NOT_AVAILABLE,
+    }
+}
@@ -0,0 +1,194 @@
+use super::*;
+use std::collections::BTreeMap;
+use vsss_rs::elliptic_curve::ff::Field;
+
+const CERTIFICATE_DST: &[u8] = b"gennaro-dkg certificate";
+
+/// A Schnorr signature by one participant over the transcript hash
+/// and public key of a completed DKG, made with its secret share.
+///
+/// Produced by [`Participant::round5_signed`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CertificateSignature<G: Group + GroupEncoding + Default> {
+    /// The id of the signing participant
+    #[serde(with = "ordinal")]
+    pub signer: usize,
+    /// The threshold of the DKG
+    #[serde(with = "ordinal")]
+    pub threshold: usize,
+    /// The generator of the public key and public shares
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub generator: G,
+    /// The public key of the DKG
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The signer's secret share times the generator
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_share: G,
+    /// The hash of the signer's transcript
+    pub transcript_hash: [u8; 32],
+    /// The signature nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub r: G,
+    /// The signature response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub s: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> CertificateSignature<G> {
+    pub(crate) fn sign(
+        signer: usize,
+        threshold: usize,
+        generator: G,
+        public_key: G,
+        transcript_hash: [u8; 32],
+        secret_share: G::Scalar,
+    ) -> DkgResult<Self> {
+        let mut signature = Self {
+            signer,
+            threshold,
+            generator,
+            public_key,
+            public_share: generator * secret_share,
+            transcript_hash,
+            r: G::identity(),
+            s: G::Scalar::ZERO,
+        };
+        (signature.r, signature.s) =
+            primitives::prove(generator, secret_share, |r| signature.challenge(r))?;
+        Ok(signature)
+    }
+
+    /// Check the signature with the signer's public share
+    pub fn verify(&self) -> bool {
+        primitives::verify(self.generator, self.public_share, self.r, self.s, |r| {
+            self.challenge(r)
+        })
+    }
+
+    fn challenge(&self, r: G) -> DkgResult<G::Scalar> {
+        primitives::challenge(
+            CERTIFICATE_DST,
+            &[self.signer, self.threshold],
+            &[self.generator, self.public_key, self.public_share, r],
+            &self.transcript_hash,
+        )
+    }
+}
+
+/// A portable proof that at least `threshold` participants
+/// agreed on the public key and transcript of a DKG.
+///
+/// The public shares of the first `threshold` signers must interpolate
+/// to the public key and the public shares of every other signer,
+/// so all the signers hold shares of its secret.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DkgCertificate<G: Group + GroupEncoding + Default> {
+    /// The signatures keyed by signer
+    #[serde(bound(serialize = "CertificateSignature<G>: Serialize"))]
+    #[serde(bound(deserialize = "CertificateSignature<G>: Deserialize<'de>"))]
+    pub signatures: BTreeMap<usize, CertificateSignature<G>>,
+}
+
+impl<G: Group + GroupEncoding + Default> DkgCertificate<G> {
+    /// Combine the signatures of the participants into a certificate.
+    ///
+    /// Throws an error if the signatures don't verify, disagree on
+    /// what was signed or there are fewer than the threshold.
+    pub fn aggregate(signatures: &[CertificateSignature<G>]) -> DkgResult<Self> {
+        let mut certificate = Self {
+            signatures: BTreeMap::new(),
+        };
+        for signature in signatures {
+            if certificate
+                .signatures
+                .insert(signature.signer, signature.clone())
+                .is_some()
+            {
+                return Err(Error::InvalidParameters(format!(
+                    "duplicate signature from participant {}",
+                    signature.signer
+                )));
+            }
+        }
+        certificate.verify()?;
+        Ok(certificate)
+    }
+
+    /// The certified public key
+    pub fn public_key(&self) -> Option<G> {
+        self.signatures.values().next().map(|s| s.public_key)
+    }
+
+    /// The certified transcript hash
+    pub fn transcript_hash(&self) -> Option<[u8; 32]> {
+        self.signatures.values().next().map(|s| s.transcript_hash)
+    }
+
+    /// Check the certificate
+    pub fn verify(&self) -> DkgResult<()> {
+        let first = self
+            .signatures
+            .values()
+            .next()
+            .ok_or_else(|| Error::InvalidParameters("no signatures".to_string()))?;
+        for (id, signature) in &self.signatures {
+            if *id != signature.signer || *id == 0 {
+                return Err(Error::InvalidParameters(format!(
+                    "signature from participant {} is keyed by {}",
+                    signature.signer, id
+                )));
+            }
+            if signature.threshold != first.threshold
+                || signature.generator != first.generator
+                || signature.public_key != first.public_key
+                || signature.transcript_hash != first.transcript_hash
+            {
+                return Err(Error::InvalidParameters(format!(
+                    "participant {} signed a different key or transcript",
+                    id
+                )));
+            }
+            if !signature.verify() {
+                return Err(Error::InvalidParameters(format!(
+                    "invalid signature from participant {}",
+                    id
+                )));
+            }
+        }
+        if self.signatures.len() < first.threshold {
+            return Err(Error::InvalidParameters(format!(
+                "{} signatures are fewer than the threshold {}",
+                self.signatures.len(),
+                first.threshold
+            )));
+        }
+
+        let points = self
+            .signatures
+            .values()
+            .map(|s| (G::Scalar::from(s.signer as u64), s.public_share))
+            .collect::<Vec<_>>();
+        let (basis, others) = points.split_at(first.threshold);
+        if primitives::interpolate(basis, G::Scalar::ZERO) != Ok(first.public_key) {
+            return Err(Error::InvalidParameters(
+                "the public shares don't match the public key".to_string(),
+            ));
+        }
+        for ((x, public_share), signer) in others
+            .iter()
+            .zip(self.signatures.keys().skip(first.threshold))
+        {
+            if primitives::interpolate(basis, *x) != Ok(*public_share) {
+                return Err(Error::InvalidParameters(format!(
+                    "the public share of participant {} doesn't match the other signers",
+                    signer
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use super::*;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A source of time used to check round deadlines.
+///
+/// Replace the [`SystemClock`] with [`Participant::set_clock`]
+/// to control time in tests or simulations.
+pub trait Clock: Send + Sync {
+    /// The time elapsed since a fixed point in the past
+    fn now(&self) -> Duration;
+}
+
+/// A [`Clock`] reading the system time
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+/// Wrapper so participants can remain `Clone` and `Debug`
+#[derive(Clone)]
+pub(crate) struct ClockHandle(pub(crate) Arc<dyn Clock>);
+
+impl fmt::Debug for ClockHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Clock")
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use soteria_rs::Protected;
+use std::sync::{Arc, Mutex};
+
+/// The shares dealt by a participant to all participants
+/// kept in protected memory until they are no longer needed
+#[derive(Clone, Default, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub(crate) struct DealtShares {
+    pub(crate) secret_shares: Vec<InnerShare>,
+    pub(crate) blinder_shares: Vec<InnerShare>,
+}
+
+impl DealtShares {
+    pub(crate) fn protect(&self) -> DkgResult<Arc<Mutex<Protected>>> {
+        let protected = Protected::serde(self)
+            .map_err(|_e| Error::InitializationError("unable to protect shares".to_string()))?;
+        Ok(Arc::new(Mutex::new(protected)))
+    }
+
+    pub(crate) fn unprotect(input: &Arc<Mutex<Protected>>) -> DkgResult<Self> {
+        let mut protected = input
+            .lock()
+            .map_err(|_e| Error::InitializationError("unable to acquire lock".to_string()))?;
+        let unprotected = protected
+            .unprotect()
+            .ok_or_else(|| Error::InitializationError("memory tampered".to_string()))?;
+        unprotected
+            .serde::<Self>()
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Unprotect the shares for all `limit` participants needed to compute `round`.
+    ///
+    /// State saved before the shares were kept here deserializes without any,
+    /// so this throws an error instead of letting the round index past the end.
+    pub(crate) fn for_round(
+        input: &Arc<Mutex<Protected>>,
+        limit: usize,
+        round: Round,
+    ) -> DkgResult<Self> {
+        let shares = Self::unprotect(input)?;
+        if shares.secret_shares.len() != limit || shares.blinder_shares.len() != limit {
+            return Err(Error::RoundError(
+                round.into(),
+                "the dealt shares are missing".to_string(),
+            ));
+        }
+        Ok(shares)
+    }
+}
+
+pub fn empty() -> Arc<Mutex<Protected>> {
+    DealtShares::default()
+        .protect()
+        .expect("to protect empty shares")
+}
+
+pub fn serialize<S: Serializer>(input: &Arc<Mutex<Protected>>, s: S) -> Result<S::Ok, S::Error> {
+    DealtShares::unprotect(input)
+        .map_err(|e| ser::Error::custom(e.to_string()))?
+        .serialize(s)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Arc<Mutex<Protected>>, D::Error> {
+    let input = DealtShares::deserialize(d)?;
+    input.protect().map_err(serde::de::Error::custom)
+}
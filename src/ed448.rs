@@ -0,0 +1,16 @@
+//! Run the DKG on Ed448.
+//!
+//! The participants are generic over any group so the Edwards points from
+//! `ed448-goldilocks-plus` are used directly, this module re-exports the
+//! crate and names the participants for the curve.
+pub use ed448_goldilocks_plus;
+
+use crate::*;
+use ed448_goldilocks_plus::EdwardsPoint;
+
+/// A participant generating a new key on Ed448
+pub type Ed448SecretParticipant = SecretParticipant<EdwardsPoint>;
+/// A participant refreshing a key on Ed448
+pub type Ed448RefreshParticipant = RefreshParticipant<EdwardsPoint>;
+/// The parameters for a ceremony on Ed448
+pub type Ed448Parameters = Parameters<EdwardsPoint>;
@@ -0,0 +1,125 @@
+//! Participants whose inputs and outputs are serialized bytes,
+//! shared by the language bindings.
+//!
+//! All data is encoded with serde_bare and uses secp256k1.
+use super::*;
+use std::collections::BTreeMap;
+
+type G = k256::ProjectivePoint;
+
+fn encode<T: Serialize>(value: &T) -> DkgResult<Vec<u8>> {
+    serde_bare::to_vec(value).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+fn decode<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> DkgResult<T> {
+    serde_bare::from_slice(bytes).map_err(|e| Error::SerializationError(e.to_string()))
+}
+
+/// The output of round 1
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EncodedRound1 {
+    /// `Round1BroadcastData` to send to all other participants
+    pub(crate) broadcast: Vec<u8>,
+    /// `Round1P2PData` to send to each participant by id
+    pub(crate) p2p: BTreeMap<usize, Vec<u8>>,
+}
+
+pub(crate) struct EncodedParticipant {
+    inner: SecretParticipant<G>,
+}
+
+impl EncodedParticipant {
+    pub(crate) fn new(id: usize, threshold: usize, limit: usize) -> DkgResult<Self> {
+        let id = NonZeroUsize::new(id)
+            .ok_or_else(|| Error::InvalidParameters("id cannot be zero".to_string()))?;
+        let parameters = Parameters::<G>::builder()
+            .threshold(threshold)
+            .limit(limit)
+            .build()?;
+        Ok(Self {
+            inner: SecretParticipant::new(id, parameters)?,
+        })
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        Ok(Self {
+            inner: decode(bytes)?,
+        })
+    }
+
+    pub(crate) fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        encode(&self.inner)
+    }
+
+    pub(crate) fn id(&self) -> usize {
+        self.inner.get_id()
+    }
+
+    pub(crate) fn round(&self) -> Round {
+        self.inner.get_round()
+    }
+
+    pub(crate) fn round1(&mut self) -> DkgResult<EncodedRound1> {
+        let (broadcast, p2p) = self.inner.round1()?;
+        Ok(EncodedRound1 {
+            broadcast: encode(&broadcast)?,
+            p2p: p2p
+                .iter()
+                .map(|(id, data)| Ok((*id, encode(data)?)))
+                .collect::<DkgResult<_>>()?,
+        })
+    }
+
+    /// `broadcast` and `p2p` are the encoded data received from each participant
+    pub(crate) fn round2(
+        &mut self,
+        broadcast: &BTreeMap<usize, Vec<u8>>,
+        p2p: &BTreeMap<usize, Vec<u8>>,
+    ) -> DkgResult<Vec<u8>> {
+        let broadcast = decode_all(broadcast)?;
+        let p2p = decode_all(p2p)?;
+        encode(&self.inner.round2(broadcast, p2p)?)
+    }
+
+    pub(crate) fn round3(&mut self, echo: &BTreeMap<usize, Vec<u8>>) -> DkgResult<Vec<u8>> {
+        encode(&self.inner.round3(&decode_all(echo)?)?)
+    }
+
+    pub(crate) fn round4(&mut self, broadcast: &BTreeMap<usize, Vec<u8>>) -> DkgResult<Vec<u8>> {
+        encode(&self.inner.round4(&decode_all(broadcast)?)?)
+    }
+
+    pub(crate) fn round5(&mut self, echo: &BTreeMap<usize, Vec<u8>>) -> DkgResult<()> {
+        self.inner.round5(&decode_all(echo)?)
+    }
+
+    pub(crate) fn secret_share(&self) -> Option<Vec<u8>> {
+        self.inner
+            .get_secret_share()
+            .map(|s| s.to_repr().as_slice().to_vec())
+    }
+
+    pub(crate) fn public_key(&self) -> Option<Vec<u8>> {
+        self.inner
+            .get_public_key()
+            .map(|pk| pk.to_bytes().as_slice().to_vec())
+    }
+}
+
+/// Messages from several participants are passed as a single encoded
+/// map from participant id to the encoded message
+pub(crate) fn decode_map(bytes: &[u8]) -> DkgResult<BTreeMap<usize, Vec<u8>>> {
+    decode(bytes)
+}
+
+pub(crate) fn encode_map(map: &BTreeMap<usize, Vec<u8>>) -> DkgResult<Vec<u8>> {
+    encode(map)
+}
+
+fn decode_all<T: for<'de> Deserialize<'de>>(
+    map: &BTreeMap<usize, Vec<u8>>,
+) -> DkgResult<BTreeMap<usize, T>> {
+    map.iter()
+        .map(|(id, bytes)| Ok((*id, decode(bytes)?)))
+        .collect()
+}
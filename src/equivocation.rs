@@ -0,0 +1,56 @@
+use super::*;
+
+/// Two different messages received from the same sender for the same round.
+///
+/// Produced by [`SessionManager::receive`] when a sender delivers
+/// conflicting broadcast data. The messages carry no signatures so this
+/// is a local record, not proof: anyone can build a conflicting pair for
+/// any sender and [`EquivocationEvidence::check_conflict`] only checks
+/// the pair is consistent. It attributes the messages to the sender only
+/// for a receiver that trusts its transport to authenticate senders, for
+/// example with signed envelopes or a [`BulletinBoard`], and can't be used
+/// to convince a third party. Peer-to-peer data carries secret shares so
+/// conflicting peer-to-peer data is rejected without producing a record.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EquivocationEvidence<G: Group + GroupEncoding + Default> {
+    /// The participant that sent both messages
+    #[serde(with = "ordinal")]
+    pub sender: usize,
+    /// The message received first
+    #[serde(bound(serialize = "BoardMessage<G>: Serialize"))]
+    #[serde(bound(deserialize = "BoardMessage<G>: Deserialize<'de>"))]
+    pub first: BoardMessage<G>,
+    /// The conflicting message received later
+    #[serde(bound(serialize = "BoardMessage<G>: Serialize"))]
+    #[serde(bound(deserialize = "BoardMessage<G>: Deserialize<'de>"))]
+    pub second: BoardMessage<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> EquivocationEvidence<G> {
+    /// The round the messages were sent in
+    pub fn round(&self) -> Round {
+        self.first.round()
+    }
+
+    /// Check that both messages are for the same round and differ.
+    ///
+    /// This does not check who sent the messages.
+    pub fn check_conflict(&self) -> DkgResult<()> {
+        if self.first.round() != self.second.round() {
+            return Err(Error::InvalidParameters(format!(
+                "the messages from participant {} are for different rounds",
+                self.sender
+            )));
+        }
+        let encode = |message: &BoardMessage<G>| {
+            serde_bare::to_vec(message).map_err(|e| Error::SerializationError(e.to_string()))
+        };
+        if encode(&self.first)? == encode(&self.second)? {
+            return Err(Error::InvalidParameters(format!(
+                "the messages from participant {} are identical",
+                self.sender
+            )));
+        }
+        Ok(())
+    }
+}
@@ -16,9 +16,104 @@ pub enum Error {
     /// Errors during secret_participant initialization
     #[error("error during secret_participant creation: {0}")]
     InitializationError(String),
+    /// Errors in the parameters
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+    /// The same share identifier was supplied more than once,
+    /// the value is the position of the duplicate
+    #[error("duplicate share id at position {0}")]
+    DuplicateShareId(usize),
+    /// The parameters failed validation
+    #[error("invalid parameters: {0:?}")]
+    ParameterViolations(Vec<ParameterViolation>),
+    /// A round was called while the participant is in a different round
+    #[error("round {round} cannot be computed, participant is in round {current}")]
+    InvalidRound {
+        /// The round that was called
+        round: usize,
+        /// The round the participant is in
+        current: usize,
+    },
+    /// A specific sender supplied invalid data during a round
+    #[error("round {round} invalid data: {error}")]
+    SenderError {
+        /// The round in which the data was rejected
+        round: usize,
+        /// Why the sender's data was rejected
+        error: ParticipantError,
+    },
+    /// No session exists with the requested id
+    #[error("unknown session")]
+    UnknownSession,
+    /// Data could not be serialized or deserialized
+    #[error("serialization error: {0}")]
+    SerializationError(String),
+    /// Not enough data has been received yet to compute the round
+    #[error("round {0} insufficient data: `{1}`")]
+    InsufficientData(usize, String),
     /// Errors using rounds
     #[error("round {0} invalid input: `{1}`")]
     RoundError(usize, String),
+    /// The ceremony was stopped with [`crate::Participant::abort`]
+    #[error("the ceremony was aborted: {0}")]
+    Aborted(String),
+    /// A number doesn't match any [`crate::Round`]
+    #[error("unknown round {0}")]
+    UnknownRound(String),
+}
+
+impl Error {
+    /// The machine-readable category of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::FmtError(_) => ErrorKind::Format,
+            Self::IoError(_) => ErrorKind::Io,
+            Self::VsssError(_) => ErrorKind::Vsss,
+            Self::InitializationError(_) => ErrorKind::Initialization,
+            Self::InvalidParameters(_) | Self::ParameterViolations(_) => {
+                ErrorKind::InvalidParameters
+            }
+            Self::DuplicateShareId(_) => ErrorKind::DuplicateShareId,
+            Self::InvalidRound { .. } => ErrorKind::InvalidRound,
+            Self::SenderError { .. } => ErrorKind::InvalidSender,
+            Self::InsufficientData(_, _) => ErrorKind::InsufficientData,
+            Self::UnknownSession => ErrorKind::UnknownSession,
+            Self::SerializationError(_) => ErrorKind::Serialization,
+            Self::RoundError(_, _) => ErrorKind::Round,
+            Self::Aborted(_) => ErrorKind::Aborted,
+            Self::UnknownRound(_) => ErrorKind::UnknownRound,
+        }
+    }
+
+    /// How a driver should react to this error
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::SenderError { .. } => Severity::SenderFault,
+            Self::InsufficientData(_, _) => Severity::Retryable,
+            // Called before the participant reached the round
+            Self::InvalidRound { round, current } if round > current => Severity::Retryable,
+            _ => Severity::Fatal,
+        }
+    }
+
+    /// The id of the participant that caused this error, if known
+    pub fn sender(&self) -> Option<usize> {
+        match self {
+            Self::SenderError { error, .. } => Some(error.participant_id()),
+            _ => None,
+        }
+    }
+
+    /// The round in which this error occurred, if any
+    pub fn round(&self) -> Option<usize> {
+        match self {
+            Self::InvalidRound { round, .. }
+            | Self::SenderError { round, .. }
+            | Self::InsufficientData(round, _)
+            | Self::RoundError(round, _) => Some(*round),
+            _ => None,
+        }
+    }
 }
 
 impl From<vsss_rs::Error> for Error {
@@ -27,11 +122,55 @@ impl From<vsss_rs::Error> for Error {
     }
 }
 
+/// The category of an [`Error`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ErrorKind {
+    /// Formatting failed
+    Format,
+    /// An io operation failed
+    Io,
+    /// The secret sharing scheme failed
+    Vsss,
+    /// The participant could not be created
+    Initialization,
+    /// The parameters are invalid
+    InvalidParameters,
+    /// A share identifier was used more than once
+    DuplicateShareId,
+    /// A round was called out of order
+    InvalidRound,
+    /// A specific sender supplied invalid data, see [`Error::sender`]
+    InvalidSender,
+    /// Not enough data has been received yet
+    InsufficientData,
+    /// No session exists with the requested id
+    UnknownSession,
+    /// Data could not be serialized or deserialized
+    Serialization,
+    /// The round could not be completed with the given input
+    Round,
+    /// The ceremony was aborted
+    Aborted,
+    /// A number doesn't match any round
+    UnknownRound,
+}
+
+/// How severe an [`Error`] is for the ceremony
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Severity {
+    /// The ceremony cannot continue and must be aborted
+    Fatal,
+    /// The sender should be dropped and the ceremony can continue without them
+    SenderFault,
+    /// The input arrived too early, retry once more data is available
+    Retryable,
+}
+
 /// Dkg results
 pub type DkgResult<T> = anyhow::Result<T, Error>;
 
 /// Detailed errors to describe problems that occurred with specific participants
-#[derive(DError, Debug, Deserialize, Serialize)]
+#[derive(DError, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ParticipantError {
     /// Round 2 - didn't receive any p2p data from secret_participant
     #[error("secret_participant {0} has broadcast data but no peer-to-peer data")]
@@ -66,4 +205,103 @@ pub enum ParticipantError {
     /// Participant has identity elements for feldman commitments
     #[error("secret_participant {0} has identity element feldman commitments")]
     IdentityElementFeldmanCommitments(usize),
+    /// Round 3 - participant reported a different valid set
+    #[error("secret_participant {0} reported a different set of valid participants")]
+    MismatchedValidParticipants(usize),
+    /// Round 5 - participant computed a different public key
+    #[error("secret_participant {0} computed a different public key")]
+    MismatchedPublicKey(usize),
+    /// Participant did not send data before the round was forced to advance
+    #[error("secret_participant {0} did not send data before the round was forced")]
+    Absent(usize),
+    /// Round 3 - participant is not in the valid set reported by another participant
+    #[error("secret_participant {0} is not in the valid set reported by another participant")]
+    NotCommonlyValid(usize),
+    /// Participant sent different data for the same round to a [`crate::BulletinBoard`]
+    /// or [`crate::SessionManager`]
+    #[error("secret_participant {0} posted conflicting broadcast data")]
+    ConflictingBroadcast(usize),
+    /// Round 4 - participant committed to a different key than the imported key
+    #[error("secret_participant {0} committed to a different key than the imported key")]
+    MismatchedImportedKey(usize),
+    /// Participant sent more messages for a round than a [`crate::SessionManager`] accepts
+    #[error("secret_participant {0} sent too many messages for the round")]
+    TooManyMessages(usize),
+    /// Round 0 - participant sent an invalid encryption key
+    #[error("secret_participant {0} sent an invalid encryption key")]
+    InvalidEncryptionKey(usize),
+    /// Round 2 - participant sent a zero secret proof that doesn't verify
+    #[error("secret_participant {0} sent an invalid zero secret proof")]
+    InvalidZeroSecretProof(usize),
+    /// Participant confirmed a different transcript on the fast path
+    #[error("secret_participant {0} confirmed a different transcript")]
+    MismatchedTranscript(usize),
+}
+
+impl ParticipantError {
+    /// The id of the participant that caused the error
+    pub fn participant_id(&self) -> usize {
+        match self {
+            Self::MissingP2PData(id)
+            | Self::MissingBroadcastData(id)
+            | Self::MismatchedParameters(id)
+            | Self::IdentityElementPedersenCommitments(id)
+            | Self::ZeroValueShares(id)
+            | Self::NoVerifyShares(id)
+            | Self::BadFormatShare(id)
+            | Self::UnexpectedBroadcast(id)
+            | Self::MissingP2PDataRound1(id)
+            | Self::MissingBroadcastDataRound1(id)
+            | Self::IdentityElementFeldmanCommitments(id)
+            | Self::MismatchedValidParticipants(id)
+            | Self::MismatchedPublicKey(id)
+            | Self::Absent(id)
+            | Self::NotCommonlyValid(id)
+            | Self::ConflictingBroadcast(id)
+            | Self::MismatchedImportedKey(id)
+            | Self::TooManyMessages(id)
+            | Self::InvalidEncryptionKey(id)
+            | Self::InvalidZeroSecretProof(id)
+            | Self::MismatchedTranscript(id) => *id,
+        }
+    }
+}
+
+/// Problems found when validating [`crate::Parameters`]
+#[derive(DError, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ParameterViolation {
+    /// The threshold must be at least 1
+    #[error("threshold {0} is less than 1")]
+    ThresholdTooSmall(usize),
+    /// The threshold is below the minimum of the [`crate::ThresholdPolicy`]
+    #[error("threshold {threshold} is below the policy minimum {minimum}")]
+    ThresholdBelowPolicy {
+        /// The threshold
+        threshold: usize,
+        /// The minimum required by the policy
+        minimum: usize,
+    },
+    /// The threshold cannot be greater than the limit
+    #[error("threshold {threshold} is greater than limit {limit}")]
+    ThresholdExceedsLimit {
+        /// The threshold
+        threshold: usize,
+        /// The limit
+        limit: usize,
+    },
+    /// Share identifiers are a single byte so the limit cannot exceed 255
+    #[error("limit {0} is greater than 255")]
+    LimitTooLarge(usize),
+    /// The message generator is the identity element
+    #[error("message generator is the identity element")]
+    IdentityMessageGenerator,
+    /// The blinder generator is the identity element
+    #[error("blinder generator is the identity element")]
+    IdentityBlinderGenerator,
+    /// The message and blinder generators are the same
+    #[error("message and blinder generators are equal")]
+    EqualGenerators,
+    /// At least one secret must be generated
+    #[error("secret count is 0")]
+    NoSecrets,
 }
@@ -0,0 +1,113 @@
+//! Anchor [`BulletinBoard`] entries in an append-only contract
+//! so a chain is the consistent broadcast layer.
+//!
+//! Only the hash of each entry is posted. The data itself is carried by
+//! any other board, each reader checks the entries it reads against the
+//! contract before using them. The contract keeps the first hash posted
+//! for each ceremony, sender and round and emits an `Equivocation` event
+//! when a different hash is posted later, which is on-chain evidence
+//! that the sender equivocated. The contract must only accept posts for
+//! a sender from the address registered for it.
+//!
+//! The expected contract interface is
+//! ```solidity
+//! function post(bytes32 ceremony, uint64 sender, uint8 round, bytes32 hash) external;
+//! function hashOf(bytes32 ceremony, uint64 sender, uint8 round) external view returns (bytes32);
+//! event Equivocation(bytes32 indexed ceremony, uint64 indexed sender, uint8 round, bytes32 first, bytes32 second);
+//! ```
+use crate::*;
+use ethers::{contract::abigen, providers::Middleware, types::Address};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+abigen!(
+    BulletinContract,
+    r#"[
+        function post(bytes32 ceremony, uint64 sender, uint8 round, bytes32 hash) external
+        function hashOf(bytes32 ceremony, uint64 sender, uint8 round) external view returns (bytes32)
+        event Equivocation(bytes32 indexed ceremony, uint64 indexed sender, uint8 round, bytes32 first, bytes32 second)
+    ]"#
+);
+
+/// Posts and checks the hashes of board entries for one ceremony
+#[derive(Clone, Debug)]
+pub struct EvmAnchor<M: Middleware> {
+    contract: BulletinContract<M>,
+    ceremony: [u8; 32],
+}
+
+impl<M: Middleware + 'static> EvmAnchor<M> {
+    /// Use the contract at `address` for the ceremony identified by `ceremony`
+    pub fn new(address: Address, client: Arc<M>, ceremony: [u8; 32]) -> Self {
+        Self {
+            contract: BulletinContract::new(address, client),
+            ceremony,
+        }
+    }
+
+    /// Post the hash of `entry`.
+    ///
+    /// Throws [`ParticipantError::ConflictingBroadcast`] if the sender
+    /// already posted a different hash for the round.
+    pub async fn post<G: Group + GroupEncoding + Default>(
+        &self,
+        entry: &BoardEntry<G>,
+    ) -> DkgResult<()> {
+        let round = u8::from(entry.message.round());
+        let hash = entry_hash(entry)?;
+        self.contract
+            .post(self.ceremony, entry.sender as u64, round, hash)
+            .send()
+            .await
+            .map_err(evm_error)?
+            .await
+            .map_err(evm_error)?;
+        self.check(entry).await
+    }
+
+    /// Check that `entry` matches the hash posted by its sender.
+    ///
+    /// Throws [`ParticipantError::ConflictingBroadcast`] if it doesn't
+    /// and [`Error::InsufficientData`] if nothing was posted yet.
+    pub async fn check<G: Group + GroupEncoding + Default>(
+        &self,
+        entry: &BoardEntry<G>,
+    ) -> DkgResult<()> {
+        let round = entry.message.round();
+        let posted = self
+            .contract
+            .hash_of(self.ceremony, entry.sender as u64, u8::from(round))
+            .call()
+            .await
+            .map_err(evm_error)?;
+        if posted == [0u8; 32] {
+            return Err(Error::InsufficientData(
+                round.into(),
+                format!("participant {} has not posted a hash", entry.sender),
+            ));
+        }
+        if posted != entry_hash(entry)? {
+            return Err(Error::SenderError {
+                round: round.into(),
+                error: ParticipantError::ConflictingBroadcast(entry.sender),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The hash posted for `entry`, the SHA-256 of its sender and message
+pub fn entry_hash<G: Group + GroupEncoding + Default>(
+    entry: &BoardEntry<G>,
+) -> DkgResult<[u8; 32]> {
+    let message =
+        serde_bare::to_vec(&entry.message).map_err(|e| Error::SerializationError(e.to_string()))?;
+    let mut hasher = Sha256::new();
+    hasher.update((entry.sender as u64).to_be_bytes());
+    hasher.update(message);
+    Ok(hasher.finalize().into())
+}
+
+fn evm_error<E: std::error::Error + Send + Sync + 'static>(e: E) -> Error {
+    Error::IoError(std::io::Error::new(std::io::ErrorKind::Other, e))
+}
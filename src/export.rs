@@ -0,0 +1,305 @@
+use super::*;
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use vsss_rs::elliptic_curve::ff::Field;
+
+const EXPORT_DST: &[u8] = b"gennaro-dkg export";
+
+/// A request to reconstruct the key of a completed DKG for a designated recipient.
+///
+/// The request is bound to the key and transcript hash so an approval
+/// cannot be replayed for another key or another recipient.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRequest<G: Group + GroupEncoding + Default> {
+    /// The public key to export
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The transcript hash of the DKG that produced the key
+    pub transcript_hash: [u8; 32],
+    /// Who receives the key
+    pub recipient: String,
+    /// Why the key is exported
+    pub reason: String,
+    /// Makes each request unique
+    pub nonce: [u8; 32],
+}
+
+impl<G: Group + GroupEncoding + Default> ExportRequest<G> {
+    /// Create a request with a random nonce
+    pub fn new<S: Into<String>, R: Into<String>>(
+        public_key: G,
+        transcript_hash: [u8; 32],
+        recipient: S,
+        reason: R,
+    ) -> Self {
+        let mut nonce = [0u8; 32];
+        rand_core::OsRng.fill_bytes(&mut nonce);
+        Self {
+            public_key,
+            transcript_hash,
+            recipient: recipient.into(),
+            reason: reason.into(),
+            nonce,
+        }
+    }
+
+    /// The SHA-256 hash of the request that approvals sign
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.public_key.to_bytes().as_ref());
+        hasher.update(self.transcript_hash);
+        hasher.update((self.recipient.len() as u64).to_be_bytes());
+        hasher.update(self.recipient.as_bytes());
+        hasher.update((self.reason.len() as u64).to_be_bytes());
+        hasher.update(self.reason.as_bytes());
+        hasher.update(self.nonce);
+        hasher.finalize().into()
+    }
+}
+
+/// A Schnorr signature by one participant over an [`ExportRequest`],
+/// made with its secret share.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportApproval<G: Group + GroupEncoding + Default> {
+    /// The id of the approving participant
+    #[serde(with = "ordinal")]
+    pub signer: usize,
+    /// The threshold of the DKG
+    #[serde(with = "ordinal")]
+    pub threshold: usize,
+    /// The generator of the public key and public shares
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub generator: G,
+    /// The signer's secret share times the generator
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_share: G,
+    /// The hash of the approved request
+    pub request_hash: [u8; 32],
+    /// The signature nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub r: G,
+    /// The signature response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub s: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> ExportApproval<G> {
+    /// Check the signature with the signer's public share
+    pub fn verify(&self) -> bool {
+        primitives::verify(self.generator, self.public_share, self.r, self.s, |r| {
+            self.challenge(r)
+        })
+    }
+
+    fn challenge(&self, r: G) -> DkgResult<G::Scalar> {
+        primitives::challenge(
+            EXPORT_DST,
+            &[self.signer, self.threshold],
+            &[self.generator, self.public_share, r],
+            &self.request_hash,
+        )
+    }
+}
+
+/// An approval and the secret share it reveals.
+///
+/// Must only be sent to the recipient of the request over a private channel.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExportShare<G: Group + GroupEncoding + Default> {
+    /// The signed approval
+    #[serde(bound(serialize = "ExportApproval<G>: Serialize"))]
+    #[serde(bound(deserialize = "ExportApproval<G>: Deserialize<'de>"))]
+    pub approval: ExportApproval<G>,
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    share: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for ExportShare<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExportShare")
+            .field("approval", &self.approval)
+            .field("share", &"<redacted>")
+            .finish()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for ExportShare<G> {
+    fn drop(&mut self) {
+        utils::zeroize_scalar(&mut self.share);
+    }
+}
+
+/// The auditable record of a key export, it contains no secrets.
+///
+/// Proves that the approving participants, at least the threshold,
+/// signed the request and hold shares of the exported key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportRecord<G: Group + GroupEncoding + Default> {
+    /// The approved request
+    #[serde(bound(serialize = "ExportRequest<G>: Serialize"))]
+    #[serde(bound(deserialize = "ExportRequest<G>: Deserialize<'de>"))]
+    pub request: ExportRequest<G>,
+    /// The approvals keyed by signer
+    #[serde(bound(serialize = "ExportApproval<G>: Serialize"))]
+    #[serde(bound(deserialize = "ExportApproval<G>: Deserialize<'de>"))]
+    pub approvals: BTreeMap<usize, ExportApproval<G>>,
+}
+
+impl<G: Group + GroupEncoding + Default> ExportRecord<G> {
+    /// Check the record
+    pub fn verify(&self) -> DkgResult<()> {
+        let request_hash = self.request.hash();
+        let first = self
+            .approvals
+            .values()
+            .next()
+            .ok_or_else(|| Error::InvalidParameters("no approvals".to_string()))?;
+        for (id, approval) in &self.approvals {
+            if *id != approval.signer || *id == 0 {
+                return Err(Error::InvalidParameters(format!(
+                    "approval from participant {} is keyed by {}",
+                    approval.signer, id
+                )));
+            }
+            if approval.request_hash != request_hash
+                || approval.threshold != first.threshold
+                || approval.generator != first.generator
+            {
+                return Err(Error::InvalidParameters(format!(
+                    "participant {} approved a different request",
+                    id
+                )));
+            }
+            if !approval.verify() {
+                return Err(Error::InvalidParameters(format!(
+                    "invalid approval from participant {}",
+                    id
+                )));
+            }
+        }
+        if self.approvals.len() < first.threshold {
+            return Err(Error::InvalidParameters(format!(
+                "{} approvals are fewer than the threshold {}",
+                self.approvals.len(),
+                first.threshold
+            )));
+        }
+        let points = self
+            .approvals
+            .values()
+            .map(|a| (G::Scalar::from(a.signer as u64), a.public_share))
+            .collect::<Vec<_>>();
+        let (basis, others) = points.split_at(first.threshold);
+        if primitives::interpolate(basis, G::Scalar::ZERO) != Ok(self.request.public_key) {
+            return Err(Error::InvalidParameters(
+                "the public shares don't match the public key".to_string(),
+            ));
+        }
+        for ((x, public_share), signer) in others
+            .iter()
+            .zip(self.approvals.keys().skip(first.threshold))
+        {
+            if primitives::interpolate(basis, *x) != Ok(*public_share) {
+                return Err(Error::InvalidParameters(format!(
+                    "the public share of participant {} doesn't match the other approvals",
+                    signer
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reconstruct the key from the shares revealed to the recipient
+    /// of `request` and return it with the record of the export.
+    ///
+    /// Throws an error if there are fewer valid shares than the threshold
+    /// or they don't reconstruct the requested key.
+    pub fn reconstruct(
+        request: ExportRequest<G>,
+        shares: &[ExportShare<G>],
+    ) -> DkgResult<(G::Scalar, Self)> {
+        let mut record = Self {
+            request,
+            approvals: BTreeMap::new(),
+        };
+        let mut secrets = BTreeMap::new();
+        for share in shares {
+            let approval = &share.approval;
+            if approval.generator * share.share != approval.public_share {
+                return Err(Error::InvalidParameters(format!(
+                    "participant {} revealed a share that doesn't match its approval",
+                    approval.signer
+                )));
+            }
+            if record
+                .approvals
+                .insert(approval.signer, approval.clone())
+                .is_some()
+            {
+                return Err(Error::InvalidParameters(format!(
+                    "duplicate approval from participant {}",
+                    approval.signer
+                )));
+            }
+            secrets.insert(approval.signer, share.share);
+        }
+        record.verify()?;
+
+        let points = secrets
+            .iter()
+            .map(|(id, share)| (G::Scalar::from(*id as u64), *share))
+            .collect::<Vec<_>>();
+        let mut key = primitives::interpolate(&points, G::Scalar::ZERO)
+            .map_err(|_| Error::InvalidParameters("duplicate approval".to_string()))?;
+        let generator = shares[0].approval.generator;
+        if generator * key != record.request.public_key {
+            utils::zeroize_scalar(&mut key);
+            return Err(Error::InvalidParameters(
+                "the shares don't reconstruct the public key".to_string(),
+            ));
+        }
+        Ok((key, record))
+    }
+}
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Approve `request` and reveal this participant's secret share
+    /// to its recipient, see [`ExportRecord::reconstruct`].
+    ///
+    /// Throws an error if the ceremony is not complete or the request
+    /// is for a different key or transcript.
+    pub fn approve_export(&self, request: &ExportRequest<G>) -> DkgResult<ExportShare<G>> {
+        if !self.completed() {
+            return Err(self.invalid_round(Round::Five));
+        }
+        if Some(request.public_key) != self.get_public_key()
+            || request.transcript_hash != self.get_transcript_hash()
+        {
+            return Err(Error::InvalidParameters(
+                "the export request is for a different key or transcript".to_string(),
+            ));
+        }
+        let share = self.computed_secret_share().ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid secret share".to_string())
+        })?;
+        let generator = self.parameters().message_generator;
+        let mut approval = ExportApproval {
+            signer: self.get_id(),
+            threshold: self.get_threshold(),
+            generator,
+            public_share: generator * share,
+            request_hash: request.hash(),
+            r: G::identity(),
+            s: G::Scalar::ZERO,
+        };
+        (approval.r, approval.s) = primitives::prove(generator, share, |r| approval.challenge(r))?;
+        Ok(ExportShare { approval, share })
+    }
+}
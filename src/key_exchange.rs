@@ -0,0 +1,301 @@
+use super::*;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use vsss_rs::elliptic_curve::ff::Field;
+
+const PAIRWISE_KEY_DST: &[u8] = b"gennaro-dkg pairwise key";
+const ENCRYPTION_KEY_DST: &[u8] = b"gennaro-dkg encryption key";
+const NONCE_LENGTH: usize = 12;
+
+/// Round 0 broadcast data with the sender's ephemeral encryption key
+/// signed with its long-term key
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Round0BroadcastData<G: Group + GroupEncoding + Default> {
+    /// The ephemeral encryption key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub encryption_key: G,
+    /// The signature nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub r: G,
+    /// The signature response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub s: G::Scalar,
+}
+
+/// An optional round before round 1 where the participants exchange
+/// ephemeral encryption keys to derive a 32 byte key with each other
+/// participant, used to seal the [`Round1P2PData`] sent to them
+/// with [`PairwiseKeys::seal`].
+///
+/// Each participant signs its encryption key with a long-term signing key.
+/// The matching verifying key, `message_generator * signing_key` encoded
+/// with [`GroupEncoding`], must be set for every other participant in the
+/// [`ParticipantRegistry`] before the keys are exchanged, otherwise a
+/// network attacker could substitute its own keys and read the shares.
+/// The keys are also absorbed into the transcript of the participant so
+/// comparing transcript hashes at the end of the ceremony confirms every
+/// participant used the same keys.
+pub struct KeyExchange<G: Group + GroupEncoding + Default> {
+    id: usize,
+    generator: G,
+    secret: G::Scalar,
+    round0: Round0BroadcastData<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for KeyExchange<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyExchange")
+            .field("id", &self.id)
+            .field("encryption_key", &self.round0.encryption_key)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for KeyExchange<G> {
+    fn drop(&mut self) {
+        utils::zeroize_scalar(&mut self.secret);
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> KeyExchange<G> {
+    /// Create an ephemeral key for `participant` before it computes round 1
+    /// and sign it with the participant's long-term `signing_key`
+    pub fn new<I: ParticipantImpl<G> + Default>(
+        participant: &Participant<I, G>,
+        signing_key: &G::Scalar,
+    ) -> DkgResult<Self> {
+        let generator = participant.parameters().message_generator;
+        let secret = G::Scalar::random(rand_core::OsRng);
+        let encryption_key = generator * secret;
+        let context = participant.transcript().setup_hash();
+        let (r, s) = primitives::prove(generator, *signing_key, |r| {
+            challenge(participant.get_id(), &context, generator, encryption_key, r)
+        })?;
+        Ok(Self {
+            id: participant.get_id(),
+            generator,
+            secret,
+            round0: Round0BroadcastData {
+                encryption_key,
+                r,
+                s,
+            },
+        })
+    }
+
+    /// The data to send to all other participants
+    pub fn round0(&self) -> Round0BroadcastData<G> {
+        self.round0.clone()
+    }
+
+    /// Derive a key with each participant in `broadcast_data` and
+    /// absorb all the encryption keys into the transcript of `participant`.
+    ///
+    /// Throws [`ParticipantError::InvalidEncryptionKey`] if a key is the identity,
+    /// its signature doesn't verify with the sender's verifying key or the sender
+    /// is not a participant. Throws an error if a sender has no verifying key
+    /// in the registry or `participant` already computed round 1.
+    pub fn finish<I: ParticipantImpl<G> + Default>(
+        self,
+        participant: &mut Participant<I, G>,
+        broadcast_data: &BTreeMap<usize, Round0BroadcastData<G>>,
+    ) -> DkgResult<PairwiseKeys> {
+        if participant.get_round() != Round::One || participant.get_id() != self.id {
+            return Err(participant.invalid_round(Round::Zero));
+        }
+        let context = participant.transcript().setup_hash();
+        let mut keys = BTreeMap::new();
+        for (id, data) in broadcast_data.iter().filter(|(id, _)| **id != self.id) {
+            let invalid = Error::SenderError {
+                round: Round::Zero.into(),
+                error: ParticipantError::InvalidEncryptionKey(*id),
+            };
+            if *id == 0 || *id > participant.get_limit() || data.encryption_key.is_identity().into()
+            {
+                return Err(invalid);
+            }
+            let verifying_key = participant
+                .get_registry()
+                .get(*id)
+                .and_then(|identity| identity.verifying_key.as_deref())
+                .and_then(decode_point::<G>)
+                .ok_or_else(|| {
+                    Error::InvalidParameters(format!(
+                        "participant {} has no verifying key in the registry",
+                        id
+                    ))
+                })?;
+            if !primitives::verify(self.generator, verifying_key, data.r, data.s, |r| {
+                challenge(*id, &context, self.generator, data.encryption_key, r)
+            }) {
+                return Err(invalid);
+            }
+            keys.insert(*id, data.encryption_key);
+        }
+        keys.insert(self.id, self.round0.encryption_key);
+        for (id, key) in &keys {
+            participant
+                .transcript_mut()
+                .append_points(Round::Zero, *id, "encryption_key", &[*key]);
+        }
+
+        let pairwise = keys
+            .iter()
+            .filter(|(id, _)| **id != self.id)
+            .map(|(id, key)| {
+                let (low, high) = if *id < self.id {
+                    ((*id, *key), (self.id, self.round0.encryption_key))
+                } else {
+                    ((self.id, self.round0.encryption_key), (*id, *key))
+                };
+                let mut hasher = Sha256::new();
+                hasher.update(PAIRWISE_KEY_DST);
+                for (id, key) in [low, high] {
+                    hasher.update((id as u32).to_be_bytes());
+                    hasher.update(key.to_bytes().as_ref());
+                }
+                hasher.update((*key * self.secret).to_bytes().as_ref());
+                (*id, hasher.finalize().into())
+            })
+            .collect();
+        Ok(PairwiseKeys {
+            id: self.id,
+            keys: pairwise,
+        })
+    }
+}
+
+/// The keys derived by [`KeyExchange::finish`] keyed by the other participant's id
+#[derive(Clone)]
+pub struct PairwiseKeys {
+    id: usize,
+    keys: BTreeMap<usize, [u8; 32]>,
+}
+
+impl fmt::Debug for PairwiseKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.keys.keys()).finish()
+    }
+}
+
+impl Drop for PairwiseKeys {
+    fn drop(&mut self) {
+        self.keys.values_mut().for_each(|key| key.zeroize());
+    }
+}
+
+impl PairwiseKeys {
+    /// The key shared with participant `id`
+    pub fn get(&self, id: usize) -> Option<&[u8; 32]> {
+        self.keys.get(&id)
+    }
+
+    /// The ids of the participants a key is shared with
+    pub fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.keys.keys().copied()
+    }
+
+    /// Encrypt the round 1 peer data for participant `recipient`.
+    ///
+    /// The ciphertext can be sent over an untrusted channel and is
+    /// opened by the recipient with [`PairwiseKeys::open`].
+    pub fn seal(&self, recipient: usize, data: &Round1P2PData) -> DkgResult<Vec<u8>> {
+        let cipher = self.cipher(recipient)?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand_core::OsRng.fill_bytes(&mut nonce);
+        let mut plaintext =
+            serde_bare::to_vec(data).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let ciphertext = cipher.encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &associated_data(self.id, recipient),
+            },
+        );
+        plaintext.zeroize();
+        let ciphertext = ciphertext.map_err(|_| {
+            Error::InvalidParameters(format!("unable to seal the data for {}", recipient))
+        })?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt the round 1 peer data sealed by participant `sender`.
+    ///
+    /// Throws [`ParticipantError::InvalidEncryptionKey`] if the data
+    /// wasn't sealed by `sender` for this participant or was modified.
+    pub fn open(&self, sender: usize, sealed: &[u8]) -> DkgResult<Round1P2PData> {
+        let cipher = self.cipher(sender)?;
+        let invalid = || Error::SenderError {
+            round: Round::One.into(),
+            error: ParticipantError::InvalidEncryptionKey(sender),
+        };
+        if sealed.len() < NONCE_LENGTH {
+            return Err(invalid());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &associated_data(sender, self.id),
+                },
+            )
+            .map_err(|_| invalid())?;
+        let data = serde_bare::from_slice(&plaintext).map_err(|_| invalid());
+        plaintext.zeroize();
+        data
+    }
+
+    fn cipher(&self, id: usize) -> DkgResult<ChaCha20Poly1305> {
+        self.keys
+            .get(&id)
+            .map(|key| ChaCha20Poly1305::new(Key::from_slice(key)))
+            .ok_or_else(|| {
+                Error::InvalidParameters(format!("no key is shared with participant {}", id))
+            })
+    }
+}
+
+fn challenge<G: Group + GroupEncoding + Default>(
+    id: usize,
+    context: &[u8; 32],
+    generator: G,
+    encryption_key: G,
+    r: G,
+) -> DkgResult<G::Scalar> {
+    primitives::challenge(
+        ENCRYPTION_KEY_DST,
+        &[id],
+        &[generator, encryption_key, r],
+        context,
+    )
+}
+
+fn associated_data(sender: usize, recipient: usize) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(PAIRWISE_KEY_DST.len() + 8);
+    aad.extend_from_slice(PAIRWISE_KEY_DST);
+    aad.extend_from_slice(&(sender as u32).to_be_bytes());
+    aad.extend_from_slice(&(recipient as u32).to_be_bytes());
+    aad
+}
+
+fn decode_point<G: Group + GroupEncoding + Default>(bytes: &[u8]) -> Option<G> {
+    let mut repr = G::Repr::default();
+    if repr.as_ref().len() != bytes.len() {
+        return None;
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(G::from_bytes(&repr)).filter(|point: &G| !bool::from(point.is_identity()))
+}
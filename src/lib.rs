@@ -196,21 +196,74 @@
 pub use rand_core;
 pub use vsss_rs;
 
+mod archive;
+mod audit;
+mod backup;
+mod batch;
+mod blinder_opening;
+#[cfg(feature = "bn254")]
+pub mod bn254;
+mod board;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod certificate;
+mod clock;
+mod dealing;
+#[cfg(feature = "ed448")]
+pub mod ed448;
+#[cfg(any(feature = "wasm", feature = "capi"))]
+mod encoded;
+mod equivocation;
 mod error;
+#[cfg(feature = "evm")]
+pub mod evm;
+mod export;
+mod key_exchange;
+mod linked;
+#[cfg(feature = "mnemonic")]
+pub mod mnemonic;
+#[cfg(feature = "noise")]
+pub mod noise;
+mod observer;
+mod ordinal;
+mod output;
 mod parameters;
 mod participant;
+#[cfg(feature = "pasta")]
+pub mod pasta;
 mod pedersen_result;
+mod primitives;
 mod protected;
+mod provider;
+mod record;
+mod registry;
+mod report;
 mod secret_share;
+mod session;
+pub mod simulator;
+mod slots;
+mod telemetry;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod transcript;
+mod transport;
+pub mod utils;
+mod version;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod weighted;
+mod zero_secret;
 
+use dealing::DealtShares;
 use rand_core::SeedableRng;
 use serde::{
     de::{Error as DError, SeqAccess, Unexpected, Visitor},
     ser::{SerializeSeq, SerializeTuple},
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use slots::Slots;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Display, Formatter},
     marker::PhantomData,
     num::NonZeroUsize,
@@ -219,13 +272,41 @@ use uint_zigzag::Uint;
 use vsss_rs::elliptic_curve::{group::GroupEncoding, Group, PrimeField};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+pub use archive::*;
+pub use audit::*;
+pub use backup::*;
+pub use batch::*;
+pub use blinder_opening::*;
+pub use board::*;
+pub use certificate::*;
+pub use clock::*;
+pub use equivocation::*;
 pub use error::*;
+pub use export::*;
+pub use key_exchange::*;
+pub use linked::*;
+pub use observer::*;
+pub use output::*;
 pub use parameters::*;
 pub use participant::*;
 pub use pedersen_result::*;
+pub use provider::*;
+pub use record::*;
+pub use registry::*;
+pub use report::*;
+pub use session::*;
+pub use transcript::*;
+pub use transport::*;
+pub use version::STATE_VERSION;
+pub use weighted::*;
+pub use zero_secret::*;
 
 /// Valid rounds
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+///
+/// The variants are declared in the order they were added so the
+/// serialized variant indices of existing rounds never change,
+/// [`Ord`] and the integer conversions follow the protocol order.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash)]
 pub enum Round {
     /// First round
     One,
@@ -237,16 +318,35 @@ pub enum Round {
     Four,
     /// Five round
     Five,
+    /// Terminal state after the ceremony was stopped with [`Participant::abort`]
+    Aborted,
+    /// Pre-round before any data is exchanged.
+    /// Participants in this crate are created ready for round one
+    Zero,
+}
+
+impl Ord for Round {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        u8::from(*self).cmp(&u8::from(*other))
+    }
+}
+
+impl PartialOrd for Round {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Display for Round {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Zero => write!(f, "0"),
             Self::One => write!(f, "1"),
             Self::Two => write!(f, "2"),
             Self::Three => write!(f, "3"),
             Self::Four => write!(f, "4"),
             Self::Five => write!(f, "5"),
+            Self::Aborted => write!(f, "aborted"),
         }
     }
 }
@@ -257,11 +357,30 @@ macro_rules! impl_round_to_int {
             impl From<Round> for $ident {
                 fn from(value: Round) -> Self {
                     match value {
+                        Round::Zero => 0,
                         Round::One => 1,
                         Round::Two => 2,
                         Round::Three => 3,
                         Round::Four => 4,
                         Round::Five => 5,
+                        Round::Aborted => 6,
+                    }
+                }
+            }
+
+            impl TryFrom<$ident> for Round {
+                type Error = Error;
+
+                fn try_from(value: $ident) -> DkgResult<Self> {
+                    match value {
+                        0 => Ok(Round::Zero),
+                        1 => Ok(Round::One),
+                        2 => Ok(Round::Two),
+                        3 => Ok(Round::Three),
+                        4 => Ok(Round::Four),
+                        5 => Ok(Round::Five),
+                        6 => Ok(Round::Aborted),
+                        _ => Err(Error::UnknownRound(value.to_string())),
                     }
                 }
             }
@@ -271,6 +390,68 @@ macro_rules! impl_round_to_int {
 
 impl_round_to_int!(u8, u16, u32, u128, usize);
 
+impl Round {
+    /// All rounds in protocol order, starting with [`Round::Zero`].
+    /// [`Round::Aborted`] is not part of the protocol order
+    pub const ALL: [Round; 6] = [
+        Round::Zero,
+        Round::One,
+        Round::Two,
+        Round::Three,
+        Round::Four,
+        Round::Five,
+    ];
+
+    /// Iterate over all rounds in protocol order
+    pub fn iter() -> impl Iterator<Item = Round> {
+        Self::ALL.into_iter()
+    }
+
+    /// The round that follows this one, if any
+    pub fn next(&self) -> Option<Round> {
+        Self::ALL.get(usize::from(*self) + 1).copied()
+    }
+}
+
+/// A snapshot of a participant's progress through the protocol,
+/// returned by [`SessionManager::progress`]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash)]
+pub struct DkgProgress {
+    /// The round the participant is waiting to compute
+    pub round: Round,
+    /// The number of participants whose data has been accepted so far,
+    /// including this participant
+    pub received: usize,
+    /// The number of participants required to continue
+    pub required: usize,
+    /// Whether the participant can compute the next round
+    pub can_advance: bool,
+}
+
+/// The state of a ceremony as shown to a user,
+/// returned by [`SessionManager::dkg_status`]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash)]
+pub enum DkgStatus {
+    /// More data is needed before `round` can be computed
+    WaitingForMessages {
+        /// The round waiting for data
+        round: Round,
+        /// The number of participants whose data has arrived, including this participant
+        have: usize,
+        /// The number of participants whose data is needed
+        need: usize,
+    },
+    /// The round can be computed now
+    ReadyToRun(Round),
+    /// All rounds completed successfully
+    Completed,
+    /// The ceremony was stopped
+    Aborted {
+        /// Why the ceremony was stopped
+        reason: String,
+    },
+}
+
 /// Broadcast data from round 1 that should be sent to all other participants
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Round1BroadcastData<G: Group + GroupEncoding + Default> {
@@ -286,6 +467,10 @@ pub struct Round1BroadcastData<G: Group + GroupEncoding + Default> {
     )]
     /// The Pedersen commitments
     pub pedersen_commitments: Vec<G>,
+    /// The proof that the dealt secret is zero, sent by refresh participants
+    #[serde(bound(serialize = "ZeroSecretProof<G>: Serialize"))]
+    #[serde(bound(deserialize = "ZeroSecretProof<G>: Deserialize<'de>"))]
+    pub zero_secret_proof: Option<ZeroSecretProof<G>>,
 }
 
 #[cfg(test)]
@@ -299,6 +484,7 @@ impl<G: Group + GroupEncoding + Default> serde_encrypt::traits::SerdeEncryptShar
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Round2EchoBroadcastData {
     /// The current valid participant ids
+    #[serde(with = "ordinal::set")]
     pub valid_participant_ids: BTreeSet<usize>,
 }
 
@@ -318,6 +504,32 @@ pub struct Round3BroadcastData<G: Group + GroupEncoding + Default> {
     pub commitments: Vec<G>,
 }
 
+/// Broadcast data from round 2 of the merged round variant
+/// that should be sent to all valid participants
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MergedRound2Data<G: Group + GroupEncoding + Default> {
+    /// The round 2 echo broadcast data
+    pub echo: Round2EchoBroadcastData,
+    /// The round 3 broadcast data
+    #[serde(bound(serialize = "Round3BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round3BroadcastData<G>: Deserialize<'de>"))]
+    pub commitments: Round3BroadcastData<G>,
+}
+
+/// The confirmation sent on the fast path of the merged round variant
+/// that should be sent to all valid participants.
+///
+/// Batches the echoes of rounds 3 to 5: the transcript hash covers
+/// the valid sets and all the commitments each participant used.
+#[derive(Copy, Debug, Clone, Serialize, Deserialize)]
+pub struct FastConfirmationData<G: Group + GroupEncoding + Default> {
+    /// The computed public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The hash of the transcript after computing the public key
+    pub transcript_hash: [u8; 32],
+}
+
 /// Echo broadcast data from round 4 that should be sent to all valid participants
 #[derive(Copy, Debug, Clone, Serialize, Deserialize)]
 pub struct Round4EchoBroadcastData<G: Group + GroupEncoding + Default> {
@@ -326,8 +538,18 @@ pub struct Round4EchoBroadcastData<G: Group + GroupEncoding + Default> {
     pub public_key: G,
 }
 
+/// Shares received from disqualified dealers that should be sent to all valid participants
+/// so the dealers' secrets can be reconstructed
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RecoveryShareData {
+    /// The round 1 peer data received from each disqualified dealer keyed by the dealer's id
+    #[serde(with = "ordinal::map")]
+    pub shares: BTreeMap<usize, Round1P2PData>,
+}
+
 /// Peer data from round 1 that should only be sent to a specific secret_participant
-#[derive(Clone, Debug, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+#[cfg_attr(feature = "unsafe_debug", derive(Debug))]
 pub struct Round1P2PData {
     /// The secret share
     pub secret_share: Vec<u8>,
@@ -335,6 +557,16 @@ pub struct Round1P2PData {
     pub blind_share: Vec<u8>,
 }
 
+#[cfg(not(feature = "unsafe_debug"))]
+impl fmt::Debug for Round1P2PData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Round1P2PData")
+            .field("secret_share", &"<redacted>")
+            .field("blind_share", &"<redacted>")
+            .finish()
+    }
+}
+
 pub(crate) fn serialize_scalar<F: PrimeField, S: Serializer>(
     scalar: &F,
     s: S,
@@ -614,9 +846,14 @@ pub(crate) fn deserialize_g_vec<'de, G: Group + GroupEncoding + Default, D: Dese
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_chacha::ChaCha8Rng;
     use serde_encrypt::traits::SerdeEncryptSharedKey;
     use std::collections::BTreeMap;
-    use vsss_rs::{combine_shares, Share};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use vsss_rs::{combine_shares, elliptic_curve::ff::Field, Share};
 
     #[test]
     fn one_corrupted_party_k256() {
@@ -655,6 +892,16 @@ mod tests {
             SecretParticipant::<G>::new(NonZeroUsize::new(4).unwrap(), parameters).unwrap(),
         ];
 
+        let restored = participants[0].parameters();
+        assert_eq!(restored.threshold(), THRESHOLD);
+        assert_eq!(restored.limit(), LIMIT);
+        assert_eq!(restored.message_generator(), parameters.message_generator());
+        assert_eq!(restored.blinder_generator(), parameters.blinder_generator());
+        assert_eq!(
+            restored.participant_ids().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+
         let mut r1bdata = Vec::with_capacity(LIMIT);
         let mut r1p2pdata = Vec::with_capacity(LIMIT);
         for p in participants.iter_mut() {
@@ -701,10 +948,17 @@ mod tests {
             if BAD_ID == p.get_id() {
                 continue;
             }
+            let progress = p.progress(r2bdata.len());
+            assert_eq!(progress.round, Round::Three);
+            assert_eq!(progress.received, LIMIT - 1);
+            assert!(progress.can_advance);
             let res = p.round3(&r2bdata);
             assert!(res.is_ok());
             r3bdata.insert(p.get_id(), res.unwrap());
-            assert!(p.round3(&r2bdata).is_err());
+            let err = p.round3(&r2bdata).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidRound);
+            assert_eq!(err.sender(), None);
+            assert_eq!(err.severity(), Severity::Fatal);
         }
 
         let mut r4bdata = BTreeMap::new();
@@ -717,17 +971,51 @@ mod tests {
             assert!(res.is_ok());
             let bdata = res.unwrap();
             let share = p.get_secret_share().unwrap();
+            let public_share = r3bdata
+                .values()
+                .map(|r3| {
+                    utils::evaluate_commitments(&r3.commitments, G::Scalar::from(p.get_id() as u64))
+                })
+                .sum::<G>();
+            assert_eq!(public_share, G::generator() * share);
             r4bdata.insert(p.get_id(), bdata);
             r4shares
                 .push(<InnerShare as Share>::from_field_element(p.get_id() as u8, share).unwrap());
             assert!(p.round4(&r3bdata).is_err());
         }
 
-        for p in &participants {
+        let transcript = participants[0].export_transcript();
+        let blinder = participants
+            .iter()
+            .filter(|p| p.get_id() != BAD_ID)
+            .map(|p| p.get_blinder())
+            .sum::<G::Scalar>();
+        for p in participants.iter_mut() {
             if BAD_ID == p.get_id() {
                 continue;
             }
+            let mut bad_r4bdata = r4bdata.clone();
+            let other = if p.get_id() == 1 { 2 } else { 1 };
+            bad_r4bdata.get_mut(&other).unwrap().public_key = G::generator();
+            let err = p.round5(&bad_r4bdata).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidSender);
+            assert_eq!(err.sender(), Some(other));
+            assert_eq!(err.round(), Some(5));
+            assert_eq!(err.severity(), Severity::SenderFault);
             assert!(p.round5(&r4bdata).is_ok());
+            assert_eq!(p.export_transcript(), transcript);
+            assert_eq!(
+                p.get_transcript_hash_with::<Sha256Transcript>(),
+                p.get_transcript_hash()
+            );
+            assert_eq!(
+                p.get_transcript_hash_with::<CountingTranscript>(),
+                p.export_transcript().len()
+            );
+            assert_eq!(
+                p.get_blind_key(),
+                Some(r1bdata[0].blinder_generator * blinder)
+            );
         }
 
         let res = combine_shares::<G::Scalar, u8, InnerShare>(&r4shares);
@@ -737,6 +1025,3007 @@ mod tests {
         assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
     }
 
+    #[test]
+    fn batch_verify_shares() {
+        type G = k256::ProjectivePoint;
+        type S = k256::Scalar;
+        let mut rng = rand_core::OsRng;
+        let h = G::random(&mut rng);
+        let id = S::from(3u64);
+
+        let polys = (0..4)
+            .map(|_| {
+                (
+                    [S::random(&mut rng), S::random(&mut rng)],
+                    [S::random(&mut rng), S::random(&mut rng)],
+                )
+            })
+            .collect::<Vec<_>>();
+        let commitments = polys
+            .iter()
+            .map(|(a, b)| {
+                [
+                    G::generator() * a[0] + h * b[0],
+                    G::generator() * a[1] + h * b[1],
+                ]
+            })
+            .collect::<Vec<_>>();
+        let feldman = polys
+            .iter()
+            .map(|(a, _)| [G::generator() * a[0], G::generator() * a[1]])
+            .collect::<Vec<_>>();
+        let mut openings = polys
+            .iter()
+            .zip(commitments.iter())
+            .map(|((a, b), c)| (c.as_slice(), a[0] + a[1] * id, b[0] + b[1] * id))
+            .collect::<Vec<_>>();
+        let feldman_openings = openings
+            .iter()
+            .zip(feldman.iter())
+            .map(|((_, s, _), c)| (c.as_slice(), *s))
+            .collect::<Vec<_>>();
+
+        assert!(utils::batch_verify_pedersen_shares(
+            G::generator(),
+            h,
+            id,
+            &openings,
+            rng
+        ));
+        assert!(utils::batch_verify_feldman_shares(
+            G::generator(),
+            id,
+            &feldman_openings,
+            rng
+        ));
+        openings[2].2 += S::ONE;
+        assert!(!utils::batch_verify_pedersen_shares(
+            G::generator(),
+            h,
+            id,
+            &openings,
+            rng
+        ));
+    }
+
+    #[test]
+    fn hash_to_scalar() {
+        // RFC 9380 K.1
+        let expanded =
+            utils::expand_message_xmd(b"QUUX-V01-CS02-with-expander-SHA256-128", b"", 0x20)
+                .unwrap();
+        assert_eq!(
+            data_encoding::HEXLOWER.encode(&expanded),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+
+        let a = utils::hash_to_scalar::<k256::Scalar>(b"dst", b"msg").unwrap();
+        assert_eq!(
+            a,
+            utils::hash_to_scalar::<k256::Scalar>(b"dst", b"msg").unwrap()
+        );
+        assert_ne!(
+            a,
+            utils::hash_to_scalar::<k256::Scalar>(b"other", b"msg").unwrap()
+        );
+        assert!(utils::hash_to_scalar::<k256::Scalar>(&[0u8; 256], b"msg").is_err());
+    }
+
+    #[cfg(feature = "curve25519")]
+    #[test]
+    fn ed25519_verifying_key() {
+        use vsss_rs::curve25519::WrappedEdwards;
+
+        let secret = <WrappedEdwards as Group>::Scalar::random(rand_core::OsRng);
+        let public_key = WrappedEdwards::generator() * secret;
+        let bytes = utils::ed25519_verifying_key(&public_key);
+        let mut repr = <WrappedEdwards as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(&bytes);
+        assert_eq!(
+            Option::<WrappedEdwards>::from(WrappedEdwards::from_bytes(&repr)),
+            Some(public_key)
+        );
+    }
+
+    #[test]
+    fn participant_id_from_bytes() {
+        let a = utils::participant_id_from_bytes::<k256::ProjectivePoint>(b"node-a").unwrap();
+        let b = utils::participant_id_from_bytes::<k256::ProjectivePoint>(b"node-b").unwrap();
+        assert_ne!(a, b);
+        assert!(!bool::from(a.is_zero()));
+        assert_eq!(
+            a,
+            utils::participant_id_from_bytes::<k256::ProjectivePoint>(b"node-a").unwrap()
+        );
+
+        let parameters = Parameters::<k256::ProjectivePoint>::new(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(3).unwrap(),
+        );
+        assert_eq!(parameters.participant_id(b"node-a").unwrap(), a);
+        let tenant_a = parameters.with_namespace(b"tenant-a");
+        let tenant_b = parameters.with_namespace(b"tenant-b");
+        let id = tenant_a.participant_id(b"node-a").unwrap();
+        assert_ne!(id, a);
+        assert_ne!(id, tenant_b.participant_id(b"node-a").unwrap());
+        assert_eq!(
+            id,
+            utils::namespaced_participant_id_from_bytes::<k256::ProjectivePoint>(
+                &tenant_a.namespace().unwrap(),
+                b"node-a"
+            )
+            .unwrap()
+        );
+
+        let first = SecretParticipant::new(NonZeroUsize::new(1).unwrap(), tenant_a).unwrap();
+        let second = SecretParticipant::new(NonZeroUsize::new(1).unwrap(), tenant_b).unwrap();
+        assert_ne!(first.get_transcript_hash(), second.get_transcript_hash());
+        assert!(SecretParticipant::resume(first, tenant_b).is_err());
+    }
+
+    #[cfg(not(feature = "unsafe_debug"))]
+    #[test]
+    fn redacted_debug() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let participant =
+            SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        let output = format!("{:?}", participant);
+        assert!(output.contains("<redacted>"));
+        assert!(!output.contains(&format!("{:?}", participant.get_blinder())));
+
+        let p2p = Round1P2PData {
+            secret_share: vec![1, 2, 3],
+            blind_share: vec![4, 5, 6],
+        };
+        assert_eq!(
+            format!("{:?}", p2p),
+            r#"Round1P2PData { secret_share: "<redacted>", blind_share: "<redacted>" }"#
+        );
+    }
+
+    #[test]
+    fn refresh_share_delta() {
+        type G = k256::ProjectivePoint;
+        let mut rng = rand_core::OsRng;
+        let share = k256::Scalar::random(&mut rng);
+        let delta = k256::Scalar::random(&mut rng);
+        let refresh = <RefreshParticipantImpl<G> as ParticipantImpl<G>>::secret_share(delta);
+        assert_eq!(refresh.apply(share), share + delta);
+        assert_eq!(format!("{:?}", refresh), "RefreshShareDelta(<redacted>)");
+        let mut wiped = refresh.clone();
+        wiped.zeroize();
+        assert_eq!(wiped.apply(share), share);
+        assert_eq!(refresh.into_new_share(), delta);
+    }
+
+    #[test]
+    fn zero_secret_proof() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| {
+                RefreshParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let mut broadcast = BTreeMap::new();
+        let mut p2p = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (bdata, shares) = p.round1().unwrap();
+            broadcast.insert(p.get_id(), bdata);
+            p2p.insert(p.get_id(), shares);
+        }
+        let context = participants[0].transcript_mut().setup_hash();
+        let proof = broadcast[&2].zero_secret_proof.clone().unwrap();
+        assert!(proof.verify(2, &context, &broadcast[&2]));
+        assert!(!proof.verify(3, &context, &broadcast[&2]));
+        assert!(!proof.verify(2, &[0u8; 32], &broadcast[&2]));
+
+        // A dealer whose proof doesn't verify is rejected in round 2
+        broadcast.get_mut(&2).unwrap().zero_secret_proof = broadcast[&3].zero_secret_proof.clone();
+        let received = broadcast
+            .iter()
+            .filter(|(id, _)| **id != 1)
+            .map(|(id, bdata)| (*id, bdata.clone()))
+            .collect();
+        let shares = p2p
+            .iter()
+            .filter(|(id, _)| **id != 1)
+            .map(|(id, shares)| (*id, shares[&1].clone()))
+            .collect();
+        participants[0].round2(received, shares).unwrap();
+        assert_eq!(
+            participants[0].get_valid_participant_ids(),
+            &BTreeSet::from([1, 3])
+        );
+        assert_eq!(
+            participants[0]
+                .export_transcript()
+                .iter()
+                .filter(|item| item.label == "zero_secret")
+                .count(),
+            2
+        );
+
+        // A dealer that strips its proof is rejected unless it was
+        // set as re-sharing an existing secret
+        let mut received = broadcast
+            .iter()
+            .filter(|(id, _)| **id != 2)
+            .map(|(id, bdata)| (*id, bdata.clone()))
+            .collect::<BTreeMap<_, _>>();
+        received.get_mut(&3).unwrap().zero_secret_proof = None;
+        let shares = p2p
+            .iter()
+            .filter(|(id, _)| **id != 2)
+            .map(|(id, shares)| (*id, shares[&2].clone()))
+            .collect::<BTreeMap<_, _>>();
+        let mut stripped = participants[1].clone();
+        stripped.round2(received.clone(), shares.clone()).unwrap();
+        assert_eq!(
+            stripped.get_valid_participant_ids(),
+            &BTreeSet::from([1, 2])
+        );
+        participants[1].set_secret_dealers([3]);
+        participants[1].round2(received, shares).unwrap();
+        assert_eq!(
+            participants[1].get_valid_participant_ids(),
+            &BTreeSet::from([1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn out_of_range_ids() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let mut broadcast = BTreeMap::new();
+        let mut p2p = BTreeMap::new();
+        for p in participants[1..].iter_mut() {
+            let (bdata, shares) = p.round1().unwrap();
+            broadcast.insert(p.get_id(), bdata);
+            p2p.insert(p.get_id(), shares[&1].clone());
+        }
+        // Data for ids outside 1..=limit is never stored
+        for id in [0, 4, usize::MAX] {
+            broadcast.insert(id, broadcast[&2].clone());
+            p2p.insert(id, p2p[&2].clone());
+        }
+        participants[0].round1().unwrap();
+        participants[0].round2(broadcast, p2p).unwrap();
+        assert_eq!(
+            participants[0].get_valid_participant_ids(),
+            &BTreeSet::from([1, 2, 3])
+        );
+        let state = serde_bare::to_vec(&participants[0]).unwrap();
+        assert!(serde_bare::from_slice::<SecretParticipant<G>>(&state).is_ok());
+    }
+
+    #[test]
+    fn ordinal_encoding() {
+        let echo = Round2EchoBroadcastData {
+            valid_participant_ids: [1, 2].into_iter().collect(),
+        };
+        let bytes = serde_bare::to_vec(&echo).unwrap();
+        assert_eq!(bytes, [2, 1, 0, 0, 0, 2, 0, 0, 0]);
+        let decoded: Round2EchoBroadcastData = serde_bare::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.valid_participant_ids, echo.valid_participant_ids);
+
+        let json = serde_json::to_string(&RecoveryShareData::default()).unwrap();
+        assert_eq!(json, r#"{"shares":{}}"#);
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let echo = Round2EchoBroadcastData {
+                valid_participant_ids: [u32::MAX as usize + 1].into_iter().collect(),
+            };
+            assert!(serde_bare::to_vec(&echo).is_err());
+        }
+    }
+
+    #[test]
+    fn state_versioning() {
+        type G = k256::ProjectivePoint;
+
+        /// The state as serialized before it was versioned, with only the
+        /// original fields and the dealt shares kept with the polynomials
+        fn baseline_state(participant: &SecretParticipant<G>) -> serde_json::Value {
+            const FIELDS: [&str; 13] = [
+                "id",
+                "components",
+                "threshold",
+                "limit",
+                "round",
+                "secret_share",
+                "blind_share",
+                "public_key",
+                "blind_key",
+                "round1_broadcast_data",
+                "round1_p2p_data",
+                "valid_participant_ids",
+                "participant_impl",
+            ];
+            let mut json = serde_json::to_value(participant).unwrap();
+            let dealt_shares = json["dealt_shares"].take();
+            json["components"]["secret_shares"] = dealt_shares["secret_shares"].clone();
+            json["components"]["blinder_shares"] = dealt_shares["blinder_shares"].clone();
+            json.as_object_mut()
+                .unwrap()
+                .retain(|field, _| FIELDS.contains(&field.as_str()));
+            json
+        }
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(participants[0].get_state_version(), STATE_VERSION);
+
+        // Participant 1 was saved before round 1 and participant 2 before round 2
+        let mut round1 = BTreeMap::new();
+        round1.insert(2, participants[1].round1().unwrap());
+        for i in 0..2 {
+            let mut legacy: SecretParticipant<G> =
+                serde_json::from_value(baseline_state(&participants[i])).unwrap();
+            assert_eq!(legacy.get_state_version(), 0);
+            if i == 0 {
+                assert!(matches!(
+                    legacy.round1(),
+                    Err(Error::RoundError(1, ref msg)) if msg == "the dealt shares are missing"
+                ));
+            }
+            let migrated = legacy.migrate().unwrap();
+            assert_eq!(migrated.get_state_version(), STATE_VERSION);
+            assert_eq!(migrated.get_round(), participants[i].get_round());
+            assert_eq!(migrated.get_blinder(), participants[i].get_blinder());
+            assert_eq!(
+                migrated.get_transcript_hash(),
+                participants[i].get_transcript_hash()
+            );
+            participants[i] = migrated;
+        }
+
+        for p in participants.iter_mut().filter(|p| p.get_id() != 2) {
+            round1.insert(p.get_id(), p.round1().unwrap());
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let others = round1.iter().filter(|(id, _)| **id != my_id);
+            let bdata = others
+                .clone()
+                .map(|(id, (b, _))| (*id, b.clone()))
+                .collect();
+            let p2pdata = others
+                .map(|(id, (_, p2p))| (*id, p2p[&my_id].clone()))
+                .collect();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        let r3bdata = participants
+            .iter_mut()
+            .map(|p| (p.get_id(), p.round3(&r2bdata).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let r4bdata = participants
+            .iter_mut()
+            .map(|p| (p.get_id(), p.round4(&r3bdata).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let transcript_hash = participants[2].get_transcript_hash();
+        for p in participants.iter_mut() {
+            p.round5(&r4bdata).unwrap();
+            assert_eq!(p.get_transcript_hash(), transcript_hash);
+        }
+
+        let mut json = serde_json::to_value(&participants[0]).unwrap();
+        json["version"] = serde_json::json!(STATE_VERSION + 1);
+        assert!(serde_json::from_value::<SecretParticipant<G>>(json).is_err());
+
+        let outputs = simulator::Simulator::run(parameters, &[Default::default(); 3]).unwrap();
+        let mut json = serde_json::to_value(&outputs[0]).unwrap();
+        assert_eq!(json["version"], STATE_VERSION);
+        let output: DkgOutput<G> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(output.public_key, outputs[0].public_key);
+        assert_eq!(output.secret_shares, outputs[0].secret_shares);
+        assert_eq!(output.report(), outputs[0].report());
+        json["version"] = serde_json::json!(STATE_VERSION + 1);
+        assert!(serde_json::from_value::<DkgOutput<G>>(json).is_err());
+    }
+
+    #[test]
+    fn resume_participant() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        participants[0].round1().unwrap();
+        let json = serde_json::to_value(&participants[0]).unwrap();
+        let state: SecretParticipant<G> = serde_json::from_value(json.clone()).unwrap();
+        let resumed = SecretParticipant::resume(state.clone(), parameters).unwrap();
+        assert_eq!(resumed.get_round(), Round::Two);
+        let other =
+            Parameters::<G>::new(NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(3).unwrap());
+        assert!(SecretParticipant::resume(state, other).is_err());
+
+        // Round 3 requires the shares computed in round 2
+        let mut skipped = json;
+        skipped["round"] = serde_json::json!("Three");
+        let state: SecretParticipant<G> = serde_json::from_value(skipped).unwrap();
+        assert!(SecretParticipant::resume(state, parameters).is_err());
+
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        run_ceremony(&mut participants).unwrap();
+        let json = serde_json::to_value(&participants[0]).unwrap();
+        let state: SecretParticipant<G> = serde_json::from_value(json).unwrap();
+        let resumed = SecretParticipant::resume(state, parameters).unwrap();
+        assert!(resumed.completed());
+        assert_eq!(resumed.get_public_key(), participants[0].get_public_key());
+
+        participants[0].abort("operator request");
+        let json = serde_json::to_value(&participants[0]).unwrap();
+        let state: SecretParticipant<G> = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            SecretParticipant::resume(state, parameters),
+            Err(Error::Aborted(_))
+        ));
+    }
+
+    #[test]
+    fn secret_provider() {
+        type G = k256::ProjectivePoint;
+
+        #[derive(Default)]
+        struct MemoryProvider {
+            blinder: k256::Scalar,
+            stored: Option<(usize, k256::Scalar, k256::Scalar, G)>,
+        }
+
+        impl SecretProvider<G> for MemoryProvider {
+            fn dealing_secret(&mut self, _id: usize) -> DkgResult<k256::Scalar> {
+                Ok(k256::Scalar::random(rand_core::OsRng))
+            }
+            fn dealing_blinder(&mut self, _id: usize) -> DkgResult<k256::Scalar> {
+                self.blinder = k256::Scalar::random(rand_core::OsRng);
+                Ok(self.blinder)
+            }
+            fn store_shares(
+                &mut self,
+                id: usize,
+                secret_share: k256::Scalar,
+                blind_share: k256::Scalar,
+                public_key: G,
+            ) -> DkgResult<()> {
+                self.stored = Some((id, secret_share, blind_share, public_key));
+                Ok(())
+            }
+        }
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut providers = (0..3)
+            .map(|_| MemoryProvider::default())
+            .collect::<Vec<_>>();
+        let mut participants = providers
+            .iter_mut()
+            .enumerate()
+            .map(|(i, provider)| {
+                SecretParticipant::<G>::with_provider(
+                    NonZeroUsize::new(i + 1).unwrap(),
+                    parameters,
+                    provider,
+                )
+            })
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(participants[0].get_blinder(), providers[0].blinder);
+        let err = participants[0].store_shares(&mut providers[0]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRound);
+        assert!(providers[0].stored.is_none());
+
+        run_ceremony(&mut participants).unwrap();
+        for (p, provider) in participants.iter().zip(providers.iter_mut()) {
+            p.store_shares(provider).unwrap();
+            assert_eq!(
+                provider.stored,
+                Some((
+                    p.get_id(),
+                    p.get_secret_share().unwrap(),
+                    p.get_blind_share().unwrap(),
+                    p.get_public_key().unwrap(),
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn zeroize_pedersen_result() {
+        type G = k256::ProjectivePoint;
+        let mut rng = rand_core::OsRng;
+        let mut result = GennaroDkgPedersenResult::<G>::from(
+            vsss_rs::pedersen::split_secret::<G, u8, InnerShare>(
+                2,
+                3,
+                k256::Scalar::random(&mut rng),
+                None,
+                None,
+                None,
+                &mut rng,
+            )
+            .unwrap(),
+        );
+        assert!(!bool::from(result.blinder.is_zero()));
+        result.zeroize();
+        assert!(bool::from(result.blinder.is_zero()));
+        assert!(result.secret_shares.is_empty());
+        assert!(result.blinder_shares.is_empty());
+    }
+
+    #[test]
+    fn zeroize_participant() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        run_ceremony(&mut participants).unwrap();
+
+        let mut output = participants[0].output().unwrap();
+        output.zeroize();
+        assert!(output.secret_shares.is_empty());
+
+        // Clones keep their own shares
+        let copy = participants[0].clone();
+        participants[0].zeroize();
+        assert!(bool::from(
+            participants[0].get_secret_share().unwrap().is_zero()
+        ));
+        assert!(!bool::from(copy.get_secret_share().unwrap().is_zero()));
+    }
+
+    #[test]
+    fn round_sequence() {
+        let rounds = Round::iter().collect::<Vec<_>>();
+        assert_eq!(rounds.first(), Some(&Round::Zero));
+        assert_eq!(rounds.last(), Some(&Round::Five));
+        for (i, round) in rounds.iter().enumerate() {
+            assert_eq!(usize::from(*round), i);
+            assert_eq!(Round::try_from(i).unwrap(), *round);
+            assert_eq!(round.to_string(), i.to_string());
+        }
+        assert_eq!(Round::Zero.next(), Some(Round::One));
+        assert_eq!(Round::Five.next(), None);
+        assert_eq!(Round::Aborted.next(), None);
+        assert_eq!(Round::try_from(6u8).unwrap(), Round::Aborted);
+        assert!(matches!(Round::try_from(7u8), Err(Error::UnknownRound(ref r)) if r == "7"));
+        assert!(Round::Zero < Round::One);
+        assert!(Round::Five < Round::Aborted);
+        // The rounds before round zero was added keep their encoding
+        assert_eq!(serde_bare::to_vec(&Round::One).unwrap(), [0]);
+        assert_eq!(serde_bare::to_vec(&Round::Aborted).unwrap(), [5]);
+        assert_eq!(serde_bare::to_vec(&Round::Zero).unwrap(), [6]);
+    }
+
+    #[test]
+    fn abort_ceremony() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participant =
+            SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        participant.round1().unwrap();
+        assert_eq!(
+            participant.status(1),
+            DkgStatus::WaitingForMessages {
+                round: Round::Two,
+                have: 1,
+                need: 2
+            }
+        );
+        assert_eq!(participant.status(2), DkgStatus::ReadyToRun(Round::Two));
+        participant.abort("operator cancel");
+        assert_eq!(
+            participant.status(0),
+            DkgStatus::Aborted {
+                reason: "operator cancel".to_string()
+            }
+        );
+        assert_eq!(participant.get_round(), Round::Aborted);
+        assert_eq!(participant.get_abort_reason(), Some("operator cancel"));
+        assert!(participant.get_secret_share().is_none());
+
+        let err = participant
+            .round2(BTreeMap::new(), BTreeMap::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Aborted);
+        assert_eq!(err.severity(), Severity::Fatal);
+        assert!(participant.round1().is_err());
+
+        let mut manager = SessionManager::<u8, SecretParticipantImpl<G>, G>::new();
+        manager.insert(0, participant);
+        assert_eq!(manager.advance(&0).unwrap_err().kind(), ErrorKind::Aborted);
+    }
+
+    #[derive(Default)]
+    struct CountingTranscript(usize);
+
+    impl DkgTranscript for CountingTranscript {
+        type Output = usize;
+
+        fn absorb(&mut self, _item: &TranscriptItem) {
+            self.0 += 1;
+        }
+
+        fn finalize(self) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn quorum_policy() {
+        assert!(QuorumPolicy::Threshold.is_satisfied(2, 2, 4));
+        assert!(!QuorumPolicy::Threshold.is_satisfied(1, 2, 4));
+        assert!(!QuorumPolicy::WaitForAll.is_satisfied(3, 2, 4));
+        assert!(QuorumPolicy::WaitForAll.is_satisfied(4, 2, 4));
+        let policy = QuorumPolicy::Custom(|received, _, expected| received + 1 >= expected);
+        assert!(policy.is_satisfied(3, 2, 4));
+        assert!(!policy.is_satisfied(2, 2, 4));
+        // Never below the threshold
+        assert!(!policy.is_satisfied(1, 2, 2));
+
+        let parameters = Parameters::<k256::ProjectivePoint>::new(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(4).unwrap(),
+        )
+        .with_quorum_policy(QuorumPolicy::WaitForAll);
+        let json = serde_json::to_string(&parameters).unwrap();
+        let parameters2 = serde_json::from_str::<Parameters<k256::ProjectivePoint>>(&json).unwrap();
+        assert!(matches!(
+            parameters2.quorum_policy(),
+            QuorumPolicy::WaitForAll
+        ));
+        assert!(serde_json::to_string(&parameters.with_quorum_policy(policy)).is_err());
+
+        // Checkpointing a participant with a custom policy fails and the
+        // policy must be supplied again when resuming
+        let custom = parameters.with_quorum_policy(policy);
+        let participant =
+            SecretParticipant::<k256::ProjectivePoint>::new(NonZeroUsize::new(1).unwrap(), custom)
+                .unwrap();
+        assert!(serde_json::to_string(&participant).is_err());
+        let participant =
+            SecretParticipant::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        let json = serde_json::to_string(&participant).unwrap();
+        let state: SecretParticipant<k256::ProjectivePoint> = serde_json::from_str(&json).unwrap();
+        let resumed = SecretParticipant::resume(state.clone(), custom).unwrap();
+        assert!(matches!(
+            resumed.parameters().quorum_policy(),
+            QuorumPolicy::Custom(_)
+        ));
+        let threshold = parameters.with_quorum_policy(QuorumPolicy::Threshold);
+        assert!(SecretParticipant::resume(state, threshold).is_err());
+    }
+
+    #[test]
+    fn parameters_builder() {
+        let parameters = Parameters::<k256::ProjectivePoint>::builder()
+            .threshold(2)
+            .limit(3)
+            .build()
+            .unwrap();
+        let expected = Parameters::<k256::ProjectivePoint>::new(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(3).unwrap(),
+        );
+        assert_eq!(parameters.threshold(), 2);
+        assert_eq!(parameters.limit(), 3);
+        assert_eq!(parameters.message_generator(), expected.message_generator());
+        assert_eq!(parameters.blinder_generator(), expected.blinder_generator());
+
+        let builder = Parameters::<k256::ProjectivePoint>::builder();
+        assert!(builder.threshold(2).build().is_err());
+        assert!(builder.limit(3).build().is_err());
+        assert!(builder.threshold(4).limit(3).build().is_err());
+        assert!(builder.threshold(0).limit(3).build().is_err());
+        assert!(builder
+            .threshold(2)
+            .limit(3)
+            .blinder_generator(k256::ProjectivePoint::GENERATOR)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn parameters_validate() {
+        let parameters = Parameters::<k256::ProjectivePoint>::with_generators(
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroUsize::new(256).unwrap(),
+            k256::ProjectivePoint::IDENTITY,
+            k256::ProjectivePoint::IDENTITY,
+        );
+        match parameters.validate() {
+            Err(Error::ParameterViolations(violations)) => assert_eq!(
+                violations,
+                vec![
+                    ParameterViolation::LimitTooLarge(256),
+                    ParameterViolation::IdentityMessageGenerator,
+                    ParameterViolation::IdentityBlinderGenerator,
+                    ParameterViolation::EqualGenerators,
+                ]
+            ),
+            _ => panic!("expected parameter violations"),
+        }
+        assert!(SecretParticipant::new(NonZeroUsize::new(1).unwrap(), parameters).is_err());
+
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(5).unwrap());
+        // Low thresholds are only rejected when a policy asks for it
+        let low =
+            Parameters::<G>::new(NonZeroUsize::new(1).unwrap(), NonZeroUsize::new(5).unwrap());
+        assert!(low.validate().is_ok());
+        assert!(matches!(
+            low.with_threshold_policy(ThresholdPolicy::AtLeast(2)).validate(),
+            Err(Error::ParameterViolations(violations)) if violations == vec![
+                ParameterViolation::ThresholdBelowPolicy {
+                    threshold: 1,
+                    minimum: 2,
+                }
+            ]
+        ));
+        for (policy, minimum) in [
+            (ThresholdPolicy::Majority, 3),
+            (ThresholdPolicy::AtLeast(4), 4),
+        ] {
+            assert_eq!(policy.minimum(5), minimum);
+            match parameters.with_threshold_policy(policy).validate() {
+                Err(Error::ParameterViolations(violations)) => assert_eq!(
+                    violations,
+                    vec![ParameterViolation::ThresholdBelowPolicy {
+                        threshold: 2,
+                        minimum,
+                    }]
+                ),
+                _ => panic!("expected parameter violations"),
+            }
+        }
+        let parameters = Parameters::<G>::builder()
+            .threshold(3)
+            .limit(5)
+            .threshold_policy(ThresholdPolicy::Majority)
+            .build()
+            .unwrap();
+        let participant =
+            SecretParticipant::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        assert_eq!(
+            participant.parameters().threshold_policy(),
+            ThresholdPolicy::Majority
+        );
+        assert!(participant
+            .export_transcript()
+            .iter()
+            .any(|item| item.label == "threshold_policy" && item.data[0] == 2));
+    }
+
+    #[derive(Default)]
+    struct CountingObserver {
+        verified: AtomicUsize,
+        invalidated: AtomicUsize,
+        completed: AtomicUsize,
+    }
+
+    impl RoundObserver for CountingObserver {
+        fn on_message_verified(&self, _participant: usize, _round: Round, _sender: usize) {
+            self.verified.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_participant_invalidated(&self, _participant: usize, _round: Round, _invalid: usize) {
+            self.invalidated.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_complete(&self, _participant: usize, _public_key: &[u8]) {
+            self.completed.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn participant_registry() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let registry = ParticipantRegistry::new().with_identity(
+            3,
+            ParticipantIdentity {
+                label: "custodian-c".to_string(),
+                address: Some("10.0.0.3:7000".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(registry.validate(&parameters).is_ok());
+        assert!(ParticipantRegistry::new()
+            .with_identity(4, ParticipantIdentity::default())
+            .validate(&parameters)
+            .is_err());
+
+        let err = Error::SenderError {
+            round: 2,
+            error: ParticipantError::NoVerifyShares(3),
+        };
+        assert_eq!(registry.blame(&err).unwrap().label, "custodian-c");
+        assert!(registry
+            .describe(&err)
+            .ends_with("(participant 3 is custodian-c at 10.0.0.3:7000)"));
+        assert_eq!(
+            registry.describe(&Error::UnknownSession),
+            Error::UnknownSession.to_string()
+        );
+
+        let mut participant =
+            SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        participant.set_registry(registry.clone()).unwrap();
+        assert_eq!(participant.get_registry(), &registry);
+    }
+
+    #[test]
+    fn bulletin_board() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let record = CeremonyRecord::generate(parameters, [5u8; 32]).unwrap();
+        let mut board = MemoryBulletinBoard::<G>::new();
+        for (id, data) in &record.broadcast.round1_broadcast {
+            board
+                .append(*id, BoardMessage::Round1(data.clone()))
+                .unwrap();
+        }
+        for (id, data) in &record.broadcast.round2_echo {
+            board
+                .append(*id, BoardMessage::Round2(data.clone()))
+                .unwrap();
+        }
+        assert_eq!(board.read_since(4).unwrap().len(), 2);
+        assert_eq!(
+            board
+                .append(
+                    1,
+                    BoardMessage::Round2(record.broadcast.round2_echo[&1].clone())
+                )
+                .unwrap(),
+            3
+        );
+        let err = board
+            .append(
+                1,
+                BoardMessage::Round2(Round2EchoBroadcastData {
+                    valid_participant_ids: [1, 2].into_iter().collect(),
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(err.sender(), Some(1));
+
+        let archive = board.archive().unwrap();
+        assert_eq!(archive.round1_broadcast.len(), 3);
+        assert_eq!(archive.round2_echo.len(), 3);
+        assert!(archive.round3_broadcast.is_empty());
+    }
+
+    #[test]
+    fn ceremony_certificate() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap())
+                .with_blinder_opening(true);
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        assert!(participants[0].blinder_opening().is_none());
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        // Auditors check the feldman commitments match the Pedersen commitments
+        let opening = participants[1].blinder_opening().unwrap();
+        assert_eq!(opening.dealer, 2);
+        assert!(opening.verify(&r1bdata[&2], &r3bdata[&2]));
+        assert!(!opening.verify(&r1bdata[&2], &r3bdata[&3]));
+        assert!(!opening.verify(&r1bdata[&3], &r3bdata[&3]));
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        }
+        let signatures = participants
+            .iter_mut()
+            .map(|p| p.round5_signed(&r4bdata).unwrap())
+            .collect::<Vec<_>>();
+
+        let certificate = DkgCertificate::aggregate(&signatures[1..]).unwrap();
+        assert_eq!(certificate.public_key(), participants[0].get_public_key());
+        assert_eq!(
+            certificate.transcript_hash(),
+            Some(participants[0].get_transcript_hash())
+        );
+        let json = serde_json::to_string(&certificate).unwrap();
+        let restored: DkgCertificate<G> = serde_json::from_str(&json).unwrap();
+        assert!(restored.verify().is_ok());
+
+        assert!(DkgCertificate::aggregate(&signatures[..1]).is_err());
+        // Signers beyond the threshold must also hold shares of the key
+        let mut forged = signatures.clone();
+        forged[2] = CertificateSignature::sign(
+            3,
+            2,
+            signatures[2].generator,
+            signatures[2].public_key,
+            signatures[2].transcript_hash,
+            k256::Scalar::random(rand_core::OsRng),
+        )
+        .unwrap();
+        assert!(forged[2].verify());
+        assert!(DkgCertificate::aggregate(&forged[..2]).is_ok());
+        assert!(DkgCertificate::aggregate(&forged).is_err());
+        let mut forged = signatures.clone();
+        forged[2].public_share = G::generator();
+        assert!(DkgCertificate::aggregate(&forged).is_err());
+        let mut forged = signatures;
+        forged[1].transcript_hash = [0u8; 32];
+        assert!(DkgCertificate::aggregate(&forged).is_err());
+    }
+
+    #[derive(Default)]
+    struct CountingSink(AtomicUsize);
+
+    impl AuditSink for CountingSink {
+        fn record(&self, _entry: &AuditEntry) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn audit_log() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let record = CeremonyRecord::generate(parameters, [3u8; 32]).unwrap();
+        let mut rng = ChaCha8Rng::from_seed(record.seed);
+        rng.set_stream(1);
+        let mut participant =
+            SecretParticipant::<G>::with_rng(NonZeroUsize::new(1).unwrap(), parameters, rng)
+                .unwrap();
+        let sink = Arc::new(CountingSink::default());
+        participant.enable_audit_log();
+        participant.set_audit_sink(sink.clone());
+
+        participant.round1().unwrap();
+        let bdata = record
+            .broadcast
+            .round1_broadcast
+            .iter()
+            .filter(|(id, _)| **id != 1)
+            .map(|(id, b)| (*id, b.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let p2p = record
+            .round1_p2p
+            .iter()
+            .filter(|(id, _)| **id != 1)
+            .map(|(id, p2p)| (*id, p2p[&1].clone()))
+            .collect::<BTreeMap<_, _>>();
+        participant.round2(bdata, p2p).unwrap();
+        participant.round3(&record.broadcast.round2_echo).unwrap();
+        participant
+            .round4(&record.broadcast.round3_broadcast)
+            .unwrap();
+        participant.round5(&record.broadcast.round4_echo).unwrap();
+
+        let log = participant.export_audit_log();
+        // Two senders in each of rounds 2 to 5
+        assert_eq!(log.len(), 8);
+        assert_eq!(sink.0.load(Ordering::SeqCst), 8);
+        assert_eq!(log[0].round, Round::Two);
+        assert_eq!(log[0].sender, 2);
+        assert_eq!(
+            log.last().unwrap().hash,
+            AuditEntry::new(Round::Five, 3, &record.broadcast.round4_echo[&3]).hash
+        );
+        let json = serde_json::to_string(&participant).unwrap();
+        let restored: SecretParticipant<G> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.export_audit_log(), log);
+    }
+
+    #[test]
+    fn force_advance_with_absent_party() {
+        const THRESHOLD: usize = 2;
+        const LIMIT: usize = 4;
+        const ABSENT_ID: usize = 4;
+
+        let observer = Arc::new(CountingObserver::default());
+
+        let parameters = Parameters::<k256::ProjectivePoint>::new(
+            NonZeroUsize::new(THRESHOLD).unwrap(),
+            NonZeroUsize::new(LIMIT).unwrap(),
+        )
+        .with_quorum_policy(QuorumPolicy::WaitForAll);
+        let mut participants = (1..ABSENT_ID)
+            .map(|id| {
+                let mut p = SecretParticipant::<k256::ProjectivePoint>::new(
+                    NonZeroUsize::new(id).unwrap(),
+                    parameters,
+                )
+                .unwrap();
+                p.set_observer(observer.clone());
+                p
+            })
+            .collect::<Vec<_>>();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            let err = p.round2(bdata.clone(), p2pdata.clone()).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InsufficientData);
+            assert_eq!(err.severity(), Severity::Retryable);
+            assert_eq!(
+                p.round3(&BTreeMap::new()).unwrap_err().severity(),
+                Severity::Retryable
+            );
+            p.force_advance();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+            assert_eq!(p.get_absent_participant_ids().len(), 1);
+            assert!(p.get_absent_participant_ids().contains(&ABSENT_ID));
+        }
+
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            assert!(p.provisional_public_key().is_none());
+            let echo = p.round4(&r3bdata).unwrap();
+            assert_eq!(
+                p.provisional_public_key().map(|k| k.unconfirmed()),
+                Some(echo.public_key)
+            );
+            r4bdata.insert(p.get_id(), echo);
+        }
+        let transcript_hash = participants[0].get_transcript_hash();
+        for p in participants.iter_mut() {
+            assert!(p.round5(&r4bdata).is_ok());
+            assert!(p.provisional_public_key().is_none());
+            assert_eq!(p.get_public_key(), Some(r4bdata[&p.get_id()].public_key));
+            assert_eq!(p.get_transcript_hash(), transcript_hash);
+            assert!(p
+                .export_transcript()
+                .iter()
+                .any(|item| item.participant == ABSENT_ID && item.label == "absent"));
+        }
+        // Each participant verifies the other two in rounds 2 through 5
+        assert_eq!(observer.verified.load(Ordering::SeqCst), 24);
+        // and drops the absent participant in round 2
+        assert_eq!(observer.invalidated.load(Ordering::SeqCst), 3);
+        assert_eq!(observer.completed.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn force_advance_in_later_rounds() {
+        type G = k256::ProjectivePoint;
+        const ABSENT_ID: usize = 4;
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap())
+                .with_quorum_policy(QuorumPolicy::WaitForAll);
+        for absent_round in [Round::Three, Round::Four] {
+            let mut participants = (1..=4)
+                .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+                .collect::<DkgResult<Vec<_>>>()
+                .unwrap();
+
+            let mut r1bdata = BTreeMap::new();
+            let mut r1p2pdata = BTreeMap::new();
+            for p in participants.iter_mut() {
+                let (broadcast, p2p) = p.round1().unwrap();
+                r1bdata.insert(p.get_id(), broadcast);
+                r1p2pdata.insert(p.get_id(), p2p);
+            }
+            let mut r2bdata = BTreeMap::new();
+            for p in participants.iter_mut() {
+                let my_id = p.get_id();
+                let bdata = r1bdata
+                    .iter()
+                    .filter(|(id, _)| **id != my_id)
+                    .map(|(id, b)| (*id, b.clone()))
+                    .collect::<BTreeMap<_, _>>();
+                let p2pdata = r1p2pdata
+                    .iter()
+                    .filter(|(id, _)| **id != my_id)
+                    .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                    .collect::<BTreeMap<_, _>>();
+                r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+            }
+            // The shares dealt by the absent participant were combined in round 2
+            if absent_round == Round::Three {
+                participants.pop();
+                r2bdata.remove(&ABSENT_ID);
+                for p in participants.iter_mut() {
+                    let err = p.round3(&r2bdata).unwrap_err();
+                    assert_eq!(err.kind(), ErrorKind::InsufficientData);
+                    p.force_advance();
+                }
+            }
+            let mut r3bdata = BTreeMap::new();
+            for p in participants.iter_mut() {
+                r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+            }
+            if absent_round == Round::Four {
+                participants.pop();
+                r3bdata.remove(&ABSENT_ID);
+                for p in participants.iter_mut() {
+                    let err = p.round4(&r3bdata).unwrap_err();
+                    assert_eq!(err.kind(), ErrorKind::InsufficientData);
+                    p.force_advance();
+                }
+            }
+            let mut r4bdata = BTreeMap::new();
+            let mut shares = Vec::new();
+            for p in participants.iter_mut() {
+                r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+                assert!(p.get_absent_participant_ids().contains(&ABSENT_ID));
+                assert!(!p.get_valid_participant_ids().contains(&ABSENT_ID));
+                shares.push(
+                    <InnerShare as Share>::from_field_element(
+                        p.get_id() as u8,
+                        p.get_secret_share().unwrap(),
+                    )
+                    .unwrap(),
+                );
+            }
+            for p in participants.iter_mut() {
+                p.round5(&r4bdata).unwrap();
+                let output = p.output().unwrap();
+                assert_eq!(output.report.dropped.len(), 1);
+                assert_eq!(output.report.dropped[0].round, absent_round);
+            }
+            let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+            assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
+        }
+    }
+
+    #[test]
+    fn round_deadlines() {
+        use std::sync::atomic::AtomicU64;
+        use std::time::Duration;
+
+        #[derive(Default)]
+        struct ManualClock(AtomicU64);
+
+        impl Clock for ManualClock {
+            fn now(&self) -> Duration {
+                Duration::from_secs(self.0.load(Ordering::SeqCst))
+            }
+        }
+
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap())
+                .with_quorum_policy(QuorumPolicy::WaitForAll)
+                .with_round_deadline(Round::Two, Duration::from_secs(30));
+        assert_eq!(
+            parameters.round_deadline(Round::Two),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parameters.round_deadline(Round::Three), None);
+        let mut participants = (1..=2)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let clock = Arc::new(ManualClock::default());
+        participants[0].set_clock(clock.clone());
+
+        let (_, mut p2p) = participants[0].round1().unwrap();
+        let (broadcast, _) = participants[1].round1().unwrap();
+        let bdata = BTreeMap::from([(2, broadcast)]);
+        let p2pdata = BTreeMap::from([(2, p2p.remove(&2).unwrap())]);
+        let p = &mut participants[0];
+        assert!(!p.deadline_exceeded());
+        assert!(!p.force_advance_if_expired());
+        // Participant 3 never sends data
+        let err = p.round2(bdata.clone(), p2pdata.clone()).unwrap_err();
+        assert_eq!(err.severity(), Severity::Retryable);
+
+        clock.0.store(30, Ordering::SeqCst);
+        assert!(p.deadline_exceeded());
+        assert!(p.force_advance_if_expired());
+        p.round2(bdata, p2pdata).unwrap();
+        assert!(p.get_absent_participant_ids().contains(&3));
+        // Round 3 has no deadline
+        clock.0.store(1000, Ordering::SeqCst);
+        assert!(!p.deadline_exceeded());
+    }
+
+    #[test]
+    fn full_participation() {
+        type G = k256::ProjectivePoint;
+        let parameters = Parameters::<G>::builder()
+            .threshold(2)
+            .limit(3)
+            .full_participation(true)
+            .build()
+            .unwrap();
+        assert!(parameters.full_participation());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        run_ceremony(&mut participants).unwrap();
+
+        let mut participants = (1..=2)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let (_, mut p2p) = participants[0].round1().unwrap();
+        let (broadcast, _) = participants[1].round1().unwrap();
+        let bdata = BTreeMap::from([(2, broadcast)]);
+        let p2pdata = BTreeMap::from([(2, p2p.remove(&2).unwrap())]);
+        let p = &mut participants[0];
+        // Participant 3 never sends data
+        let err = p.round2(bdata.clone(), p2pdata.clone()).unwrap_err();
+        assert_eq!(err.severity(), Severity::Retryable);
+        p.force_advance();
+        assert_eq!(p.get_round(), Round::Aborted);
+        let err = p.round2(bdata, p2pdata).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Aborted);
+    }
+
+    #[test]
+    fn recover_disqualified_dealer() {
+        type G = k256::ProjectivePoint;
+        const BAD_ID: usize = 4;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut participants = (1..=4)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        // The bad dealer's feldman commitments don't match the shares it sent
+        r3bdata.get_mut(&BAD_ID).unwrap().commitments[1] += G::generator();
+        participants.pop();
+
+        let mut recovery_data = BTreeMap::new();
+        for p in participants.iter_mut() {
+            p.round4(&r3bdata).unwrap();
+            assert_eq!(
+                p.get_disqualified_dealer_ids().iter().collect::<Vec<_>>(),
+                vec![&BAD_ID]
+            );
+            assert_eq!(
+                p.round5(&BTreeMap::new()).unwrap_err().kind(),
+                ErrorKind::Round
+            );
+            recovery_data.insert(p.get_id(), p.recovery_shares().unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        let mut shares = Vec::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(
+                p.get_id(),
+                p.recover_disqualified_dealers(&recovery_data).unwrap(),
+            );
+            assert!(p.get_disqualified_dealer_ids().is_empty());
+            shares.push(
+                <InnerShare as Share>::from_field_element(
+                    p.get_id() as u8,
+                    p.get_secret_share().unwrap(),
+                )
+                .unwrap(),
+            );
+        }
+        for p in participants.iter_mut() {
+            assert!(p.round5(&r4bdata).is_ok());
+        }
+        let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+        assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
+    }
+
+    #[test]
+    fn batch_participants() {
+        type G = k256::ProjectivePoint;
+        const SECRETS: usize = 3;
+        assert!(Parameters::<G>::builder()
+            .threshold(2)
+            .limit(3)
+            .secret_count(0)
+            .build()
+            .is_err());
+        let parameters = Parameters::<G>::builder()
+            .threshold(2)
+            .limit(3)
+            .secret_count(SECRETS)
+            .build()
+            .unwrap();
+        let mut participants = (1..=3)
+            .map(|id| BatchSecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(participants[0].secret_count(), SECRETS);
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        }
+        for p in participants.iter_mut() {
+            p.round5(&r4bdata).unwrap();
+            assert!(p.completed());
+        }
+
+        let public_keys = participants[0].get_public_keys().unwrap();
+        for (i, public_key) in public_keys.iter().enumerate() {
+            let shares = participants
+                .iter()
+                .map(|p| {
+                    <InnerShare as Share>::from_field_element(
+                        p.get_id() as u8,
+                        p.get_secret_shares().unwrap()[i],
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+            assert_eq!(*public_key, G::generator() * secret);
+            assert!(public_keys[..i].iter().all(|pk| pk != public_key));
+        }
+    }
+
+    fn run_ceremony<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default>(
+        participants: &mut [Participant<I, G>],
+    ) -> DkgResult<()> {
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1()?;
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata)?);
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata)?);
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata)?);
+        }
+        for p in participants.iter_mut() {
+            p.round5(&r4bdata)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn key_exchange() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let signing_keys = (0..3)
+            .map(|_| k256::Scalar::random(rand_core::OsRng))
+            .collect::<Vec<_>>();
+        let registry = signing_keys.iter().enumerate().fold(
+            ParticipantRegistry::new(),
+            |registry, (i, key)| {
+                registry.with_identity(
+                    i + 1,
+                    ParticipantIdentity {
+                        verifying_key: Some(
+                            (parameters.message_generator * key).to_bytes().to_vec(),
+                        ),
+                        ..Default::default()
+                    },
+                )
+            },
+        );
+        let new_participant = |id: usize| {
+            let mut p =
+                SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters).unwrap();
+            p.set_registry(registry.clone()).unwrap();
+            p
+        };
+        let mut participants = (1..=3).map(new_participant).collect::<Vec<_>>();
+        let exchanges = participants
+            .iter()
+            .zip(&signing_keys)
+            .map(|(p, key)| KeyExchange::new(p, key).unwrap())
+            .collect::<Vec<_>>();
+        let r0bdata = exchanges
+            .iter()
+            .zip(&participants)
+            .map(|(e, p)| (p.get_id(), e.round0()))
+            .collect::<BTreeMap<_, _>>();
+        let keys = exchanges
+            .into_iter()
+            .zip(participants.iter_mut())
+            .map(|(e, p)| e.finish(p, &r0bdata).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(keys[0].ids().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(keys[0].get(2), keys[1].get(1));
+        assert_eq!(keys[1].get(3), keys[2].get(2));
+        assert_ne!(keys[0].get(2), keys[0].get(3));
+
+        // The peer data is sealed for one recipient
+        let p2p = Round1P2PData {
+            secret_share: vec![1, 2, 3],
+            blind_share: vec![4, 5, 6],
+        };
+        let sealed = keys[0].seal(2, &p2p).unwrap();
+        let opened = keys[1].open(1, &sealed).unwrap();
+        assert_eq!(opened.secret_share, p2p.secret_share);
+        assert_eq!(opened.blind_share, p2p.blind_share);
+        assert_eq!(keys[2].open(1, &sealed).unwrap_err().sender(), Some(1));
+        assert_eq!(keys[1].open(3, &sealed).unwrap_err().sender(), Some(3));
+        assert!(keys[0].seal(4, &p2p).is_err());
+
+        // A substituted or unsigned encryption key is rejected
+        let mut p = new_participant(1);
+        let mut forged = r0bdata.clone();
+        forged.get_mut(&2).unwrap().encryption_key = G::generator();
+        let err = KeyExchange::new(&p, &signing_keys[0])
+            .unwrap()
+            .finish(&mut p, &forged)
+            .unwrap_err();
+        assert_eq!(err.sender(), Some(2));
+        forged.get_mut(&2).unwrap().encryption_key = G::identity();
+        let err = KeyExchange::new(&p, &signing_keys[0])
+            .unwrap()
+            .finish(&mut p, &forged)
+            .unwrap_err();
+        assert_eq!(err.sender(), Some(2));
+        // Participants without verifying keys can't be authenticated
+        let mut p = SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        assert!(KeyExchange::new(&p, &signing_keys[0])
+            .unwrap()
+            .finish(&mut p, &r0bdata)
+            .is_err());
+
+        run_ceremony(&mut participants).unwrap();
+        for p in &participants {
+            assert_eq!(
+                p.get_transcript_hash(),
+                participants[0].get_transcript_hash()
+            );
+            assert_eq!(
+                p.export_transcript()
+                    .iter()
+                    .filter(|item| item.label == "encryption_key")
+                    .count(),
+                3
+            );
+        }
+        // The keys must be exchanged before round 1
+        assert!(KeyExchange::new(&participants[0], &signing_keys[0])
+            .unwrap()
+            .finish(&mut participants[0], &BTreeMap::new())
+            .is_err());
+    }
+
+    #[test]
+    fn key_import() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let owner = NonZeroUsize::new(1).unwrap();
+        let key = k256::Scalar::random(rand_core::OsRng);
+        let public_key = parameters.message_generator * key;
+        let holder = |id| {
+            ImportParticipant::<G>::key_holder(
+                NonZeroUsize::new(id).unwrap(),
+                parameters,
+                owner,
+                public_key,
+            )
+            .unwrap()
+        };
+        assert!(ImportParticipant::<G>::key_holder(owner, parameters, owner, public_key).is_err());
+        assert!(ImportParticipant::<G>::key_owner(owner, parameters, k256::Scalar::ZERO).is_err());
+
+        let mut participants = vec![
+            ImportParticipant::<G>::key_owner(owner, parameters, key).unwrap(),
+            holder(2),
+            holder(3),
+        ];
+        assert_eq!(participants[1].get_key_owner(), Some(1));
+        run_ceremony(&mut participants).unwrap();
+        let outputs = participants
+            .iter()
+            .map(|p| p.output().unwrap())
+            .collect::<Vec<_>>();
+        assert!(outputs.iter().all(|o| o.public_key == public_key));
+        let shares = outputs
+            .iter()
+            .skip(1)
+            .map(|o| {
+                let (id, share) = o.secret_shares.iter().next().unwrap();
+                <InnerShare as Share>::from_field_element(*id as u8, *share).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+        assert_eq!(secret, key);
+
+        // A holder that deals a secret would change the key
+        let other = k256::Scalar::random(rand_core::OsRng);
+        let mut participants = vec![
+            ImportParticipant::<G>::key_owner(owner, parameters, key).unwrap(),
+            holder(2),
+            ImportParticipant::<G>::key_owner(NonZeroUsize::new(3).unwrap(), parameters, other)
+                .unwrap(),
+        ];
+        assert!(matches!(
+            run_ceremony(&mut participants),
+            Err(Error::SenderError {
+                error: ParticipantError::MismatchedImportedKey(3),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn supervised_export() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let request = ExportRequest::new(
+            G::generator(),
+            [0u8; 32],
+            "escrow",
+            "migrate to a new custody provider",
+        );
+        assert!(participants[0].approve_export(&request).is_err());
+        run_ceremony(&mut participants).unwrap();
+        assert!(participants[0].approve_export(&request).is_err());
+
+        let public_key = participants[0].get_public_key().unwrap();
+        let request = ExportRequest::new(
+            public_key,
+            participants[0].get_transcript_hash(),
+            "escrow",
+            "migrate to a new custody provider",
+        );
+        let shares = participants[1..]
+            .iter()
+            .map(|p| p.approve_export(&request).unwrap())
+            .collect::<Vec<_>>();
+        assert!(format!("{:?}", shares[0]).contains("<redacted>"));
+        assert!(ExportRecord::reconstruct(request.clone(), &shares[..1]).is_err());
+
+        let (key, record) = ExportRecord::reconstruct(request.clone(), &shares).unwrap();
+        assert_eq!(G::generator() * key, public_key);
+        record.verify().unwrap();
+        assert!(record.approvals.keys().eq([2, 3].iter()));
+
+        // An approval cannot be reused for another recipient
+        let mut tampered = record.clone();
+        tampered.request.recipient = "attacker".to_string();
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn linked_participants() {
+        type A = k256::ProjectivePoint;
+        type B = vsss_rs::curve25519::WrappedRistretto;
+        let threshold = NonZeroUsize::new(2).unwrap();
+        let limit = NonZeroUsize::new(3).unwrap();
+        assert!(LinkedParticipant::<A, B>::new(
+            NonZeroUsize::new(1).unwrap(),
+            Parameters::new(threshold, limit),
+            Parameters::new(threshold, NonZeroUsize::new(4).unwrap()),
+        )
+        .is_err());
+        let mut participants = (1..=3)
+            .map(|id| {
+                LinkedParticipant::<A, B>::new(
+                    NonZeroUsize::new(id).unwrap(),
+                    Parameters::new(threshold, limit),
+                    Parameters::new(threshold, limit),
+                )
+            })
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        }
+        for p in participants.iter_mut() {
+            p.round5(&r4bdata).unwrap();
+            assert!(p.completed());
+        }
+
+        let (first_key, second_key) = participants[0].get_public_keys().unwrap();
+        let hash = participants[0].get_transcript_hash();
+        for p in &participants {
+            assert_eq!(p.get_public_keys(), Some((first_key, second_key)));
+            assert_eq!(p.get_transcript_hash(), hash);
+        }
+        let transcript = participants[0].first().export_transcript();
+        assert!(transcript
+            .iter()
+            .any(|item| item.label == "linked_public_key"
+                && item.data == second_key.to_bytes().as_ref()));
+
+        // A failure on the second curve aborts both
+        let mut participant = LinkedParticipant::<A, B>::new(
+            NonZeroUsize::new(1).unwrap(),
+            Parameters::new(threshold, limit),
+            Parameters::new(threshold, limit),
+        )
+        .unwrap();
+        participant.round1().unwrap();
+        let mut bdata = r1bdata.clone();
+        bdata.remove(&1);
+        let mut p2pdata = BTreeMap::new();
+        for (id, p2p) in r1p2pdata.iter().filter(|(id, _)| **id != 1) {
+            p2pdata.insert(*id, p2p[&1].clone());
+        }
+        for (_, second) in bdata.values_mut() {
+            second.pedersen_commitments[0] = B::generator();
+        }
+        assert!(participant.round2(bdata, p2pdata).is_err());
+        assert_eq!(participant.first().get_round(), Round::Aborted);
+        assert_eq!(participant.second().get_round(), Round::Aborted);
+    }
+
+    #[test]
+    fn recovery_data() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let seed = [7u8; RECOVERY_SEED_LENGTH];
+        assert!(participants[0].recovery_data(&seed).is_err());
+        run_ceremony(&mut participants).unwrap();
+        assert!(participants[0].recovery_data(&seed[1..]).is_err());
+
+        let data = participants[0].recovery_data(&seed).unwrap();
+        let bytes = serde_bare::to_vec(&data).unwrap();
+        let data: RecoveryData<G> = serde_bare::from_slice(&bytes).unwrap();
+        let output = data.recover(&seed).unwrap();
+        let expected = participants[0].output().unwrap();
+        assert_eq!(output.public_key, expected.public_key);
+        assert_eq!(output.secret_shares, expected.secret_shares);
+        assert_eq!(output.report(), expected.report());
+
+        assert!(data.recover(&[8u8; RECOVERY_SEED_LENGTH]).is_err());
+        let mut tampered = data.clone();
+        tampered.transcript_hash[0] ^= 1;
+        assert!(tampered.recover(&seed).is_err());
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn mnemonic_share() {
+        use mnemonic::MnemonicShare;
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let outputs = simulator::Simulator::run(parameters, &[Default::default(); 3]).unwrap();
+        let share = MnemonicShare::from_output(&outputs[0]).remove(0);
+        assert!(format!("{:?}", share).contains("<redacted>"));
+        let phrase = share.to_phrase().unwrap();
+        // 9 byte header, 32 byte share and 4 byte checksum in 11 bit words
+        assert_eq!(phrase.split(' ').count(), 33);
+
+        let restored = MnemonicShare::<G>::from_phrase(&phrase.to_uppercase()).unwrap();
+        assert_eq!(restored.id, 1);
+        assert_eq!(restored.threshold, 2);
+        assert_eq!(restored.share, outputs[0].secret_shares[&1]);
+        assert!(restored.matches(&outputs[0].public_key));
+        assert!(!restored.matches(&G::generator()));
+
+        let mut words = phrase.split(' ').collect::<Vec<_>>();
+        words[5] = if words[5] == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert!(MnemonicShare::<G>::from_phrase(&words.join(" ")).is_err());
+        assert!(MnemonicShare::<G>::from_phrase(&words[1..].join(" ")).is_err());
+        assert!(MnemonicShare::<G>::from_phrase("not a mnemonic").is_err());
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn bn254_precompile_bytes() {
+        use halo2curves::bn256::G1;
+
+        let bytes = bn254::to_precompile_bytes(&G1::generator());
+        let mut expected = [0u8; 64];
+        expected[31] = 1;
+        expected[63] = 2;
+        assert_eq!(bytes, expected);
+        assert_eq!(bn254::to_precompile_bytes(&G1::identity()), [0u8; 64]);
+    }
+
+    #[cfg(feature = "noise")]
+    #[test]
+    fn noise_channel() {
+        use noise::*;
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participant =
+            SecretParticipant::<G>::new(NonZeroUsize::new(1).unwrap(), parameters).unwrap();
+        let (_, p2p) = participant.round1().unwrap();
+
+        let alice = NoiseKeypair::generate().unwrap();
+        let bob = NoiseKeypair::generate().unwrap();
+        assert!(format!("{:?}", alice).contains("<redacted>"));
+        assert!(NoiseHandshake::initiator(NoisePattern::IK, &alice, None, b"session").is_err());
+        for pattern in [NoisePattern::XX, NoisePattern::IK] {
+            let mut initiator =
+                NoiseHandshake::initiator(pattern, &alice, Some(&bob.public), b"session").unwrap();
+            let mut responder = NoiseHandshake::responder(pattern, &bob, b"session").unwrap();
+            while !initiator.is_finished() || !responder.is_finished() {
+                let message = initiator.write_message().unwrap();
+                responder.read_message(&message).unwrap();
+                if responder.is_finished() && initiator.is_finished() {
+                    break;
+                }
+                let message = responder.write_message().unwrap();
+                initiator.read_message(&message).unwrap();
+            }
+            let mut initiator = initiator.into_channel().unwrap();
+            let mut responder = responder.into_channel().unwrap();
+            assert_eq!(initiator.remote_public_key(), Some(bob.public.as_slice()));
+            assert_eq!(responder.remote_public_key(), Some(alice.public.as_slice()));
+
+            let sealed = initiator.seal(&p2p[&2]).unwrap();
+            let opened = responder.open(&sealed).unwrap();
+            assert_eq!(opened.secret_share, p2p[&2].secret_share);
+            assert!(responder.open(&sealed).is_err());
+        }
+
+        // A different prologue fails the handshake
+        let mut initiator =
+            NoiseHandshake::initiator(NoisePattern::XX, &alice, None, b"session").unwrap();
+        let mut responder = NoiseHandshake::responder(NoisePattern::XX, &bob, b"other").unwrap();
+        responder
+            .read_message(&initiator.write_message().unwrap())
+            .unwrap();
+        assert!(initiator
+            .read_message(&responder.write_message().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn weighted_participants() {
+        type G = k256::ProjectivePoint;
+        let weights = [(1, 1), (2, 2), (3, 3)]
+            .into_iter()
+            .map(|(party, weight)| (party, NonZeroUsize::new(weight).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let share_ids = weighted_share_ids(&weights);
+        assert_eq!(share_ids[&1], 1..=1);
+        assert_eq!(share_ids[&2], 2..=3);
+        assert_eq!(share_ids[&3], 4..=6);
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(5).unwrap());
+        assert!(WeightedSecretParticipant::<G>::new(1, &weights, parameters).is_err());
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(6).unwrap());
+        assert!(WeightedSecretParticipant::<G>::new(4, &weights, parameters).is_err());
+        let mut parties = weights
+            .keys()
+            .map(|party| WeightedSecretParticipant::<G>::new(*party, &weights, parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in parties.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.extend(broadcast);
+            r1p2pdata.extend(p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in parties.iter_mut() {
+            r2bdata.extend(p.round2(&r1bdata, &r1p2pdata).unwrap());
+        }
+        let mut r3bdata = BTreeMap::new();
+        for p in parties.iter_mut() {
+            r3bdata.extend(p.round3(&r2bdata).unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in parties.iter_mut() {
+            r4bdata.extend(p.round4(&r3bdata).unwrap());
+        }
+        for p in parties.iter_mut() {
+            p.round5(&r4bdata).unwrap();
+        }
+
+        // The heaviest party alone doesn't reach the threshold, with any other it does
+        let outputs = parties
+            .iter()
+            .map(|p| p.output().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(outputs[2].secret_shares.len(), 3);
+        assert_eq!(outputs[2].report().public_share_fingerprints.len(), 3);
+        let shares = outputs[0]
+            .secret_shares
+            .iter()
+            .chain(outputs[2].secret_shares.iter())
+            .map(|(id, share)| {
+                <InnerShare as Share>::from_field_element(*id as u8, *share).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+        assert_eq!(outputs[0].public_key, G::generator() * secret);
+        assert_eq!(outputs[1].public_key, outputs[0].public_key);
+    }
+
+    #[test]
+    fn catch_up() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut archive = BroadcastArchive::default();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            archive.round1_broadcast.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        // Participant 3 restarts from a checkpoint taken after round 1
+        let mut checkpoint = participants[2].clone();
+        let received = r1p2pdata
+            .iter()
+            .filter(|(id, _)| **id != 3)
+            .map(|(id, p2p)| (*id, p2p[&3].clone()))
+            .collect::<BTreeMap<_, _>>();
+        assert!(
+            SecretParticipant::<G>::new(NonZeroUsize::new(3).unwrap(), parameters)
+                .unwrap()
+                .catch_up(&archive, received.clone())
+                .is_err()
+        );
+
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = archive
+                .round1_broadcast
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            archive
+                .round2_echo
+                .insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        for p in participants.iter_mut() {
+            archive
+                .round3_broadcast
+                .insert(p.get_id(), p.round3(&archive.round2_echo).unwrap());
+        }
+        for p in participants.iter_mut() {
+            archive
+                .round4_echo
+                .insert(p.get_id(), p.round4(&archive.round3_broadcast).unwrap());
+        }
+
+        // Without its own round 4 data the participant rejoins and sends it
+        let mut partial = archive.clone();
+        partial.round4_echo.clear();
+        let mut rejoined = checkpoint.clone();
+        assert!(matches!(
+            rejoined.catch_up(&partial, received.clone()).unwrap(),
+            Some(RoundOutput::Round4(_))
+        ));
+        assert_eq!(rejoined.get_round(), Round::Five);
+
+        assert!(matches!(
+            checkpoint.catch_up(&archive, received).unwrap(),
+            Some(RoundOutput::Complete)
+        ));
+        assert_eq!(
+            checkpoint.get_public_key(),
+            participants[0].get_public_key()
+        );
+        assert_eq!(
+            checkpoint.get_secret_share(),
+            participants[2].get_secret_share()
+        );
+    }
+
+    #[test]
+    fn reconcile_by_intersection() {
+        type G = k256::ProjectivePoint;
+        const LOST_ID: usize = 4;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap())
+                .with_reconciliation_policy(ReconciliationPolicy::Intersection);
+        let mut participants = (1..=4)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            participants[0].get_reconciliation_policy(),
+            ReconciliationPolicy::Intersection
+        );
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            // Participant 1 never receives the data from participant 4
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id && (my_id != 1 || **id != LOST_ID))
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id && (my_id != 1 || **id != LOST_ID))
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+
+        let mut json = serde_json::to_value(&participants[1]).unwrap();
+        json["reconciliation_policy"] = serde_json::json!("Strict");
+        let mut strict: SecretParticipant<G> = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            strict.round3(&r2bdata).unwrap_err().kind(),
+            ErrorKind::InvalidSender
+        );
+        assert!(participants[3].round3(&r2bdata).is_err());
+        participants.pop();
+
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+            assert!(!p.get_valid_participant_ids().contains(&LOST_ID));
+        }
+        let mut r4bdata = BTreeMap::new();
+        let mut shares = Vec::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+            shares.push(
+                <InnerShare as Share>::from_field_element(
+                    p.get_id() as u8,
+                    p.get_secret_share().unwrap(),
+                )
+                .unwrap(),
+            );
+        }
+        for p in participants.iter_mut() {
+            assert!(p.round5(&r4bdata).is_ok());
+        }
+        let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+        assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
+        let blinder = participants
+            .iter()
+            .map(|p| p.get_blinder())
+            .sum::<k256::Scalar>();
+        assert_eq!(
+            participants[0].get_blind_key(),
+            Some(parameters.blinder_generator() * blinder)
+        );
+    }
+
+    #[test]
+    fn rehabilitate_late_participant() {
+        type G = k256::ProjectivePoint;
+        const LATE_ID: usize = 4;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap())
+                .with_grace_period(1);
+        let mut participants = (1..=4)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            // The data from participant 4 is late so the others force round 2
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id && **id != LATE_ID)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id && **id != LATE_ID)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            if my_id != LATE_ID {
+                p.force_advance();
+            }
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        for p in participants.iter_mut().take(3) {
+            let my_id = p.get_id();
+            assert!(p.get_absent_participant_ids().contains(&LATE_ID));
+            assert!(p
+                .rehabilitate(1, r1bdata[&1].clone(), r1p2pdata[&1][&my_id].clone())
+                .is_err());
+            let mut bad = r1p2pdata[&LATE_ID][&my_id].clone();
+            bad.secret_share = r1p2pdata[&LATE_ID][&(my_id % 3 + 1)].secret_share.clone();
+            assert!(p
+                .rehabilitate(LATE_ID, r1bdata[&LATE_ID].clone(), bad)
+                .is_err());
+            p.rehabilitate(
+                LATE_ID,
+                r1bdata[&LATE_ID].clone(),
+                r1p2pdata[&LATE_ID][&my_id].clone(),
+            )
+            .unwrap();
+            assert!(p.get_valid_participant_ids().contains(&LATE_ID));
+            assert!(p.get_absent_participant_ids().is_empty());
+        }
+
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        // Late data is no longer accepted once round 3 is computed
+        assert!(participants[0]
+            .rehabilitate(
+                LATE_ID,
+                r1bdata[&LATE_ID].clone(),
+                r1p2pdata[&LATE_ID][&1].clone(),
+            )
+            .is_err());
+        let mut r4bdata = BTreeMap::new();
+        let mut shares = Vec::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+            shares.push(
+                <InnerShare as Share>::from_field_element(
+                    p.get_id() as u8,
+                    p.get_secret_share().unwrap(),
+                )
+                .unwrap(),
+            );
+        }
+        for p in participants.iter_mut() {
+            assert!(p.round5(&r4bdata).is_ok());
+        }
+        let secret = combine_shares::<k256::Scalar, u8, InnerShare>(&shares).unwrap();
+        assert_eq!(r4bdata[&1].public_key, G::generator() * secret);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn byzantine_participant() {
+        use testing::{Attack, ByzantineParticipant};
+        type G = k256::ProjectivePoint;
+        const BAD_ID: usize = 4;
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut participants = (1..BAD_ID)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let mut bad = ByzantineParticipant::new(
+            SecretParticipant::<G>::new(NonZeroUsize::new(BAD_ID).unwrap(), parameters).unwrap(),
+        )
+        .with_attack(Attack::InconsistentCommitments)
+        .with_attack(Attack::WrongPublicKey);
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let (bad_broadcast, bad_p2p) = bad.round1().unwrap();
+        assert!(bad.replay_round1().is_some());
+
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let mut bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            bdata.insert(BAD_ID, bad_broadcast[&my_id].clone());
+            let mut p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            p2pdata.insert(BAD_ID, bad_p2p[&my_id].clone());
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        let bdata = r1bdata.clone();
+        let p2pdata = r1p2pdata
+            .iter()
+            .map(|(id, p2p)| (*id, p2p[&BAD_ID].clone()))
+            .collect::<BTreeMap<_, _>>();
+        r2bdata.insert(BAD_ID, bad.round2(bdata, p2pdata).unwrap()[&1].clone());
+
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        r3bdata.insert(BAD_ID, bad.round3(&r2bdata).unwrap()[&1].clone());
+        for p in participants.iter_mut() {
+            p.round4(&r3bdata).unwrap();
+            assert!(p.get_disqualified_dealer_ids().contains(&BAD_ID));
+        }
+        assert!(
+            bad.round4(&r3bdata).unwrap()[&1].public_key
+                != bad
+                    .participant()
+                    .provisional_public_key()
+                    .unwrap()
+                    .unconfirmed()
+        );
+    }
+
+    #[test]
+    fn known_answer_ceremony() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let record = CeremonyRecord::generate(parameters, [7u8; 32]).unwrap();
+        assert_eq!(record.outputs.len(), 3);
+        let bytes = record.to_bytes().unwrap();
+        let restored = CeremonyRecord::<G>::from_bytes(&bytes).unwrap();
+        assert!(restored.verify().is_ok());
+
+        let mut tampered = restored.clone();
+        tampered
+            .broadcast
+            .round4_echo
+            .get_mut(&1)
+            .unwrap()
+            .public_key = G::generator();
+        let err = tampered.verify().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Round);
+        assert!(err.to_string().contains("round 4"));
+    }
+
+    #[test]
+    fn simulator() {
+        use simulator::{Behavior, Simulator};
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+
+        let outputs = Simulator::run(parameters, &[Behavior::Honest; 4]).unwrap();
+        assert_eq!(outputs.len(), 4);
+        let report = outputs[0].report();
+        assert_eq!(report.participant, 1);
+        assert_eq!(report.participants, (1..=4).collect::<BTreeSet<_>>());
+        assert!(report.dropped.is_empty());
+        assert_eq!(
+            report
+                .contributions
+                .keys()
+                .copied()
+                .collect::<BTreeSet<_>>(),
+            report.participants
+        );
+        assert!(outputs
+            .iter()
+            .all(|o| o.report().transcript_hash == report.transcript_hash
+                && o.report().public_key_fingerprint == report.public_key_fingerprint
+                && o.report().contributions == report.contributions));
+        assert!(report.started_at <= report.finished_at);
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<DkgReport>(&json).unwrap(), report);
+        let shares = outputs
+            .iter()
+            .flat_map(|o| o.secret_shares.iter())
+            .take(2)
+            .map(|(id, s)| <InnerShare as Share>::from_field_element(*id as u8, *s).unwrap())
+            .collect::<Vec<_>>();
+        let secret: k256::Scalar = combine_shares(&shares).unwrap();
+        assert!(outputs
+            .iter()
+            .all(|o| o.public_key == G::generator() * secret));
+
+        let behaviors = [
+            Behavior::Honest,
+            Behavior::Offline,
+            Behavior::Crash(Round::Two),
+            Behavior::Offline,
+        ];
+        let outputs = Simulator::run(parameters, &behaviors).unwrap();
+        assert!(outputs.is_empty());
+
+        let outputs = Simulator::new()
+            .with_packet_loss(1.0)
+            .simulate(parameters, &[Behavior::Honest; 4])
+            .unwrap();
+        assert!(outputs.is_empty());
+        assert!(Simulator::run(parameters, &[Behavior::Honest; 3]).is_err());
+    }
+
+    #[cfg(feature = "testing")]
+    fn run_faulty_round2(
+        faults: &[(usize, testing::Fault)],
+    ) -> (
+        Vec<SecretParticipant<k256::ProjectivePoint>>,
+        BTreeMap<usize, Round2EchoBroadcastData>,
+    ) {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        for (id, fault) in faults {
+            participants[*id - 1].inject_fault(*fault);
+        }
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2(bdata, p2pdata).unwrap());
+        }
+        (participants, r2bdata)
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn inject_faults() {
+        use testing::Fault;
+
+        let (participants, _) = run_faulty_round2(&[(3, Fault::ZeroShare(1))]);
+        assert!(!participants[0].get_valid_participant_ids().contains(&3));
+        assert!(participants[1].get_valid_participant_ids().contains(&3));
+
+        let (mut participants, r2bdata) = run_faulty_round2(&[(2, Fault::FlipPublicKey)]);
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3(&r2bdata).unwrap());
+        }
+        let mut r4bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r4bdata.insert(p.get_id(), p.round4(&r3bdata).unwrap());
+        }
+        let err = participants[0].round5(&r4bdata).unwrap_err();
+        assert_eq!(err.sender(), Some(2));
+    }
+
+    #[cfg(feature = "testing")]
+    fn fuzz_parameters() -> Parameters<k256::ProjectivePoint> {
+        Parameters::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap())
+    }
+
+    #[cfg(feature = "testing")]
+    proptest::proptest! {
+        #[test]
+        fn fuzz_round2(
+            bdata in testing::strategies::invalid_round1_broadcast_data(fuzz_parameters()),
+            p2p in testing::strategies::invalid_round1_p2p_data::<k256::ProjectivePoint>(1),
+            valid_bdata in testing::strategies::round1_broadcast_data(fuzz_parameters()),
+            valid_p2p in testing::strategies::round1_p2p_data::<k256::ProjectivePoint>(1),
+        ) {
+            let mut participant =
+                SecretParticipant::new(NonZeroUsize::new(1).unwrap(), fuzz_parameters()).unwrap();
+            participant.round1().unwrap();
+            let snapshot = participant.clone();
+            let result = participant.round2(
+                maplit::btreemap! { 2 => bdata, 3 => valid_bdata },
+                maplit::btreemap! { 2 => p2p, 3 => valid_p2p },
+            );
+            // Neither sender verifies so the round can't complete
+            proptest::prop_assert!(result.is_err());
+            proptest::prop_assert_eq!(participant.get_round(), snapshot.get_round());
+        }
+
+        #[test]
+        fn fuzz_round3(
+            echo in testing::strategies::invalid_round2_echo_broadcast_data(3),
+        ) {
+            let (mut participants, mut r2bdata) = run_faulty_round2(&[]);
+            proptest::prop_assert_eq!(participants[0].get_round(), Round::Three);
+            r2bdata.insert(2, echo);
+            proptest::prop_assert!(participants[0].round3(&r2bdata).is_err());
+            proptest::prop_assert_eq!(participants[0].get_round(), Round::Three);
+        }
+    }
+
+    #[test]
+    fn merged_rounds() {
+        type G = k256::ProjectivePoint;
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2_merged(bdata, p2pdata).unwrap());
+        }
+
+        let mut r3bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            r3bdata.insert(p.get_id(), p.round3_merged(&r2bdata).unwrap());
+        }
+        for p in participants.iter_mut() {
+            assert!(p.round5(&r3bdata).is_ok());
+            assert_eq!(p.get_public_key(), Some(r3bdata[&1].public_key));
+        }
+
+        // All participants are honest so one confirmation completes the fast path
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (broadcast, p2p) = p.round1().unwrap();
+            r1bdata.insert(p.get_id(), broadcast);
+            r1p2pdata.insert(p.get_id(), p2p);
+        }
+        let mut r2bdata = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let my_id = p.get_id();
+            let bdata = r1bdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, b)| (*id, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2pdata = r1p2pdata
+                .iter()
+                .filter(|(id, _)| **id != my_id)
+                .map(|(id, p2p)| (*id, p2p[&my_id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            r2bdata.insert(my_id, p.round2_merged(bdata, p2pdata).unwrap());
+        }
+        // Without everyone's data the fast path is refused and nothing changes
+        let mut partial = r2bdata.clone();
+        partial.remove(&3);
+        assert!(participants[0].round3_fast(&partial).is_err());
+        assert_eq!(participants[0].get_round(), Round::Three);
+
+        let mut confirmations = BTreeMap::new();
+        for p in participants.iter_mut() {
+            confirmations.insert(p.get_id(), p.round3_fast(&r2bdata).unwrap());
+            assert!(!p.completed());
+            assert!(p.provisional_public_key().is_some());
+        }
+        for p in participants.iter_mut() {
+            assert!(p.confirm_fast(&confirmations).is_ok());
+            assert!(p.completed());
+            assert_eq!(p.get_public_key(), Some(confirmations[&1].public_key));
+        }
+        let mut tampered = confirmations.clone();
+        tampered.get_mut(&2).unwrap().transcript_hash[0] ^= 1;
+        assert!(matches!(
+            participants[0].confirm_fast(&tampered).unwrap_err(),
+            Error::SenderError {
+                error: ParticipantError::MismatchedTranscript(2),
+                ..
+            }
+        ));
+        let mut tampered = confirmations;
+        tampered.get_mut(&3).unwrap().public_key += G::generator();
+        assert!(matches!(
+            participants[0].confirm_fast(&tampered).unwrap_err(),
+            Error::SenderError {
+                error: ParticipantError::MismatchedPublicKey(3),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn transport_frame() {
+        type G = k256::ProjectivePoint;
+        let frame = TransportFrame::<G> {
+            sender: 3,
+            payload: SessionPayload::Round4Echo(Round4EchoBroadcastData {
+                public_key: G::generator(),
+            }),
+        };
+        let restored = TransportFrame::<G>::from_bytes(&frame.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.sender, 3);
+        assert!(matches!(
+            restored.payload,
+            SessionPayload::Round4Echo(data) if data.public_key == G::generator()
+        ));
+        assert!(TransportFrame::<G>::from_bytes(&[3, 0, 0, 0, 9]).is_err());
+
+        let bytes = frame.to_bytes().unwrap();
+        let filter = RelayFilter::new(1..=3);
+        assert_eq!(
+            filter.validate_envelope(&bytes).unwrap(),
+            EnvelopeHeader {
+                sender: 3,
+                round: Round::Five,
+                p2p: false,
+            }
+        );
+        let err = RelayFilter::new(1..=2)
+            .validate_envelope(&bytes)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidSender);
+        assert!(filter
+            .clone()
+            .with_max_length(bytes.len() - 1)
+            .validate_envelope(&bytes)
+            .is_err());
+        assert!(filter.validate_envelope(&bytes[..5]).is_err());
+        let mut unknown = bytes.clone();
+        unknown[4] = 5;
+        assert!(filter.validate_envelope(&unknown).is_err());
+    }
+
+    #[test]
+    fn session_manager() {
+        type G = k256::ProjectivePoint;
+        const SESSIONS: [u32; 2] = [7, 9];
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        // One manager per party, each running every session
+        let mut managers = (1..=3)
+            .map(|id| {
+                let mut manager = SessionManager::<u32, SecretParticipantImpl<G>, G>::new();
+                for session in SESSIONS {
+                    let p = SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters)
+                        .unwrap();
+                    assert!(manager.insert(session, p).is_none());
+                }
+                manager
+            })
+            .collect::<Vec<_>>();
+
+        assert!(managers[0].advance(&1).is_err());
+        assert_eq!(
+            managers[0].dkg_status(&SESSIONS[0]).unwrap(),
+            DkgStatus::ReadyToRun(Round::One)
+        );
+        // Data more than one round ahead isn't buffered
+        let err = managers[0]
+            .receive(Envelope {
+                session: SESSIONS[0],
+                sender: 2,
+                payload: SessionPayload::Round4Echo(Round4EchoBroadcastData {
+                    public_key: G::generator(),
+                }),
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRound);
+        assert_eq!(err.severity(), Severity::Retryable);
+
+        for _ in 0..5 {
+            let mut envelopes = Vec::new();
+            for (i, manager) in managers.iter_mut().enumerate() {
+                let sender = i + 1;
+                for session in SESSIONS {
+                    let payloads = match manager.advance(&session).unwrap().unwrap() {
+                        RoundOutput::Round1 { broadcast, p2p } => {
+                            let mut payloads =
+                                vec![(None, SessionPayload::Round1Broadcast(broadcast))];
+                            payloads.extend(
+                                p2p.into_iter()
+                                    .map(|(id, d)| (Some(id), SessionPayload::Round1P2P(d))),
+                            );
+                            payloads
+                        }
+                        RoundOutput::Round2(d) => vec![(None, SessionPayload::Round2Echo(d))],
+                        RoundOutput::Round3(d) => {
+                            vec![(None, SessionPayload::Round3Broadcast(d))]
+                        }
+                        RoundOutput::Round4(d) => vec![(None, SessionPayload::Round4Echo(d))],
+                        RoundOutput::Complete => Vec::new(),
+                    };
+                    for (to, payload) in payloads {
+                        envelopes.push((sender, to, session, payload));
+                    }
+                }
+            }
+            for (sender, to, session, payload) in envelopes {
+                for (i, manager) in managers.iter_mut().enumerate() {
+                    let id = i + 1;
+                    // Round 1 data isn't sent back to the sender,
+                    // later rounds include the sender's own data
+                    let is_round1 = matches!(
+                        payload,
+                        SessionPayload::Round1Broadcast(_) | SessionPayload::Round1P2P(_)
+                    );
+                    if to.is_some_and(|to| to != id) || (is_round1 && id == sender) {
+                        continue;
+                    }
+                    // Delivering the same data twice is a no-op
+                    for _ in 0..2 {
+                        manager
+                            .receive(Envelope {
+                                session,
+                                sender,
+                                payload: payload.clone(),
+                            })
+                            .unwrap();
+                    }
+                    if let SessionPayload::Round3Broadcast(mut data) = payload.clone() {
+                        // Conflicting data invalidates the sender, check it
+                        // on a copy so the ceremony can still complete
+                        let mut probe = manager.clone();
+                        data.commitments[0] += G::generator();
+                        let err = probe
+                            .receive(Envelope {
+                                session,
+                                sender,
+                                payload: SessionPayload::Round3Broadcast(data),
+                            })
+                            .unwrap_err();
+                        assert!(matches!(
+                            err,
+                            Error::SenderError {
+                                error: ParticipantError::ConflictingBroadcast(id),
+                                ..
+                            } if id == sender
+                        ));
+                        let evidence = probe.equivocations(&session);
+                        assert_eq!(evidence.len(), 1);
+                        assert_eq!(evidence[0].sender, sender);
+                        assert_eq!(evidence[0].round(), Round::Three);
+                    }
+                }
+            }
+        }
+
+        for manager in managers.iter_mut() {
+            for session in SESSIONS {
+                assert_eq!(manager.status(&session), Some(SessionStatus::Complete));
+                assert_eq!(manager.dkg_status(&session).unwrap(), DkgStatus::Completed);
+                assert!(manager.advance(&session).unwrap().is_none());
+            }
+            assert_eq!(manager.gc(), SESSIONS.to_vec());
+            assert!(manager.is_empty());
+        }
+    }
+
+    #[test]
+    fn equivocation_evidence() {
+        type G = k256::ProjectivePoint;
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=2)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let (broadcast, mut p2p) = participants[1].round1().unwrap();
+        let mut manager = SessionManager::<u32, SecretParticipantImpl<G>, G>::new();
+        manager.insert(0, participants.remove(0));
+
+        let envelope = |payload| Envelope {
+            session: 0,
+            sender: 2,
+            payload,
+        };
+        for _ in 0..2 {
+            manager
+                .receive(envelope(SessionPayload::Round1Broadcast(broadcast.clone())))
+                .unwrap();
+        }
+        assert!(manager.equivocations(&0).is_empty());
+
+        let mut conflicting = broadcast.clone();
+        conflicting.pedersen_commitments[0] += G::generator();
+        let err = manager
+            .receive(envelope(SessionPayload::Round1Broadcast(conflicting)))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SenderError {
+                error: ParticipantError::ConflictingBroadcast(2),
+                ..
+            }
+        ));
+        // Everything else from the sender is refused
+        let err = manager
+            .receive(envelope(SessionPayload::Round1P2P(p2p.remove(&1).unwrap())))
+            .unwrap_err();
+        assert_eq!(err.sender(), Some(2));
+
+        let evidence = manager.equivocations(&0);
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].sender, 2);
+        assert_eq!(evidence[0].round(), Round::One);
+        let bytes = serde_bare::to_vec(&evidence[0]).unwrap();
+        let mut evidence = serde_bare::from_slice::<EquivocationEvidence<G>>(&bytes).unwrap();
+        assert!(evidence.check_conflict().is_ok());
+        evidence.second = evidence.first.clone();
+        assert!(evidence.check_conflict().is_err());
+    }
+
+    #[test]
+    fn receive_limits() {
+        type G = k256::ProjectivePoint;
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let mut p2p = BTreeMap::new();
+        let round1 = participants[1..]
+            .iter_mut()
+            .map(|p| {
+                let (broadcast, mut data) = p.round1().unwrap();
+                p2p.insert(p.get_id(), data.remove(&1).unwrap());
+                (p.get_id(), broadcast)
+            })
+            .collect::<Vec<_>>();
+        let size = serde_bare::to_vec(&round1[0].1).unwrap().len();
+        // Each of the 3 participants can buffer one broadcast
+        let mut manager =
+            SessionManager::<u32, SecretParticipantImpl<G>, G>::with_limits(ReceiveLimits {
+                messages_per_round: 2,
+                buffered_bytes: 3 * size,
+            });
+        manager.insert(0, participants.remove(0));
+
+        let envelope = |(sender, broadcast): &(usize, Round1BroadcastData<G>)| Envelope {
+            session: 0,
+            sender: *sender,
+            payload: SessionPayload::Round1Broadcast(broadcast.clone()),
+        };
+        manager.receive(envelope(&round1[0])).unwrap();
+        manager.receive(envelope(&round1[0])).unwrap();
+        let err = manager.receive(envelope(&round1[0])).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::SenderError {
+                error: ParticipantError::TooManyMessages(2),
+                ..
+            }
+        ));
+        // The peer data is counted separately but the sender's buffer is full
+        let err = manager
+            .receive(Envelope {
+                session: 0,
+                sender: 2,
+                payload: SessionPayload::Round1P2P(p2p[&2].clone()),
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Round);
+        // Other senders have their own buffer
+        manager.receive(envelope(&round1[1])).unwrap();
+    }
+
+    #[test]
+    fn session_sender_fault() {
+        type G = k256::ProjectivePoint;
+
+        let parameters =
+            Parameters::<G>::new(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap());
+        let mut participants = (1..=3)
+            .map(|id| SecretParticipant::<G>::new(NonZeroUsize::new(id).unwrap(), parameters))
+            .collect::<DkgResult<Vec<_>>>()
+            .unwrap();
+        let round1 = participants
+            .iter_mut()
+            .map(|p| (p.get_id(), p.round1().unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let mut echo2 = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let id = p.get_id();
+            let others = round1.iter().filter(|(i, _)| **i != id);
+            let broadcast = others.clone().map(|(i, (b, _))| (*i, b.clone())).collect();
+            let p2p = others.map(|(i, (_, d))| (*i, d[&id].clone())).collect();
+            echo2.insert(id, p.round2(broadcast, p2p).unwrap());
+        }
+        let broadcast3 = participants
+            .iter_mut()
+            .map(|p| (p.get_id(), p.round3(&echo2).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        let mut echo4 = participants
+            .iter_mut()
+            .map(|p| (p.get_id(), p.round4(&broadcast3).unwrap()))
+            .collect::<BTreeMap<_, _>>();
+        echo4.get_mut(&3).unwrap().public_key = G::generator();
+
+        let mut manager = SessionManager::<u32, SecretParticipantImpl<G>, G>::new();
+        manager.insert(0, participants.remove(0));
+        let envelope = |sender: usize, data: &Round4EchoBroadcastData<G>| Envelope {
+            session: 0,
+            sender,
+            payload: SessionPayload::Round4Echo(data.clone()),
+        };
+        assert!(!manager.progress(&0).unwrap().can_advance);
+        for (sender, data) in &echo4 {
+            manager.receive(envelope(*sender, data)).unwrap();
+        }
+        let progress = manager.progress(&0).unwrap();
+        assert_eq!(progress.round, Round::Five);
+        assert_eq!(progress.received, 3);
+        assert!(progress.can_advance);
+        for sender in [0, 4] {
+            let err = manager.receive(envelope(sender, &echo4[&1])).unwrap_err();
+            assert!(matches!(
+                err,
+                Error::SenderError {
+                    error: ParticipantError::UnexpectedBroadcast(id),
+                    ..
+                } if id == sender
+            ));
+        }
+
+        // The shares of participant 3 are still combined into this
+        // participant's share so the mismatched key aborts the session
+        let err = manager.advance(&0).unwrap_err();
+        assert_eq!(err.sender(), Some(3));
+        assert!(matches!(
+            err,
+            Error::SenderError {
+                error: ParticipantError::MismatchedPublicKey(3),
+                ..
+            }
+        ));
+        assert_eq!(manager.status(&0), Some(SessionStatus::Aborted));
+        let err = manager.receive(envelope(3, &echo4[&3])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Aborted);
+        assert!(manager.advance(&0).unwrap().is_none());
+    }
+
+    #[cfg(any(feature = "wasm", feature = "capi"))]
+    #[test]
+    fn encoded_participants() {
+        use encoded::EncodedParticipant;
+
+        let mut participants = (1..=3)
+            .map(|id| EncodedParticipant::new(id, 2, 3).unwrap())
+            .collect::<Vec<_>>();
+        let round1 = participants
+            .iter_mut()
+            .map(|p| (p.id(), p.round1().unwrap()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut echo2 = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let id = p.id();
+            let others = round1.iter().filter(|(i, _)| **i != id);
+            let broadcast = others
+                .clone()
+                .map(|(i, r)| (*i, r.broadcast.clone()))
+                .collect();
+            let p2p = others.map(|(i, r)| (*i, r.p2p[&id].clone())).collect();
+            echo2.insert(id, p.round2(&broadcast, &p2p).unwrap());
+        }
+        let mut broadcast3 = BTreeMap::new();
+        for p in participants.iter_mut() {
+            broadcast3.insert(p.id(), p.round3(&echo2).unwrap());
+        }
+        let mut echo4 = BTreeMap::new();
+        for p in participants.iter_mut() {
+            echo4.insert(p.id(), p.round4(&broadcast3).unwrap());
+        }
+        for p in participants.iter_mut() {
+            p.round5(&echo4).unwrap();
+        }
+        for p in &participants {
+            let restored = EncodedParticipant::from_bytes(&p.to_bytes().unwrap()).unwrap();
+            assert_eq!(restored.public_key(), participants[0].public_key());
+            assert_eq!(restored.secret_share(), p.secret_share());
+        }
+    }
+
     #[test]
     fn serialization_k256() {
         serialization_curve::<k256::ProjectivePoint>();
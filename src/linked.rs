@@ -0,0 +1,223 @@
+use super::*;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// A participant generating one key on each of two curves with the same
+/// committee, for one logical identity with keys on multiple curves.
+///
+/// Both ceremonies run in lock-step so each round's data is a pair with
+/// one entry per curve. The transcript of each curve binds the generators
+/// and the public key of the other so neither key can be
+/// swapped for a key from another ceremony. If a round succeeds on the
+/// first curve but fails on the second both are aborted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinkedParticipant<A, B>
+where
+    A: Group + GroupEncoding + Default,
+    B: Group + GroupEncoding + Default,
+{
+    #[serde(bound(serialize = "SecretParticipant<A>: Serialize"))]
+    #[serde(bound(deserialize = "SecretParticipant<A>: Deserialize<'de>"))]
+    first: SecretParticipant<A>,
+    #[serde(bound(serialize = "SecretParticipant<B>: Serialize"))]
+    #[serde(bound(deserialize = "SecretParticipant<B>: Deserialize<'de>"))]
+    second: SecretParticipant<B>,
+}
+
+impl<A, B> LinkedParticipant<A, B>
+where
+    A: Group + GroupEncoding + Default,
+    B: Group + GroupEncoding + Default,
+{
+    /// Create a new participant to generate a key with each of the parameters.
+    ///
+    /// Both must have the same threshold and limit.
+    pub fn new(
+        id: NonZeroUsize,
+        first_parameters: Parameters<A>,
+        second_parameters: Parameters<B>,
+    ) -> DkgResult<Self> {
+        if first_parameters.threshold != second_parameters.threshold
+            || first_parameters.limit != second_parameters.limit
+        {
+            return Err(Error::InvalidParameters(
+                "linked ceremonies must have the same threshold and limit".to_string(),
+            ));
+        }
+        let mut first = SecretParticipant::new(id, first_parameters)?;
+        let mut second = SecretParticipant::new(id, second_parameters)?;
+        first.transcript_mut().append_points(
+            Round::One,
+            0,
+            "linked_generators",
+            &[
+                second_parameters.message_generator,
+                second_parameters.blinder_generator,
+            ],
+        );
+        second.transcript_mut().append_points(
+            Round::One,
+            0,
+            "linked_generators",
+            &[
+                first_parameters.message_generator,
+                first_parameters.blinder_generator,
+            ],
+        );
+        Ok(Self { first, second })
+    }
+
+    /// The identifier associated with this participant
+    pub fn get_id(&self) -> usize {
+        self.first.get_id()
+    }
+
+    /// The current round of this participant
+    pub fn get_round(&self) -> Round {
+        self.first.get_round().min(self.second.get_round())
+    }
+
+    /// Returns true if this participant is complete
+    pub fn completed(&self) -> bool {
+        self.first.completed() && self.second.completed()
+    }
+
+    /// The participant on the first curve
+    pub fn first(&self) -> &SecretParticipant<A> {
+        &self.first
+    }
+
+    /// The participant on the second curve
+    pub fn second(&self) -> &SecretParticipant<B> {
+        &self.second
+    }
+
+    /// The computed public keys, or [`None`] until complete
+    pub fn get_public_keys(&self) -> Option<(A, B)> {
+        self.first
+            .get_public_key()
+            .zip(self.second.get_public_key())
+    }
+
+    /// The outputs on both curves, or [`None`] until complete
+    pub fn output(&self) -> Option<(DkgOutput<A>, DkgOutput<B>)> {
+        self.first.output().zip(self.second.output())
+    }
+
+    /// The SHA-256 hash of the transcript hashes of both curves
+    pub fn get_transcript_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.first.get_transcript_hash());
+        hasher.update(self.second.get_transcript_hash());
+        hasher.finalize().into()
+    }
+
+    /// Abort both ceremonies, see [`Participant::abort`]
+    pub fn abort<S: Into<String>>(&mut self, reason: S) {
+        let reason = reason.into();
+        self.first.abort(reason.clone());
+        self.second.abort(reason);
+    }
+
+    /// Computes round 1 on both curves.
+    ///
+    /// See [`Participant::round1`]
+    pub fn round1(
+        &mut self,
+    ) -> DkgResult<(
+        (Round1BroadcastData<A>, Round1BroadcastData<B>),
+        BTreeMap<usize, (Round1P2PData, Round1P2PData)>,
+    )> {
+        let (first_broadcast, mut first_p2p) = self.first.round1()?;
+        let second = self.second.round1();
+        let (second_broadcast, second_p2p) = self.lock_step(second)?;
+        let p2p = second_p2p
+            .into_iter()
+            .filter_map(|(id, b)| first_p2p.remove(&id).map(|a| (id, (a, b))))
+            .collect();
+        Ok(((first_broadcast, second_broadcast), p2p))
+    }
+
+    /// Computes round 2 on both curves.
+    ///
+    /// See [`Participant::round2`]
+    pub fn round2(
+        &mut self,
+        broadcast_data: BTreeMap<usize, (Round1BroadcastData<A>, Round1BroadcastData<B>)>,
+        p2p_data: BTreeMap<usize, (Round1P2PData, Round1P2PData)>,
+    ) -> DkgResult<(Round2EchoBroadcastData, Round2EchoBroadcastData)> {
+        let (first_broadcast, second_broadcast) = split(broadcast_data);
+        let (first_p2p, second_p2p) = split(p2p_data);
+        let first = self.first.round2(first_broadcast, first_p2p)?;
+        let second = self.second.round2(second_broadcast, second_p2p);
+        Ok((first, self.lock_step(second)?))
+    }
+
+    /// Computes round 3 on both curves.
+    ///
+    /// See [`Participant::round3`]
+    pub fn round3(
+        &mut self,
+        echo_data: &BTreeMap<usize, (Round2EchoBroadcastData, Round2EchoBroadcastData)>,
+    ) -> DkgResult<(Round3BroadcastData<A>, Round3BroadcastData<B>)> {
+        let (first_echo, second_echo) = split(echo_data.clone());
+        let first = self.first.round3(&first_echo)?;
+        let second = self.second.round3(&second_echo);
+        Ok((first, self.lock_step(second)?))
+    }
+
+    /// Computes round 4 on both curves and binds each public key
+    /// into the transcript of the other curve.
+    ///
+    /// See [`Participant::round4`]
+    pub fn round4(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, (Round3BroadcastData<A>, Round3BroadcastData<B>)>,
+    ) -> DkgResult<(Round4EchoBroadcastData<A>, Round4EchoBroadcastData<B>)> {
+        let (first_broadcast, second_broadcast) = split(broadcast_data.clone());
+        let first = self.first.round4(&first_broadcast)?;
+        let second = self.second.round4(&second_broadcast);
+        let second = self.lock_step(second)?;
+        self.first.transcript_mut().append_points(
+            Round::Four,
+            0,
+            "linked_public_key",
+            &[second.public_key],
+        );
+        self.second.transcript_mut().append_points(
+            Round::Four,
+            0,
+            "linked_public_key",
+            &[first.public_key],
+        );
+        Ok((first, second))
+    }
+
+    /// Computes round 5 on both curves.
+    ///
+    /// See [`Participant::round5`]
+    pub fn round5(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, (Round4EchoBroadcastData<A>, Round4EchoBroadcastData<B>)>,
+    ) -> DkgResult<()> {
+        let (first_echo, second_echo) = split(broadcast_data.clone());
+        self.first.round5(&first_echo)?;
+        self.second.round5(&second_echo)
+    }
+
+    /// The first curve already advanced so a failure on the second
+    /// leaves the ceremonies out of step, abort both
+    fn lock_step<T>(&mut self, result: DkgResult<T>) -> DkgResult<T> {
+        if let Err(e) = &result {
+            self.abort(format!("the linked ceremony failed: {}", e));
+        }
+        result
+    }
+}
+
+/// Turn pairs keyed by sender into one map per curve
+fn split<X, Y>(data: BTreeMap<usize, (X, Y)>) -> (BTreeMap<usize, X>, BTreeMap<usize, Y>) {
+    data.into_iter()
+        .map(|(id, (x, y))| ((id, x), (id, y)))
+        .unzip()
+}
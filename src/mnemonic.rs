@@ -0,0 +1,184 @@
+//! Encode secret shares as mnemonic phrases for offline or paper backups.
+//!
+//! A phrase is a sequence of words from the English BIP-39 word list, each
+//! word carrying 11 bits. It encodes the share identifier, the threshold,
+//! a 4 byte fingerprint of the public key and the share followed by a
+//! 4 byte SHA-256 checksum so a mistyped word is detected on import.
+//! The phrase length depends on the size of the curve's scalars.
+use crate::*;
+use bip39::Language;
+use sha2::{Digest, Sha256};
+
+const VERSION: u8 = 1;
+const HEADER_LENGTH: usize = 9;
+const CHECKSUM_LENGTH: usize = 4;
+const BITS_PER_WORD: usize = 11;
+
+/// A secret share with the metadata needed to use it again
+#[derive(Clone)]
+pub struct MnemonicShare<G: Group + GroupEncoding + Default> {
+    /// The share identifier
+    pub id: usize,
+    /// The threshold of the DKG
+    pub threshold: usize,
+    /// The first 4 bytes of the SHA-256 hash of the public key
+    pub key_fingerprint: [u8; 4],
+    /// The secret share
+    pub share: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> Zeroize for MnemonicShare<G> {
+    fn zeroize(&mut self) {
+        utils::zeroize_scalar(&mut self.share);
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for MnemonicShare<G> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> ZeroizeOnDrop for MnemonicShare<G> {}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for MnemonicShare<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MnemonicShare")
+            .field("id", &self.id)
+            .field("threshold", &self.threshold)
+            .field("key_fingerprint", &self.key_fingerprint)
+            .field("share", &"<redacted>")
+            .finish()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> MnemonicShare<G> {
+    /// One share per secret share in `output`
+    pub fn from_output(output: &DkgOutput<G>) -> Vec<Self> {
+        let key_fingerprint = key_fingerprint(&output.public_key);
+        output
+            .secret_shares
+            .iter()
+            .map(|(id, share)| Self {
+                id: *id,
+                threshold: output.report.threshold,
+                key_fingerprint,
+                share: *share,
+            })
+            .collect()
+    }
+
+    /// Returns true if the share belongs to `public_key`
+    pub fn matches(&self, public_key: &G) -> bool {
+        self.key_fingerprint == key_fingerprint(public_key)
+    }
+
+    /// Encode the share as a space separated phrase
+    pub fn to_phrase(&self) -> DkgResult<String> {
+        if self.id > u16::MAX as usize || self.threshold > u16::MAX as usize {
+            return Err(Error::InvalidParameters(
+                "the share id and threshold must fit in 16 bits".to_string(),
+            ));
+        }
+        let mut payload =
+            Vec::with_capacity(HEADER_LENGTH + scalar_length::<G>() + CHECKSUM_LENGTH);
+        payload.push(VERSION);
+        payload.extend_from_slice(&(self.id as u16).to_be_bytes());
+        payload.extend_from_slice(&(self.threshold as u16).to_be_bytes());
+        payload.extend_from_slice(&self.key_fingerprint);
+        payload.extend_from_slice(self.share.to_repr().as_ref());
+        let checksum = Sha256::digest(&payload);
+        payload.extend_from_slice(&checksum[..CHECKSUM_LENGTH]);
+
+        let words = Language::English.word_list();
+        let mut payload_bits = bits(&payload);
+        let phrase = payload_bits
+            .chunks(BITS_PER_WORD)
+            .map(|chunk| {
+                let index = (0..BITS_PER_WORD).fold(0usize, |acc, i| {
+                    (acc << 1) | chunk.get(i).copied().unwrap_or(0) as usize
+                });
+                words[index]
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        payload.zeroize();
+        payload_bits.zeroize();
+        Ok(phrase)
+    }
+
+    /// Decode a phrase created by [`MnemonicShare::to_phrase`].
+    ///
+    /// Throws [`Error::SerializationError`] if a word is unknown,
+    /// the phrase has the wrong length or the checksum doesn't match.
+    pub fn from_phrase(phrase: &str) -> DkgResult<Self> {
+        let length = HEADER_LENGTH + scalar_length::<G>() + CHECKSUM_LENGTH;
+        let mut bits = Vec::with_capacity(length * 8 + BITS_PER_WORD);
+        for word in phrase.split_whitespace() {
+            let index = Language::English
+                .find_word(&word.to_lowercase())
+                .ok_or_else(|| {
+                    Error::SerializationError(format!("unknown mnemonic word '{}'", word))
+                })?;
+            bits.extend((0..BITS_PER_WORD).rev().map(|i| ((index >> i) & 1) as u8));
+        }
+        if bits.len() != (length * 8).div_ceil(BITS_PER_WORD) * BITS_PER_WORD {
+            return Err(Error::SerializationError(
+                "the mnemonic phrase has the wrong number of words".to_string(),
+            ));
+        }
+        if bits[length * 8..].iter().any(|b| *b != 0) {
+            return Err(Error::SerializationError(
+                "the mnemonic phrase has invalid padding".to_string(),
+            ));
+        }
+        let mut payload = bits[..length * 8]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, b| (acc << 1) | b))
+            .collect::<Vec<_>>();
+        bits.zeroize();
+
+        let (data, checksum) = payload.split_at(length - CHECKSUM_LENGTH);
+        let result = if Sha256::digest(data)[..CHECKSUM_LENGTH] != *checksum {
+            Err(Error::SerializationError(
+                "the mnemonic phrase checksum does not match".to_string(),
+            ))
+        } else if data[0] != VERSION {
+            Err(Error::SerializationError(format!(
+                "unsupported mnemonic version {}",
+                data[0]
+            )))
+        } else {
+            let mut repr = <G::Scalar as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(&data[HEADER_LENGTH..]);
+            Option::<G::Scalar>::from(G::Scalar::from_repr(repr))
+                .ok_or_else(|| {
+                    Error::SerializationError("the mnemonic share is not in the field".to_string())
+                })
+                .map(|share| Self {
+                    id: u16::from_be_bytes([data[1], data[2]]) as usize,
+                    threshold: u16::from_be_bytes([data[3], data[4]]) as usize,
+                    key_fingerprint: [data[5], data[6], data[7], data[8]],
+                    share,
+                })
+        };
+        payload.zeroize();
+        result
+    }
+}
+
+fn key_fingerprint<G: GroupEncoding>(public_key: &G) -> [u8; 4] {
+    let hash = Sha256::digest(public_key.to_bytes().as_ref());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn scalar_length<G: Group>() -> usize {
+    <G::Scalar as PrimeField>::Repr::default().as_ref().len()
+}
+
+fn bits(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+        .collect()
+}
@@ -0,0 +1,218 @@
+//! Mutually authenticated, encrypted channels for the peer-to-peer data
+//! using the Noise protocol framework.
+//!
+//! Each participant has a long-term [`NoiseKeypair`]. [`NoiseHandshake`]
+//! runs the `XX` pattern when the participants learn each other's static
+//! keys during the handshake, or `IK` when the initiator already knows the
+//! responder's static key. Either way the caller must check the peer's
+//! static key with [`NoiseChannel::remote_public_key`] before trusting it.
+//!
+//! Handshake messages and sealed data are bytes so any transport can carry
+//! them. The prologue should identify the ceremony, for example its session
+//! id, so a handshake can't be replayed in another ceremony.
+use crate::*;
+use snow::{Builder, HandshakeState, TransportState};
+
+const XX_PARAMS: &str = "Noise_XX_25519_ChaChaPoly_SHA256";
+const IK_PARAMS: &str = "Noise_IK_25519_ChaChaPoly_SHA256";
+const MAX_MESSAGE_LENGTH: usize = 65535;
+
+/// The Noise handshake pattern
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NoisePattern {
+    /// Both static keys are sent during the handshake, three messages
+    XX,
+    /// The initiator knows the responder's static key, two messages
+    IK,
+}
+
+impl NoisePattern {
+    fn builder<'a>(&self) -> DkgResult<Builder<'a>> {
+        let params = match self {
+            Self::XX => XX_PARAMS,
+            Self::IK => IK_PARAMS,
+        };
+        Ok(Builder::new(params.parse().map_err(noise_error)?))
+    }
+}
+
+/// A static X25519 key pair identifying a participant
+#[derive(Clone)]
+pub struct NoiseKeypair {
+    /// The public key to share with the other participants
+    pub public: Vec<u8>,
+    private: Vec<u8>,
+}
+
+impl fmt::Debug for NoiseKeypair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoiseKeypair")
+            .field("public", &self.public)
+            .field("private", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for NoiseKeypair {
+    fn drop(&mut self) {
+        self.private.zeroize();
+    }
+}
+
+impl NoiseKeypair {
+    /// Generate a random key pair
+    pub fn generate() -> DkgResult<Self> {
+        let keypair = NoisePattern::XX
+            .builder()?
+            .generate_keypair()
+            .map_err(noise_error)?;
+        Ok(Self {
+            public: keypair.public,
+            private: keypair.private,
+        })
+    }
+}
+
+/// One side of a Noise handshake.
+///
+/// The sides call [`NoiseHandshake::write_message`] and
+/// [`NoiseHandshake::read_message`] in turn, starting with the initiator,
+/// until [`NoiseHandshake::is_finished`] is true.
+pub struct NoiseHandshake {
+    state: HandshakeState,
+}
+
+impl fmt::Debug for NoiseHandshake {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoiseHandshake")
+            .field("initiator", &self.state.is_initiator())
+            .field("finished", &self.state.is_handshake_finished())
+            .finish()
+    }
+}
+
+impl NoiseHandshake {
+    /// Start the handshake as the initiator.
+    ///
+    /// `remote_public` is the responder's static key, required for [`NoisePattern::IK`].
+    pub fn initiator(
+        pattern: NoisePattern,
+        keypair: &NoiseKeypair,
+        remote_public: Option<&[u8]>,
+        prologue: &[u8],
+    ) -> DkgResult<Self> {
+        let builder = pattern
+            .builder()?
+            .local_private_key(&keypair.private)
+            .prologue(prologue);
+        let builder = match (pattern, remote_public) {
+            (NoisePattern::IK, Some(remote_public)) => builder.remote_public_key(remote_public),
+            (NoisePattern::IK, None) => {
+                return Err(Error::InvalidParameters(
+                    "the IK pattern requires the responder's static key".to_string(),
+                ))
+            }
+            (NoisePattern::XX, _) => builder,
+        };
+        Ok(Self {
+            state: builder.build_initiator().map_err(noise_error)?,
+        })
+    }
+
+    /// Start the handshake as the responder
+    pub fn responder(
+        pattern: NoisePattern,
+        keypair: &NoiseKeypair,
+        prologue: &[u8],
+    ) -> DkgResult<Self> {
+        let state = pattern
+            .builder()?
+            .local_private_key(&keypair.private)
+            .prologue(prologue)
+            .build_responder()
+            .map_err(noise_error)?;
+        Ok(Self { state })
+    }
+
+    /// The next handshake message to send to the peer
+    pub fn write_message(&mut self) -> DkgResult<Vec<u8>> {
+        let mut message = vec![0u8; MAX_MESSAGE_LENGTH];
+        let length = self
+            .state
+            .write_message(&[], &mut message)
+            .map_err(noise_error)?;
+        message.truncate(length);
+        Ok(message)
+    }
+
+    /// Process a handshake message received from the peer
+    pub fn read_message(&mut self, message: &[u8]) -> DkgResult<()> {
+        let mut payload = vec![0u8; MAX_MESSAGE_LENGTH];
+        self.state
+            .read_message(message, &mut payload)
+            .map_err(noise_error)?;
+        Ok(())
+    }
+
+    /// Returns true once all handshake messages were exchanged
+    pub fn is_finished(&self) -> bool {
+        self.state.is_handshake_finished()
+    }
+
+    /// The encrypted channel once the handshake is finished
+    pub fn into_channel(self) -> DkgResult<NoiseChannel> {
+        Ok(NoiseChannel {
+            state: self.state.into_transport_mode().map_err(noise_error)?,
+        })
+    }
+}
+
+/// An encrypted channel with one peer created by a [`NoiseHandshake`]
+pub struct NoiseChannel {
+    state: TransportState,
+}
+
+impl fmt::Debug for NoiseChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoiseChannel")
+            .field("remote_public_key", &self.remote_public_key())
+            .finish()
+    }
+}
+
+impl NoiseChannel {
+    /// The peer's static key, check it belongs to the expected participant
+    pub fn remote_public_key(&self) -> Option<&[u8]> {
+        self.state.get_remote_static()
+    }
+
+    /// Encrypt the peer-to-peer data for the peer
+    pub fn seal(&mut self, data: &Round1P2PData) -> DkgResult<Vec<u8>> {
+        let mut plaintext =
+            serde_bare::to_vec(data).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let mut message = vec![0u8; MAX_MESSAGE_LENGTH];
+        let result = self.state.write_message(&plaintext, &mut message);
+        plaintext.zeroize();
+        message.truncate(result.map_err(noise_error)?);
+        Ok(message)
+    }
+
+    /// Decrypt peer-to-peer data sealed by the peer
+    pub fn open(&mut self, message: &[u8]) -> DkgResult<Round1P2PData> {
+        let mut plaintext = vec![0u8; MAX_MESSAGE_LENGTH];
+        let result = self
+            .state
+            .read_message(message, &mut plaintext)
+            .map_err(noise_error)
+            .and_then(|length| {
+                serde_bare::from_slice(&plaintext[..length])
+                    .map_err(|e| Error::SerializationError(e.to_string()))
+            });
+        plaintext.zeroize();
+        result
+    }
+}
+
+fn noise_error(e: snow::Error) -> Error {
+    Error::IoError(std::io::Error::new(std::io::ErrorKind::Other, e))
+}
@@ -0,0 +1,28 @@
+use super::*;
+use std::sync::Arc;
+
+/// Receives notifications as a participant moves through the protocol.
+///
+/// All methods have empty default implementations so only the events
+/// of interest need to be implemented. `participant` is always the id
+/// of the participant sending the notification.
+pub trait RoundObserver: Send + Sync {
+    /// Called when the participant starts computing `round`
+    fn on_round_started(&self, _participant: usize, _round: Round) {}
+    /// Called when data from `sender` passed all the checks for `round`
+    fn on_message_verified(&self, _participant: usize, _round: Round, _sender: usize) {}
+    /// Called when `invalid` is removed from the valid set during `round`
+    fn on_participant_invalidated(&self, _participant: usize, _round: Round, _invalid: usize) {}
+    /// Called when round 5 completes and all participants agree on `public_key`
+    fn on_complete(&self, _participant: usize, _public_key: &[u8]) {}
+}
+
+/// Wrapper so participants can remain `Clone` and `Debug`
+#[derive(Clone)]
+pub(crate) struct ObserverHandle(pub(crate) Arc<dyn RoundObserver>);
+
+impl fmt::Debug for ObserverHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RoundObserver")
+    }
+}
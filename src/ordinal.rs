@@ -0,0 +1,65 @@
+//! Participant ids, indices and counts are `usize` in memory but `u32`
+//! on the wire so the encoding is the same on 32 and 64-bit targets.
+//! Values that don't fit are rejected when serializing.
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+pub fn serialize<S: Serializer>(input: &usize, s: S) -> Result<S::Ok, S::Error> {
+    to_wire::<S::Error>(*input)?.serialize(s)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<usize, D::Error> {
+    from_wire::<D::Error>(u32::deserialize(d)?)
+}
+
+fn to_wire<E: ser::Error>(value: usize) -> Result<u32, E> {
+    u32::try_from(value).map_err(|_e| E::custom(format!("{} does not fit in 32 bits", value)))
+}
+
+fn from_wire<E: de::Error>(value: u32) -> Result<usize, E> {
+    usize::try_from(value).map_err(|_e| E::custom(format!("{} does not fit in usize", value)))
+}
+
+pub mod set {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    pub fn serialize<S: Serializer>(input: &BTreeSet<usize>, s: S) -> Result<S::Ok, S::Error> {
+        input
+            .iter()
+            .map(|v| to_wire::<S::Error>(*v))
+            .collect::<Result<BTreeSet<_>, _>>()?
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<BTreeSet<usize>, D::Error> {
+        BTreeSet::<u32>::deserialize(d)?
+            .into_iter()
+            .map(from_wire::<D::Error>)
+            .collect()
+    }
+}
+
+pub mod map {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        input: &BTreeMap<usize, T>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        input
+            .iter()
+            .map(|(k, v)| Ok((to_wire::<S::Error>(*k)?, v)))
+            .collect::<Result<BTreeMap<_, _>, _>>()?
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<BTreeMap<usize, T>, D::Error> {
+        BTreeMap::<u32, T>::deserialize(d)?
+            .into_iter()
+            .map(|(k, v)| Ok((from_wire::<D::Error>(k)?, v)))
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// The output of a completed DKG for a party holding one or more shares
+///
+/// It is serialized with its [`STATE_VERSION`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(
+    into = "VersionedOutput<G>",
+    try_from = "VersionedOutput<G>",
+    bound = ""
+)]
+pub struct DkgOutput<G: Group + GroupEncoding + Default> {
+    /// The public key
+    pub public_key: G,
+    /// The secret shares keyed by share identifier
+    pub secret_shares: BTreeMap<usize, G::Scalar>,
+    pub(crate) report: DkgReport,
+}
+
+impl<G: Group + GroupEncoding + Default> DkgOutput<G> {
+    /// A summary of the ceremony that can be archived, it contains no secrets
+    pub fn report(&self) -> DkgReport {
+        self.report.clone()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct VersionedOutput<G: Group + GroupEncoding + Default> {
+    version: u16,
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    public_key: G,
+    #[serde(with = "ordinal::map")]
+    secret_shares: BTreeMap<usize, OutputShare<G::Scalar>>,
+    report: DkgReport,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct OutputShare<F: PrimeField>(
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    F,
+);
+
+impl<F: PrimeField> Drop for OutputShare<F> {
+    fn drop(&mut self) {
+        utils::zeroize_scalar(&mut self.0);
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> From<DkgOutput<G>> for VersionedOutput<G> {
+    fn from(mut output: DkgOutput<G>) -> Self {
+        Self {
+            version: STATE_VERSION,
+            public_key: output.public_key,
+            secret_shares: std::mem::take(&mut output.secret_shares)
+                .into_iter()
+                .map(|(id, share)| (id, OutputShare(share)))
+                .collect(),
+            report: output.report.clone(),
+        }
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> TryFrom<VersionedOutput<G>> for DkgOutput<G> {
+    type Error = Error;
+
+    fn try_from(output: VersionedOutput<G>) -> DkgResult<Self> {
+        version::check(output.version)?;
+        Ok(Self {
+            public_key: output.public_key,
+            secret_shares: output
+                .secret_shares
+                .into_iter()
+                .map(|(id, share)| (id, share.0))
+                .collect(),
+            report: output.report,
+        })
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Zeroize for DkgOutput<G> {
+    fn zeroize(&mut self) {
+        self.secret_shares
+            .values_mut()
+            .for_each(utils::zeroize_scalar);
+        self.secret_shares.clear();
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for DkgOutput<G> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> ZeroizeOnDrop for DkgOutput<G> {}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for DkgOutput<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DkgOutput")
+            .field("public_key", &self.public_key)
+            .field("share_ids", &self.secret_shares.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
 
 /// The parameters used by the DKG participants.
 /// This must be the same for all of them otherwise the protocol
@@ -11,6 +13,28 @@ pub struct Parameters<G: Group + GroupEncoding + Default> {
     pub(crate) message_generator: G,
     #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
     pub(crate) blinder_generator: G,
+    #[serde(default)]
+    pub(crate) quorum_policy: QuorumPolicy,
+    #[serde(default = "default_secret_count")]
+    pub(crate) secret_count: usize,
+    #[serde(default)]
+    pub(crate) reconciliation_policy: ReconciliationPolicy,
+    #[serde(default)]
+    pub(crate) grace_period: usize,
+    #[serde(default)]
+    pub(crate) round_deadlines: [Option<Duration>; 6],
+    #[serde(default)]
+    pub(crate) full_participation: bool,
+    #[serde(default)]
+    pub(crate) threshold_policy: ThresholdPolicy,
+    #[serde(default)]
+    pub(crate) namespace: Option<[u8; 32]>,
+    #[serde(default)]
+    pub(crate) blinder_opening: bool,
+}
+
+fn default_secret_count() -> usize {
+    1
 }
 
 impl<G: Group + GroupEncoding + Default> Default for Parameters<G> {
@@ -20,6 +44,15 @@ impl<G: Group + GroupEncoding + Default> Default for Parameters<G> {
             limit: 0,
             message_generator: G::identity(),
             blinder_generator: G::identity(),
+            quorum_policy: QuorumPolicy::Threshold,
+            secret_count: 1,
+            reconciliation_policy: ReconciliationPolicy::Strict,
+            grace_period: 0,
+            round_deadlines: [None; 6],
+            full_participation: false,
+            threshold_policy: ThresholdPolicy::Any,
+            namespace: None,
+            blinder_opening: false,
         }
     }
 }
@@ -28,18 +61,28 @@ impl<G: Group + GroupEncoding + Default> Parameters<G> {
     /// Create regular parameters with the message_generator as the default generator
     /// and a random blinder_generator
     pub fn new(threshold: NonZeroUsize, limit: NonZeroUsize) -> Self {
-        let message_generator = G::generator();
-        let mut seed = [0u8; 32];
-        seed.copy_from_slice(&message_generator.to_bytes().as_ref()[0..32]);
-        let rng = rand_chacha::ChaChaRng::from_seed(seed);
         Self {
             threshold: threshold.get(),
             limit: limit.get(),
             message_generator: G::generator(),
-            blinder_generator: G::random(rng),
+            blinder_generator: Self::default_blinder_generator(),
+            quorum_policy: QuorumPolicy::Threshold,
+            secret_count: 1,
+            reconciliation_policy: ReconciliationPolicy::Strict,
+            grace_period: 0,
+            round_deadlines: [None; 6],
+            full_participation: false,
+            threshold_policy: ThresholdPolicy::Any,
+            namespace: None,
+            blinder_opening: false,
         }
     }
 
+    /// Create a builder for the parameters
+    pub fn builder() -> ParametersBuilder<G> {
+        ParametersBuilder::default()
+    }
+
     /// Use the provided parameters
     pub fn with_generators(
         threshold: NonZeroUsize,
@@ -52,9 +95,28 @@ impl<G: Group + GroupEncoding + Default> Parameters<G> {
             limit: limit.get(),
             message_generator,
             blinder_generator,
+            quorum_policy: QuorumPolicy::Threshold,
+            secret_count: 1,
+            reconciliation_policy: ReconciliationPolicy::Strict,
+            grace_period: 0,
+            round_deadlines: [None; 6],
+            full_participation: false,
+            threshold_policy: ThresholdPolicy::Any,
+            namespace: None,
+            blinder_opening: false,
         }
     }
 
+    /// Get the threshold
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Get the limit
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
     /// Get the message generator
     pub fn message_generator(&self) -> G {
         self.message_generator
@@ -64,4 +126,454 @@ impl<G: Group + GroupEncoding + Default> Parameters<G> {
     pub fn blinder_generator(&self) -> G {
         self.blinder_generator
     }
+
+    /// Use the provided quorum policy instead of the default [`QuorumPolicy::Threshold`]
+    ///
+    /// Participants with a [`QuorumPolicy::Custom`] policy fail to serialize,
+    /// resume them with [`Participant::resume`] passing the custom policy.
+    pub fn with_quorum_policy(mut self, quorum_policy: QuorumPolicy) -> Self {
+        self.quorum_policy = quorum_policy;
+        self
+    }
+
+    /// Get the quorum policy
+    pub fn quorum_policy(&self) -> QuorumPolicy {
+        self.quorum_policy
+    }
+
+    /// Use the provided reconciliation policy instead of the default [`ReconciliationPolicy::Strict`]
+    pub fn with_reconciliation_policy(
+        mut self,
+        reconciliation_policy: ReconciliationPolicy,
+    ) -> Self {
+        self.reconciliation_policy = reconciliation_policy;
+        self
+    }
+
+    /// Get the reconciliation policy
+    pub fn reconciliation_policy(&self) -> ReconciliationPolicy {
+        self.reconciliation_policy
+    }
+
+    /// Accept round 1 data that arrives late from participants dropped as absent
+    /// in round 2 when `rounds` is non-zero. The data is only accepted before
+    /// round 3 is computed, see [`Participant::rehabilitate`]
+    pub fn with_grace_period(mut self, rounds: usize) -> Self {
+        self.grace_period = rounds;
+        self
+    }
+
+    /// Get the grace period for late data, 0 by default which rejects it
+    pub fn grace_period(&self) -> usize {
+        self.grace_period
+    }
+
+    /// Allow `deadline` for `round` to receive its data, measured from when the
+    /// participant reached the round. See [`Participant::deadline_exceeded`].
+    ///
+    /// Deadlines for [`Round::Aborted`] are ignored.
+    pub fn with_round_deadline(mut self, round: Round, deadline: Duration) -> Self {
+        if let Some(d) = self.round_deadlines.get_mut(usize::from(round)) {
+            *d = Some(deadline);
+        }
+        self
+    }
+
+    /// Get the deadline for `round`, [`None`] by default
+    pub fn round_deadline(&self, round: Round) -> Option<Duration> {
+        self.round_deadlines
+            .get(usize::from(round))
+            .copied()
+            .flatten()
+    }
+
+    /// Require data from all `limit` participants in every round.
+    ///
+    /// The quorum policy is ignored, the ceremony aborts if any participant
+    /// is dropped or the round is forced to advance.
+    pub fn with_full_participation(mut self, required: bool) -> Self {
+        self.full_participation = required;
+        self
+    }
+
+    /// Get whether all participants are required, false by default
+    pub fn full_participation(&self) -> bool {
+        self.full_participation
+    }
+
+    /// Reject thresholds below the minimum of `threshold_policy`
+    /// instead of the default [`ThresholdPolicy::Any`].
+    ///
+    /// The policy is recorded in the transcript.
+    pub fn with_threshold_policy(mut self, threshold_policy: ThresholdPolicy) -> Self {
+        self.threshold_policy = threshold_policy;
+        self
+    }
+
+    /// Get the threshold policy
+    pub fn threshold_policy(&self) -> ThresholdPolicy {
+        self.threshold_policy
+    }
+
+    /// Tag the ceremony with a tenant or application namespace.
+    ///
+    /// The SHA-256 hash of `namespace` is recorded in the transcript, so
+    /// ceremonies in different namespaces have different transcripts,
+    /// and is used to derive identifiers with [`Parameters::participant_id`].
+    pub fn with_namespace(mut self, namespace: &[u8]) -> Self {
+        self.namespace = Some(Sha256::digest(namespace).into());
+        self
+    }
+
+    /// Get the hash of the namespace, [`None`] by default
+    pub fn namespace(&self) -> Option<[u8; 32]> {
+        self.namespace
+    }
+
+    /// Derive the scalar identifier of an external identity, within
+    /// the namespace if one is set.
+    ///
+    /// See [`utils::participant_id_from_bytes`] and
+    /// [`utils::namespaced_participant_id_from_bytes`].
+    pub fn participant_id(&self, identity: &[u8]) -> DkgResult<G::Scalar> {
+        match &self.namespace {
+            Some(namespace) => {
+                utils::namespaced_participant_id_from_bytes::<G>(namespace, identity)
+            }
+            None => utils::participant_id_from_bytes::<G>(identity),
+        }
+    }
+
+    /// Have each dealer compute a [`BlinderOpening`] in round 2 so auditors
+    /// can check its feldman commitments against its Pedersen commitments.
+    ///
+    /// Off by default, the opening costs a Lagrange evaluation and a Schnorr
+    /// proof per dealer and is only useful when someone audits it.
+    pub fn with_blinder_opening(mut self, enabled: bool) -> Self {
+        self.blinder_opening = enabled;
+        self
+    }
+
+    /// Get whether dealers open their blinder commitments, false by default
+    pub fn blinder_opening(&self) -> bool {
+        self.blinder_opening
+    }
+
+    /// Generate `secret_count` independent secrets in one run with a [`BatchParticipant`]
+    /// instead of one
+    pub fn with_secret_count(mut self, secret_count: NonZeroUsize) -> Self {
+        self.secret_count = secret_count.get();
+        self
+    }
+
+    /// Get the number of secrets generated in one run
+    pub fn secret_count(&self) -> usize {
+        self.secret_count
+    }
+
+    /// The identifiers of all participants, `1..=limit`
+    pub fn participant_ids(&self) -> impl Iterator<Item = usize> {
+        1..=self.limit
+    }
+
+    /// Check the parameters are usable, returning every problem found
+    pub fn validate(&self) -> DkgResult<()> {
+        let mut violations = Vec::new();
+        if self.threshold == 0 {
+            violations.push(ParameterViolation::ThresholdTooSmall(self.threshold));
+        }
+        let minimum = self.threshold_policy.minimum(self.limit);
+        if self.threshold > 0 && self.threshold < minimum {
+            violations.push(ParameterViolation::ThresholdBelowPolicy {
+                threshold: self.threshold,
+                minimum,
+            });
+        }
+        if self.threshold > self.limit {
+            violations.push(ParameterViolation::ThresholdExceedsLimit {
+                threshold: self.threshold,
+                limit: self.limit,
+            });
+        }
+        if self.limit > u8::MAX as usize {
+            violations.push(ParameterViolation::LimitTooLarge(self.limit));
+        }
+        if bool::from(self.message_generator.is_identity()) {
+            violations.push(ParameterViolation::IdentityMessageGenerator);
+        }
+        if bool::from(self.blinder_generator.is_identity()) {
+            violations.push(ParameterViolation::IdentityBlinderGenerator);
+        }
+        if self.message_generator == self.blinder_generator {
+            violations.push(ParameterViolation::EqualGenerators);
+        }
+        if self.secret_count == 0 {
+            violations.push(ParameterViolation::NoSecrets);
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ParameterViolations(violations))
+        }
+    }
+
+    /// The blinder generator is derived deterministically from the default generator
+    /// so all participants using the defaults agree on it
+    fn default_blinder_generator() -> G {
+        let message_generator = G::generator();
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&message_generator.to_bytes().as_ref()[0..32]);
+        let rng = rand_chacha::ChaChaRng::from_seed(seed);
+        G::random(rng)
+    }
+}
+
+/// A builder for [`Parameters`].
+///
+/// `threshold` and `limit` are required, the generators default
+/// to the same values used by [`Parameters::new`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ParametersBuilder<G: Group + GroupEncoding + Default> {
+    threshold: Option<usize>,
+    limit: Option<usize>,
+    message_generator: Option<G>,
+    blinder_generator: Option<G>,
+    quorum_policy: QuorumPolicy,
+    secret_count: Option<usize>,
+    reconciliation_policy: ReconciliationPolicy,
+    grace_period: usize,
+    round_deadlines: [Option<Duration>; 6],
+    full_participation: bool,
+    threshold_policy: ThresholdPolicy,
+    namespace: Option<[u8; 32]>,
+    blinder_opening: bool,
+}
+
+impl<G: Group + GroupEncoding + Default> ParametersBuilder<G> {
+    /// Set the minimum number of participants required to reconstruct the secret
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Set the total number of participants
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the message generator
+    pub fn message_generator(mut self, message_generator: G) -> Self {
+        self.message_generator = Some(message_generator);
+        self
+    }
+
+    /// Set the blinder generator
+    pub fn blinder_generator(mut self, blinder_generator: G) -> Self {
+        self.blinder_generator = Some(blinder_generator);
+        self
+    }
+
+    /// Set the quorum policy
+    pub fn quorum_policy(mut self, quorum_policy: QuorumPolicy) -> Self {
+        self.quorum_policy = quorum_policy;
+        self
+    }
+
+    /// Set the reconciliation policy
+    pub fn reconciliation_policy(mut self, reconciliation_policy: ReconciliationPolicy) -> Self {
+        self.reconciliation_policy = reconciliation_policy;
+        self
+    }
+
+    /// Set the grace period for late round 1 data, see [`Parameters::with_grace_period`]
+    pub fn grace_period(mut self, rounds: usize) -> Self {
+        self.grace_period = rounds;
+        self
+    }
+
+    /// Set the deadline for `round`, see [`Parameters::with_round_deadline`]
+    pub fn round_deadline(mut self, round: Round, deadline: Duration) -> Self {
+        if let Some(d) = self.round_deadlines.get_mut(usize::from(round)) {
+            *d = Some(deadline);
+        }
+        self
+    }
+
+    /// Set whether all participants are required, see [`Parameters::with_full_participation`]
+    pub fn full_participation(mut self, required: bool) -> Self {
+        self.full_participation = required;
+        self
+    }
+
+    /// Set the threshold policy, see [`Parameters::with_threshold_policy`]
+    pub fn threshold_policy(mut self, threshold_policy: ThresholdPolicy) -> Self {
+        self.threshold_policy = threshold_policy;
+        self
+    }
+
+    /// Set the namespace, see [`Parameters::with_namespace`]
+    pub fn namespace(mut self, namespace: &[u8]) -> Self {
+        self.namespace = Some(Sha256::digest(namespace).into());
+        self
+    }
+
+    /// Set whether dealers open their blinder commitments, see [`Parameters::with_blinder_opening`]
+    pub fn blinder_opening(mut self, enabled: bool) -> Self {
+        self.blinder_opening = enabled;
+        self
+    }
+
+    /// Set the number of secrets generated in one run, defaults to 1
+    pub fn secret_count(mut self, secret_count: usize) -> Self {
+        self.secret_count = Some(secret_count);
+        self
+    }
+
+    /// Create the parameters, checking that they are usable
+    pub fn build(self) -> DkgResult<Parameters<G>> {
+        let threshold = self
+            .threshold
+            .ok_or_else(|| Error::InvalidParameters("threshold is required".to_string()))?;
+        let limit = self
+            .limit
+            .ok_or_else(|| Error::InvalidParameters("limit is required".to_string()))?;
+        let parameters = Parameters {
+            threshold,
+            limit,
+            message_generator: self.message_generator.unwrap_or_else(G::generator),
+            blinder_generator: self
+                .blinder_generator
+                .unwrap_or_else(Parameters::<G>::default_blinder_generator),
+            quorum_policy: self.quorum_policy,
+            secret_count: self.secret_count.unwrap_or(1),
+            reconciliation_policy: self.reconciliation_policy,
+            grace_period: self.grace_period,
+            round_deadlines: self.round_deadlines,
+            full_participation: self.full_participation,
+            threshold_policy: self.threshold_policy,
+            namespace: self.namespace,
+            blinder_opening: self.blinder_opening,
+        };
+        parameters.validate()?;
+        Ok(parameters)
+    }
+}
+
+/// Decides when a participant has received enough data to compute a round.
+///
+/// Regardless of the policy, a round never proceeds with fewer
+/// than `threshold` participants.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum QuorumPolicy {
+    /// Wait for data from every participant that is expected to send it.
+    ///
+    /// In round 2 this is all `limit` participants, in later rounds
+    /// it is every participant in the valid set.
+    WaitForAll,
+    /// Proceed once `threshold` participants have sent data
+    #[default]
+    Threshold,
+    /// Decide using a custom function called with
+    /// `(received, threshold, expected)`.
+    ///
+    /// Custom policies cannot be serialized, serializing them or a
+    /// participant using them throws an error instead of dropping the policy.
+    Custom(fn(usize, usize, usize) -> bool),
+}
+
+impl QuorumPolicy {
+    /// Returns true if `received` out of `expected` participants
+    /// satisfy this policy.
+    pub fn is_satisfied(&self, received: usize, threshold: usize, expected: usize) -> bool {
+        if received < threshold {
+            return false;
+        }
+        match self {
+            Self::WaitForAll => received >= expected,
+            Self::Threshold => true,
+            Self::Custom(f) => f(received, threshold, expected),
+        }
+    }
+}
+
+impl Serialize for QuorumPolicy {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::WaitForAll => s.serialize_unit_variant("QuorumPolicy", 0, "WaitForAll"),
+            Self::Threshold => s.serialize_unit_variant("QuorumPolicy", 1, "Threshold"),
+            Self::Custom(_) => Err(serde::ser::Error::custom(
+                "custom quorum policies cannot be serialized",
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for QuorumPolicy {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        enum QuorumPolicyRepr {
+            WaitForAll,
+            Threshold,
+        }
+
+        match QuorumPolicyRepr::deserialize(d)? {
+            QuorumPolicyRepr::WaitForAll => Ok(Self::WaitForAll),
+            QuorumPolicyRepr::Threshold => Ok(Self::Threshold),
+        }
+    }
+}
+
+/// Decides what happens in round 3 when participants report different valid sets.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReconciliationPolicy {
+    /// Abort if a valid participant reports a set missing any
+    /// participant this participant considers valid
+    #[default]
+    Strict,
+    /// Keep only the participants every valid participant reported as valid
+    /// and continue if at least `threshold` remain.
+    ///
+    /// Useful when participants saw different subsets due to message loss.
+    /// All honest participants must receive the same round 2 data to
+    /// reach the same set.
+    Intersection,
+}
+
+/// The smallest threshold a deployment accepts.
+///
+/// Low thresholds are valid for the protocol but make sharings that
+/// few colluding participants can reconstruct.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ThresholdPolicy {
+    /// Any threshold, this crate enforces no minimum
+    #[default]
+    Any,
+    /// The threshold must be at least the value
+    AtLeast(usize),
+    /// The threshold must be a majority of the participants, at least `limit / 2 + 1`
+    Majority,
+}
+
+impl ThresholdPolicy {
+    /// The smallest threshold this policy accepts for `limit` participants
+    pub fn minimum(&self, limit: usize) -> usize {
+        match self {
+            Self::Any => 1,
+            Self::AtLeast(minimum) => (*minimum).max(1),
+            Self::Majority => limit / 2 + 1,
+        }
+    }
+
+    /// The encoding absorbed into the transcript
+    pub(crate) fn to_bytes(self) -> [u8; 9] {
+        let (tag, value) = match self {
+            Self::Any => (0u8, 0u64),
+            Self::AtLeast(minimum) => (1, minimum as u64),
+            Self::Majority => (2, 0),
+        };
+        let mut bytes = [tag; 9];
+        bytes[1..].copy_from_slice(&value.to_be_bytes());
+        bytes
+    }
 }
@@ -1,3 +1,9 @@
+mod catch_up;
+mod import;
+mod merged;
+mod recovery;
+mod rehabilitate;
+mod resume;
 mod round1;
 mod round2;
 mod round3;
@@ -8,14 +14,18 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::*;
 use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use soteria_rs::Protected;
 use vsss_rs::pedersen;
+
+use import::KeyImport;
+pub use import::{ImportParticipant, ImportParticipantImpl};
 use vsss_rs::{
-    elliptic_curve::{ff::Field, group::GroupEncoding, Group},
+    elliptic_curve::{ff::Field, group::GroupEncoding, subtle::ConstantTimeEq, Group},
     FeldmanVerifierSet, PedersenVerifierSet, Share,
 };
 
@@ -27,6 +37,10 @@ pub type RefreshParticipant<G> = Participant<RefreshParticipantImpl<G>, G>;
 
 /// Participant implementation
 pub trait ParticipantImpl<G: Group + GroupEncoding + Default> {
+    /// The share returned by [`Participant::get_secret_share`]
+    type SecretShare;
+    /// Wrap the computed secret share
+    fn secret_share(share: G::Scalar) -> Self::SecretShare;
     /// Get the participants secret
     fn secret(rng: impl RngCore + CryptoRng) -> G::Scalar;
     /// Check the feldman verifier at position 0.
@@ -37,10 +51,18 @@ pub trait ParticipantImpl<G: Group + GroupEncoding + Default> {
     /// During a new or update key gen, this value is not the identity
     /// during a refresh, it must be identity
     fn check_public_key(key: G, computed: G) -> bool;
+    /// Returns true if the participant sends a [`ZeroSecretProof`] in round 1
+    fn proves_zero_secret() -> bool {
+        false
+    }
 }
 
 /// A DKG participant FSM
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// The [`Debug`](std::fmt::Debug) output omits the secret and blind shares,
+/// enable the `unsafe_debug` feature to print them during development.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "unsafe_debug", derive(Debug))]
 pub struct Participant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
     id: usize,
     #[serde(bound(serialize = "GennaroDkgPedersenResult<G>: Serialize"))]
@@ -59,11 +81,77 @@ pub struct Participant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default
     blind_key: G,
     #[serde(bound(serialize = "Round1BroadcastData<G>: Serialize"))]
     #[serde(bound(deserialize = "Round1BroadcastData<G>: Deserialize<'de>"))]
-    round1_broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+    round1_broadcast_data: Slots<Round1BroadcastData<G>>,
     #[serde(with = "protected")]
-    round1_p2p_data: BTreeMap<usize, Arc<Mutex<Protected>>>,
+    round1_p2p_data: Slots<Arc<Mutex<Protected>>>,
     valid_participant_ids: BTreeSet<usize>,
     participant_impl: I,
+    // Fields added after version 0 go below so the original layout is a prefix
+    #[serde(default, deserialize_with = "version::deserialize")]
+    version: u16,
+    #[serde(default = "dealing::empty", with = "dealing")]
+    dealt_shares: Arc<Mutex<Protected>>,
+    #[serde(default)]
+    quorum_policy: QuorumPolicy,
+    #[serde(default)]
+    reconciliation_policy: ReconciliationPolicy,
+    #[serde(default)]
+    grace_period: usize,
+    #[serde(default)]
+    round_deadlines: [Option<Duration>; 6],
+    #[serde(default)]
+    full_participation: bool,
+    #[serde(default)]
+    threshold_policy: ThresholdPolicy,
+    #[serde(default)]
+    namespace: Option<[u8; 32]>,
+    #[serde(default)]
+    round_started_at: Option<Duration>,
+    #[serde(default)]
+    absent_participant_ids: BTreeSet<usize>,
+    #[serde(default)]
+    disqualified_dealer_ids: BTreeSet<usize>,
+    #[serde(default)]
+    rehabilitated_ids: BTreeSet<usize>,
+    #[serde(default)]
+    force_advance: bool,
+    #[serde(default)]
+    abort_reason: Option<String>,
+    #[serde(default)]
+    invalidated: BTreeMap<usize, (Round, ParticipantError)>,
+    #[serde(default)]
+    registry: ParticipantRegistry,
+    #[serde(default)]
+    #[serde(bound(serialize = "KeyImport<G>: Serialize"))]
+    #[serde(bound(deserialize = "KeyImport<G>: Deserialize<'de>"))]
+    key_import: Option<KeyImport<G>>,
+    #[serde(default)]
+    started_at: Option<u64>,
+    #[serde(default)]
+    finished_at: Option<u64>,
+    #[serde(default)]
+    transcript: Transcript,
+    #[serde(skip)]
+    observer: Option<ObserverHandle>,
+    #[serde(default)]
+    audit_log: AuditLog,
+    #[serde(skip)]
+    audit_sink: Option<AuditSinkHandle>,
+    #[serde(skip)]
+    clock: Option<ClockHandle>,
+    #[cfg(feature = "testing")]
+    #[serde(skip)]
+    faults: Vec<testing::Fault>,
+    #[serde(default)]
+    open_blinder: bool,
+    #[serde(default)]
+    #[serde(bound(serialize = "BlinderOpening<G>: Serialize"))]
+    #[serde(bound(deserialize = "BlinderOpening<G>: Deserialize<'de>"))]
+    blinder_opening: Option<BlinderOpening<G>>,
+    #[serde(default)]
+    secret_dealer_ids: BTreeSet<usize>,
+    #[serde(default)]
+    confirmed: bool,
 }
 
 impl<I, G> Participant<I, G>
@@ -73,15 +161,55 @@ where
 {
     /// Create a new participant to generate a new key share
     pub fn new(id: NonZeroUsize, parameters: Parameters<G>) -> DkgResult<Self> {
-        let rng = rand_core::OsRng;
-        let secret = I::secret(rng);
-        let blinder = G::Scalar::random(rng);
-        Self::initialize(id, parameters, secret, blinder)
+        Self::with_rng(id, parameters, rand_core::OsRng)
+    }
+
+    /// Create a new participant whose secret, blinder and polynomials
+    /// are all drawn from `rng`
+    pub(crate) fn with_rng(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> DkgResult<Self> {
+        let secret = I::secret(&mut rng);
+        let blinder = G::Scalar::random(&mut rng);
+        Self::initialize(id, parameters, secret, blinder, rng)
+    }
+
+    /// Create a new participant whose secret and blinder come from `provider`
+    pub fn with_provider<P: SecretProvider<G>>(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        provider: &mut P,
+    ) -> DkgResult<Self> {
+        let secret = provider.dealing_secret(id.get())?;
+        let blinder = provider.dealing_blinder(id.get())?;
+        Self::initialize(id, parameters, secret, blinder, rand_core::OsRng)
+    }
+
+    /// Hand the computed shares to `provider` once this participant is complete.
+    ///
+    /// Throws an error if this participant has not completed round 5.
+    pub fn store_shares<P: SecretProvider<G>>(&self, provider: &mut P) -> DkgResult<()> {
+        if !self.completed() {
+            return Err(self.invalid_round(Round::Five));
+        }
+        let secret_share = self.computed_secret_share().ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid secret share".to_string())
+        })?;
+        let blind_share = self.get_blind_share().ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid blind share".to_string())
+        })?;
+        provider.store_shares(self.id, secret_share, blind_share, self.public_key)
     }
 
     /// Create a new participant with an existing secret.
     ///
     /// This allows the polynomial to be updated versus refreshing the shares.
+    ///
+    /// `shares_ids` are the identifiers of all the existing shares taking part,
+    /// there must be at least as many as the previous threshold.
+    /// `index` is the position of this participant's identifier in `shares_ids`.
     pub fn with_secret(
         id: NonZeroUsize,
         parameters: Parameters<G>,
@@ -92,7 +220,7 @@ where
         let mut rng = rand_core::OsRng;
         let blinder = G::Scalar::random(&mut rng);
         let secret = Self::lagrange_interpolation(share, shares_ids, index)?;
-        Self::initialize(id, parameters, secret, blinder)
+        Self::initialize(id, parameters, secret, blinder, rng)
     }
 
     fn initialize(
@@ -100,8 +228,9 @@ where
         parameters: Parameters<G>,
         secret: G::Scalar,
         blinder: G::Scalar,
+        mut rng: impl RngCore + CryptoRng,
     ) -> DkgResult<Self> {
-        let rng = rand_core::OsRng;
+        parameters.validate()?;
 
         let components = pedersen::split_secret::<G, u8, InnerShare>(
             parameters.threshold,
@@ -110,9 +239,10 @@ where
             Some(blinder),
             Some(parameters.message_generator),
             Some(parameters.blinder_generator),
-            rng,
+            &mut rng,
         )?;
-        let components = GennaroDkgPedersenResult::from(components);
+        let mut components = GennaroDkgPedersenResult::from(components);
+        let transcript = Self::setup_transcript(&parameters);
 
         if (components
             .pedersen_verifier_set
@@ -124,6 +254,7 @@ where
                 .is_identity())
         .into()
         {
+            components.zeroize();
             return Err(Error::InitializationError("Invalid generators".to_string()));
         }
         let pedersen_commitments = components.pedersen_verifier_set.blind_verifiers();
@@ -135,6 +266,7 @@ where
                 .any(|c| c.is_identity().into())
             || !I::check_feldman_verifier(feldman_commitments[0])
         {
+            components.zeroize();
             return Err(Error::InitializationError(
                 "Invalid commitments".to_string(),
             ));
@@ -142,33 +274,142 @@ where
         if components.secret_shares.iter().any(|s| s.is_zero().into())
             || components.blinder_shares.iter().any(|s| s.is_zero().into())
         {
+            components.zeroize();
             return Err(Error::InitializationError("Invalid shares".to_string()));
         }
+        // Keep the dealt shares in protected memory until they are sent and combined
+        let dealt_shares = DealtShares {
+            secret_shares: std::mem::take(&mut components.secret_shares),
+            blinder_shares: std::mem::take(&mut components.blinder_shares),
+        }
+        .protect()?;
         Ok(Self {
+            version: STATE_VERSION,
             id: id.get(),
             components,
+            dealt_shares,
             threshold: parameters.threshold,
             limit: parameters.limit,
+            quorum_policy: parameters.quorum_policy,
+            reconciliation_policy: parameters.reconciliation_policy,
+            grace_period: parameters.grace_period,
+            round_deadlines: parameters.round_deadlines,
+            full_participation: parameters.full_participation,
+            threshold_policy: parameters.threshold_policy,
+            namespace: parameters.namespace,
             round: Round::One,
-            round1_broadcast_data: BTreeMap::new(),
-            round1_p2p_data: BTreeMap::new(),
+            round_started_at: Some(SystemClock.now()),
+            round1_broadcast_data: Slots::default(),
+            round1_p2p_data: Slots::default(),
             secret_share: Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO))),
             blind_share: Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO))),
             public_key: G::identity(),
             blind_key: G::identity(),
             valid_participant_ids: BTreeSet::new(),
+            absent_participant_ids: BTreeSet::new(),
+            disqualified_dealer_ids: BTreeSet::new(),
+            rehabilitated_ids: BTreeSet::new(),
+            force_advance: false,
+            abort_reason: None,
+            invalidated: BTreeMap::new(),
+            registry: ParticipantRegistry::default(),
+            key_import: None,
+            started_at: None,
+            finished_at: None,
+            transcript,
+            observer: None,
+            audit_log: AuditLog::default(),
+            audit_sink: None,
+            clock: None,
+            #[cfg(feature = "testing")]
+            faults: Vec::new(),
             participant_impl: Default::default(),
+            open_blinder: parameters.blinder_opening,
+            blinder_opening: None,
+            secret_dealer_ids: BTreeSet::new(),
+            confirmed: false,
         })
     }
 
+    /// The transcript with the parameters of the ceremony
+    /// absorbed before any round is computed
+    fn setup_transcript(parameters: &Parameters<G>) -> Transcript {
+        let mut transcript = Transcript::default();
+        transcript.append(
+            Round::One,
+            0,
+            "threshold",
+            &(parameters.threshold as u64).to_be_bytes(),
+        );
+        transcript.append(
+            Round::One,
+            0,
+            "limit",
+            &(parameters.limit as u64).to_be_bytes(),
+        );
+        transcript.append_points(
+            Round::One,
+            0,
+            "generators",
+            &[parameters.message_generator, parameters.blinder_generator],
+        );
+        transcript.append(
+            Round::One,
+            0,
+            "threshold_policy",
+            &parameters.threshold_policy.to_bytes(),
+        );
+        if let Some(namespace) = &parameters.namespace {
+            transcript.append(Round::One, 0, "namespace", namespace);
+        }
+        transcript
+    }
+
+    /// The layout version this participant was serialized with
+    pub fn get_state_version(&self) -> u16 {
+        self.version
+    }
+
+    /// Upgrade a participant deserialized from an older layout
+    /// to [`STATE_VERSION`].
+    ///
+    /// Fields added since version 0 read as their defaults, which requires the
+    /// state to be deserialized from a self-describing format like JSON.
+    /// Version 0 kept the dealt shares in the [`GennaroDkgPedersenResult`],
+    /// they are moved to protected memory if rounds 1 or 2 still need them
+    /// and erased otherwise. Version 0 had no transcript so it restarts
+    /// from the parameters of the ceremony.
+    ///
+    /// Throws an error if the state is from a newer version of the crate.
+    pub fn migrate(mut self) -> DkgResult<Self> {
+        version::check(self.version)?;
+        if self.version == 0 {
+            let dealt_shares = DealtShares {
+                secret_shares: std::mem::take(&mut self.components.secret_shares),
+                blinder_shares: std::mem::take(&mut self.components.blinder_shares),
+            };
+            if matches!(self.round, Round::One | Round::Two) {
+                self.dealt_shares = dealt_shares.protect()?;
+            }
+            if self.transcript.items().is_empty() {
+                self.transcript = Self::setup_transcript(&self.parameters());
+            }
+            // Version 0 didn't record round 5 and treated round 4 as the end
+            self.confirmed = self.round == Round::Five;
+        }
+        self.version = STATE_VERSION;
+        Ok(self)
+    }
+
     /// The identifier associated with this secret_participant
     pub fn get_id(&self) -> usize {
         self.id
     }
 
-    /// Returns true if this secret_participant is complete
+    /// Returns true if this secret_participant is complete,
+    /// which requires round 5 to confirm the public key
     pub fn completed(&self) -> bool {
-        self.round == Round::Five
+        self.round == Round::Five && self.confirmed
     }
 
     /// Return the current round
@@ -176,6 +417,125 @@ where
         self.round
     }
 
+    /// The opening of this participant's blinder commitments, [`None`]
+    /// until round 3 is computed or if [`Parameters::with_blinder_opening`]
+    /// is not set
+    pub fn blinder_opening(&self) -> Option<BlinderOpening<G>> {
+        if self.round >= Round::Four && self.round != Round::Aborted {
+            self.blinder_opening.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Stop the ceremony and move to [`Round::Aborted`].
+    ///
+    /// The dealt and computed shares are erased and every later
+    /// round returns [`Error::Aborted`] with `reason`.
+    pub fn abort<S: Into<String>>(&mut self, reason: S) {
+        if self.round == Round::Aborted {
+            return;
+        }
+        let reason = reason.into();
+        self.transcript
+            .append(self.round, self.id, "aborted", reason.as_bytes());
+        self.dealt_shares = dealing::empty();
+        self.secret_share = Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO)));
+        self.blind_share = Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO)));
+        self.round1_p2p_data = Slots::default();
+        self.round = Round::Aborted;
+        self.abort_reason = Some(reason);
+    }
+
+    /// The public key and secret share of this participant, or [`None`] until complete
+    pub fn output(&self) -> Option<DkgOutput<G>> {
+        if !self.completed() {
+            return None;
+        }
+        let public_key = self.get_public_key()?;
+        let secret_share = self.computed_secret_share()?;
+        let generator = self.components.pedersen_verifier_set.secret_generator();
+        // Disqualified dealers contribute once their secrets are recovered
+        let recovered = self
+            .transcript
+            .items()
+            .iter()
+            .filter(|item| item.label == "recovered_public_share")
+            .map(|item| item.participant)
+            .collect::<BTreeSet<_>>();
+        let mut report = DkgReport {
+            participant: self.id,
+            threshold: self.threshold,
+            limit: self.limit,
+            participants: self.valid_participant_ids.clone(),
+            dropped: self
+                .invalidated
+                .iter()
+                .map(|(id, (round, reason))| DroppedParticipant {
+                    id: *id,
+                    round: *round,
+                    reason: reason.to_string(),
+                    identity: self.registry.get(*id).cloned(),
+                })
+                .collect(),
+            disqualified_dealers: self.disqualified_dealer_ids.clone(),
+            contributions: self
+                .transcript
+                .items()
+                .iter()
+                .filter(|item| {
+                    item.label == "pedersen_commitments"
+                        && (self.valid_participant_ids.contains(&item.participant)
+                            || recovered.contains(&item.participant))
+                })
+                .map(|item| (item.participant, digest(&item.data)))
+                .collect(),
+            public_key_fingerprint: fingerprint(&public_key),
+            public_share_fingerprints: BTreeMap::new(),
+            transcript_hash: data_encoding::HEXLOWER.encode(&self.get_transcript_hash()),
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+        };
+        report
+            .public_share_fingerprints
+            .insert(self.id, fingerprint(&(generator * secret_share)));
+        Some(DkgOutput {
+            public_key,
+            secret_shares: BTreeMap::from([(self.id, secret_share)]),
+            report,
+        })
+    }
+
+    /// Set the external identities of the participants used in reports.
+    ///
+    /// Throws an error if the registry has ids outside `1..=limit`.
+    pub fn set_registry(&mut self, registry: ParticipantRegistry) -> DkgResult<()> {
+        registry.validate(&self.parameters())?;
+        self.registry = registry;
+        Ok(())
+    }
+
+    /// Accept round 1 data without a [`ZeroSecretProof`] from the dealers in `ids`.
+    ///
+    /// A participant that proves its own secret is zero, like a
+    /// [`RefreshParticipant`], requires a proof from every other dealer
+    /// since a single dealer with a nonzero secret changes the key. When
+    /// participants join a ceremony where the existing holders re-share their
+    /// shares with [`Participant::with_secret`], set those holders here.
+    pub fn set_secret_dealers<T: IntoIterator<Item = usize>>(&mut self, ids: T) {
+        self.secret_dealer_ids = ids.into_iter().collect();
+    }
+
+    /// Return the external identities of the participants
+    pub fn get_registry(&self) -> &ParticipantRegistry {
+        &self.registry
+    }
+
+    /// Return why the ceremony was aborted, if it was
+    pub fn get_abort_reason(&self) -> Option<&str> {
+        self.abort_reason.as_deref()
+    }
+
     /// Return the set threshold
     pub fn get_threshold(&self) -> usize {
         self.threshold
@@ -186,11 +546,53 @@ where
         self.limit
     }
 
+    /// Return the quorum policy
+    pub fn get_quorum_policy(&self) -> QuorumPolicy {
+        self.quorum_policy
+    }
+
+    /// Corrupt this participant's data as described by `fault` when it is computed
+    #[cfg(feature = "testing")]
+    pub fn inject_fault(&mut self, fault: testing::Fault) {
+        self.faults.push(fault);
+    }
+
+    /// Return the reconciliation policy
+    pub fn get_reconciliation_policy(&self) -> ReconciliationPolicy {
+        self.reconciliation_policy
+    }
+
+    /// Return the parameters this participant was created with.
+    ///
+    /// Useful to re-validate a participant restored from storage
+    /// against the expected configuration.
+    pub fn parameters(&self) -> Parameters<G> {
+        Parameters {
+            threshold: self.threshold,
+            limit: self.limit,
+            message_generator: self.components.pedersen_verifier_set.secret_generator(),
+            blinder_generator: self.components.pedersen_verifier_set.blinder_generator(),
+            quorum_policy: self.quorum_policy,
+            secret_count: 1,
+            reconciliation_policy: self.reconciliation_policy,
+            grace_period: self.grace_period,
+            round_deadlines: self.round_deadlines,
+            full_participation: self.full_participation,
+            threshold_policy: self.threshold_policy,
+            namespace: self.namespace,
+            blinder_opening: self.open_blinder,
+        }
+    }
+
     /// Computed secret share.
     /// This value is useless until at least 2 rounds have been run
     /// so [`None`] is returned until completion
-    pub fn get_secret_share(&self) -> Option<G::Scalar> {
-        if self.round >= Round::Two {
+    pub fn get_secret_share(&self) -> Option<I::SecretShare> {
+        self.computed_secret_share().map(I::secret_share)
+    }
+
+    pub(crate) fn computed_secret_share(&self) -> Option<G::Scalar> {
+        if (Round::Two..=Round::Five).contains(&self.round) {
             let mut protected = self.secret_share.lock().ok()?;
             let u = protected.unprotect()?;
             u.field_element::<G::Scalar>().ok()
@@ -206,7 +608,7 @@ where
     /// however, it can be used as a second secret share if needed
     /// thereby allowing to extract a 2nd share from one run of the protocol
     pub fn get_blind_share(&self) -> Option<G::Scalar> {
-        if self.round >= Round::Two {
+        if (Round::Two..=Round::Five).contains(&self.round) {
             let mut protected = self.blind_share.lock().ok()?;
             let u = protected.unprotect()?;
             u.field_element::<G::Scalar>().ok()
@@ -215,11 +617,45 @@ where
         }
     }
 
-    /// Computed public key
+    /// The blinder this participant used for its Pedersen commitments.
+    ///
+    /// This value is sensitive, together with the Pedersen commitments
+    /// it opens this participant's contribution to the blind key.
+    /// Only expose it to auditing tooling that needs to verify the openings.
+    pub fn get_blinder(&self) -> G::Scalar {
+        self.components.blinder
+    }
+
+    /// Computed blind key, the sum of all valid participants' blinders
+    /// times the blinder generator.
     /// This value is useless until all rounds have been run
     /// so [`None`] is returned until completion
+    pub fn get_blind_key(&self) -> Option<G> {
+        if self.completed() {
+            Some(self.blind_key)
+        } else {
+            None
+        }
+    }
+
+    /// The public key computed in round 4 before the other participants
+    /// confirm it in round 5, [`None`] before round 4 completes and
+    /// once round 5 succeeds, use [`Participant::get_public_key`] then
+    pub fn provisional_public_key(&self) -> Option<ProvisionalPublicKey<G>> {
+        if self.round == Round::Five && !self.confirmed {
+            Some(ProvisionalPublicKey {
+                public_key: self.public_key,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Computed public key
+    /// This value is useless until all rounds have been run
+    /// so [`None`] is returned until round 5 confirms it
     pub fn get_public_key(&self) -> Option<G> {
-        if self.round == Round::Five {
+        if self.completed() {
             Some(self.public_key)
         } else {
             None
@@ -231,39 +667,419 @@ where
         &self.valid_participant_ids
     }
 
+    /// Return the list of participant ids that were dropped
+    /// because they did not send data for a forced round
+    pub fn get_absent_participant_ids(&self) -> &BTreeSet<usize> {
+        &self.absent_participant_ids
+    }
+
+    /// Compute the next round with the data received so far
+    /// instead of waiting for the quorum policy to be satisfied.
+    ///
+    /// This is intended to be called when a timeout fires.
+    /// The next round still requires data from at least `threshold`
+    /// participants. Any participant that does not send data for
+    /// that round is permanently removed from the valid set, its shares
+    /// are removed from this participant's shares and it is
+    /// recorded in [`Self::get_absent_participant_ids`].
+    ///
+    /// If [`Parameters::with_full_participation`] is set the
+    /// ceremony is aborted instead.
+    pub fn force_advance(&mut self) {
+        if self.full_participation {
+            self.abort("all participants are required, the round cannot be forced");
+            return;
+        }
+        self.force_advance = true;
+    }
+
+    /// Use `clock` instead of the [`SystemClock`] to check round deadlines.
+    ///
+    /// The current round is timed from when the clock is set.
+    /// Clocks are not serialized so they must be set again after deserializing.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.round_started_at = Some(clock.now());
+        self.clock = Some(ClockHandle(clock));
+    }
+
+    /// Returns true if the deadline set for the current round with
+    /// [`Parameters::with_round_deadline`] has passed
+    pub fn deadline_exceeded(&self) -> bool {
+        let deadline = self
+            .round_deadlines
+            .get(usize::from(self.round))
+            .copied()
+            .flatten();
+        match (deadline, self.round_started_at) {
+            (Some(deadline), Some(started)) => self.now().saturating_sub(started) >= deadline,
+            _ => false,
+        }
+    }
+
+    /// Call [`Participant::force_advance`] if the deadline
+    /// for the current round has passed.
+    ///
+    /// Returns true if the round was forced to advance.
+    pub fn force_advance_if_expired(&mut self) -> bool {
+        let expired = self.deadline_exceeded();
+        if expired {
+            self.force_advance();
+        }
+        expired
+    }
+
+    /// Return the hash of the transcript.
+    ///
+    /// Honest participants that have computed the same rounds
+    /// will have the same hash which can be compared out-of-band.
+    pub fn get_transcript_hash(&self) -> [u8; 32] {
+        self.transcript.hash()
+    }
+
+    /// Return the digest of the transcript computed with the backend `T`
+    /// instead of the default SHA-256
+    pub fn get_transcript_hash_with<T: DkgTranscript>(&self) -> T::Output {
+        self.transcript.hash_with::<T>()
+    }
+
+    pub(crate) fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    pub(crate) fn transcript_mut(&mut self) -> &mut Transcript {
+        &mut self.transcript
+    }
+
+    /// Return the ordered list of entries absorbed into the transcript
+    /// so it can be archived by auditors
+    pub fn export_transcript(&self) -> Vec<TranscriptItem> {
+        self.transcript.items().to_vec()
+    }
+
+    /// Retain an [`AuditEntry`] for every verified incoming message
+    /// from now on, see [`Participant::export_audit_log`]
+    pub fn enable_audit_log(&mut self) {
+        self.audit_log.enable();
+    }
+
+    /// Stream an [`AuditEntry`] for every verified incoming message to `sink`.
+    ///
+    /// Sinks are not serialized so they must be set again after deserializing.
+    pub fn set_audit_sink(&mut self, sink: Arc<dyn AuditSink>) {
+        self.audit_sink = Some(AuditSinkHandle(sink));
+    }
+
+    /// Return the retained audit entries in the order the messages were verified.
+    ///
+    /// Empty unless [`Participant::enable_audit_log`] was called.
+    pub fn export_audit_log(&self) -> Vec<AuditEntry> {
+        self.audit_log.entries()
+    }
+
+    /// Set the observer to be notified as this participant moves through the protocol.
+    ///
+    /// Observers are not serialized so they must be set again after deserializing.
+    pub fn set_observer(&mut self, observer: Arc<dyn RoundObserver>) {
+        self.observer = Some(ObserverHandle(observer));
+    }
+
+    /// The progress of this participant when `received` participants
+    /// have sent the data for the current round, including this participant.
+    ///
+    /// Participants don't buffer the data of other participants so this is
+    /// exposed through [`SessionManager::progress`] which counts the data it
+    /// has buffered. Round 1 requires no data from other participants so it
+    /// can always advance.
+    pub(crate) fn progress(&self, received: usize) -> DkgProgress {
+        let required = self.required();
+        DkgProgress {
+            round: self.round,
+            received,
+            required,
+            can_advance: match self.round {
+                Round::Zero | Round::One => true,
+                Round::Five if self.confirmed => false,
+                Round::Aborted => false,
+                _ => received >= required,
+            },
+        }
+    }
+
+    /// The status of this participant when `received` participants have sent
+    /// the data for the current round, including this participant,
+    /// exposed through [`SessionManager::dkg_status`]
+    pub(crate) fn status(&self, received: usize) -> DkgStatus {
+        let round = match self.round {
+            Round::Zero | Round::One => return DkgStatus::ReadyToRun(Round::One),
+            Round::Five if self.confirmed => return DkgStatus::Completed,
+            Round::Aborted => {
+                return DkgStatus::Aborted {
+                    reason: self.abort_reason.clone().unwrap_or_default(),
+                }
+            }
+            round => round,
+        };
+        let need = self.required();
+        if received >= need {
+            DkgStatus::ReadyToRun(round)
+        } else {
+            DkgStatus::WaitingForMessages {
+                round,
+                have: received,
+                need,
+            }
+        }
+    }
+
+    /// The number of participants whose data the current round needs
+    /// to satisfy the quorum, including this participant
+    fn required(&self) -> usize {
+        match self.round {
+            Round::Zero | Round::One | Round::Aborted => 0,
+            round => {
+                let expected = if round == Round::Two {
+                    self.limit
+                } else {
+                    self.valid_participant_ids.len()
+                };
+                (self.threshold..=expected)
+                    .find(|n| self.quorum_satisfied(*n, expected))
+                    .unwrap_or(expected)
+            }
+        }
+    }
+
+    /// The error for calling `round` while in a different round
+    pub(crate) fn invalid_round(&self, round: Round) -> Error {
+        match &self.abort_reason {
+            Some(reason) => Error::Aborted(reason.clone()),
+            None => Error::InvalidRound {
+                round: round.into(),
+                current: self.round.into(),
+            },
+        }
+    }
+
+    /// Returns true if the zero secret proof in the round 1 data of `dealer`
+    /// verifies or none is required from it, see [`Participant::set_secret_dealers`]
+    pub(crate) fn check_zero_secret_proof(
+        &self,
+        dealer: usize,
+        context: &[u8; 32],
+        bdata: &Round1BroadcastData<G>,
+    ) -> bool {
+        match &bdata.zero_secret_proof {
+            Some(proof) => proof.verify(dealer, context, bdata),
+            None => !I::proves_zero_secret() || self.secret_dealer_ids.contains(&dealer),
+        }
+    }
+
+    /// Abort if a participant was dropped in `round` while all are required
+    pub(crate) fn check_full_participation(&mut self, round: Round) -> DkgResult<()> {
+        if !self.full_participation || self.valid_participant_ids.len() == self.limit {
+            return Ok(());
+        }
+        let reason = format!(
+            "all participants are required but only {} of {} are valid in round {}",
+            self.valid_participant_ids.len(),
+            self.limit,
+            round
+        );
+        self.abort(reason.clone());
+        Err(Error::Aborted(reason))
+    }
+
+    /// Move to `round` and start timing it
+    pub(crate) fn start_round(&mut self, round: Round) {
+        self.round = round;
+        self.round_started_at = Some(self.now());
+    }
+
+    fn now(&self) -> Duration {
+        match &self.clock {
+            Some(clock) => clock.0.now(),
+            None => SystemClock.now(),
+        }
+    }
+
+    pub(crate) fn quorum_satisfied(&self, received: usize, expected: usize) -> bool {
+        if self.full_participation {
+            received >= self.limit
+        } else if self.force_advance {
+            received >= self.threshold
+        } else {
+            self.quorum_policy
+                .is_satisfied(received, self.threshold, expected)
+        }
+    }
+
+    pub(crate) fn notify<F: FnOnce(&dyn RoundObserver)>(&self, f: F) {
+        if let Some(observer) = &self.observer {
+            f(observer.0.as_ref());
+        }
+    }
+
+    pub(crate) fn message_verified(&self, round: Round, sender: usize) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(participant = self.id, %round, sender, "message verified");
+        telemetry::message_verified(round);
+        self.notify(|o| o.on_message_verified(self.id, round, sender));
+    }
+
+    /// Record that `message` from `sender` was verified in `round`
+    pub(crate) fn audit<T: Serialize>(&self, round: Round, sender: usize, message: &T) {
+        if self.audit_sink.is_none() && !self.audit_log.is_enabled() {
+            return;
+        }
+        let entry = AuditEntry::new(round, sender, message);
+        if let Some(sink) = &self.audit_sink {
+            sink.0.record(&entry);
+        }
+        self.audit_log.push(entry);
+    }
+
+    pub(crate) fn message_rejected(&self, round: Round, reason: ParticipantError) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(participant = self.id, %round, %reason, "message rejected");
+        telemetry::message_rejected(round, &reason);
+    }
+
+    pub(crate) fn participant_invalidated(&mut self, round: Round, reason: ParticipantError) {
+        let invalid = reason.participant_id();
+        self.invalidated.insert(invalid, (round, reason));
+        self.message_rejected(round, reason);
+        telemetry::participant_invalidated(round);
+        self.notify(|o| o.on_participant_invalidated(self.id, round, invalid));
+    }
+
+    pub(crate) fn is_invalidated(&self, id: usize) -> bool {
+        self.invalidated.contains_key(&id)
+    }
+
+    /// Drop the valid participants that sent no data for the forced round
+    /// and remove their dealing from the shares combined in round 2
+    pub(crate) fn remove_absent_participants(
+        &mut self,
+        received: &BTreeSet<usize>,
+    ) -> DkgResult<()> {
+        if !self.force_advance {
+            return Ok(());
+        }
+        let absent = self
+            .valid_participant_ids
+            .iter()
+            .filter(|id| **id != self.id && !received.contains(*id))
+            .copied()
+            .collect::<Vec<usize>>();
+        for id in absent {
+            self.remove_dealer(id)?;
+            self.transcript.append(self.round, id, "absent", &[]);
+            self.absent_participant_ids.insert(id);
+            self.participant_invalidated(self.round, ParticipantError::Absent(id));
+        }
+        self.force_advance = false;
+        Ok(())
+    }
+
     fn lagrange_interpolation(
         share: G::Scalar,
         shares_ids: &[G::Scalar],
         index: usize,
     ) -> DkgResult<G::Scalar> {
+        if shares_ids.len() < 2 {
+            return Err(Error::InitializationError(format!(
+                "at least 2 share ids are required, found {}",
+                shares_ids.len()
+            )));
+        }
+        if index >= shares_ids.len() {
+            return Err(Error::InitializationError(format!(
+                "share index {} is out of bounds for {} share ids",
+                index,
+                shares_ids.len()
+            )));
+        }
         let mut set = HashSet::new();
-        for id in shares_ids {
-            if !set.insert(id.to_repr().as_ref().to_vec()) {
-                return Err(Error::InitializationError(format!(
-                    "duplicate id found {:?}",
-                    id
-                )));
+        for (i, id) in shares_ids.iter().enumerate() {
+            if id.is_zero().into() {
+                return Err(Error::InitializationError(
+                    "share ids cannot be zero".to_string(),
+                ));
             }
-        }
-
-        let mut basis = G::Scalar::ONE;
-        for (j, x_j) in shares_ids.iter().enumerate() {
-            if j == index {
-                continue;
+            if !set.insert(id.to_repr().as_ref().to_vec()) {
+                return Err(Error::DuplicateShareId(i));
             }
-            let denominator = *x_j - shares_ids[index];
-            basis *= *x_j * denominator.invert().unwrap();
         }
 
+        let basis = primitives::lagrange_basis(shares_ids, index, G::Scalar::ZERO)
+            .map_err(Error::DuplicateShareId)?;
         Ok(basis * share)
     }
 }
 
+#[cfg(not(feature = "unsafe_debug"))]
+impl<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> fmt::Debug for Participant<I, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Participant")
+            .field("id", &self.id)
+            .field("threshold", &self.threshold)
+            .field("limit", &self.limit)
+            .field("quorum_policy", &self.quorum_policy)
+            .field("reconciliation_policy", &self.reconciliation_policy)
+            .field("grace_period", &self.grace_period)
+            .field("round", &self.round)
+            .field("components", &self.components)
+            .field("secret_share", &"<redacted>")
+            .field("blind_share", &"<redacted>")
+            .field("public_key", &self.public_key)
+            .field("blind_key", &self.blind_key)
+            .field("valid_participant_ids", &self.valid_participant_ids)
+            .field("absent_participant_ids", &self.absent_participant_ids)
+            .field("disqualified_dealer_ids", &self.disqualified_dealer_ids)
+            .field("rehabilitated_ids", &self.rehabilitated_ids)
+            .field("force_advance", &self.force_advance)
+            .field("abort_reason", &self.abort_reason)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Drops this participant's references to its shares and the shares it
+/// received so they are wiped once no clone of the participant holds them.
+/// The participant can't compute any further rounds afterwards.
+impl<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> Zeroize for Participant<I, G> {
+    fn zeroize(&mut self) {
+        self.components.zeroize();
+        self.dealt_shares = dealing::empty();
+        self.secret_share = Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO)));
+        self.blind_share = Arc::new(Mutex::new(Protected::field_element(G::Scalar::ZERO)));
+        self.round1_p2p_data = Slots::default();
+    }
+}
+
+impl<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> Drop for Participant<I, G> {
+    fn drop(&mut self) {
+        self.components.zeroize();
+    }
+}
+
+/// The shares are kept in [`Protected`] memory which is wiped when dropped
+/// and the polynomials in [`GennaroDkgPedersenResult`] are wiped by [`Drop`].
+impl<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> ZeroizeOnDrop
+    for Participant<I, G>
+{
+}
+
 /// Secret Participant Implementation
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct SecretParticipantImpl<G>(PhantomData<G>);
 
 impl<G: Group + GroupEncoding + Default> ParticipantImpl<G> for SecretParticipantImpl<G> {
+    type SecretShare = G::Scalar;
+
+    fn secret_share(share: G::Scalar) -> G::Scalar {
+        share
+    }
+
     fn secret(mut rng: impl RngCore) -> <G as Group>::Scalar {
         G::Scalar::random(&mut rng)
     }
@@ -282,6 +1098,12 @@ impl<G: Group + GroupEncoding + Default> ParticipantImpl<G> for SecretParticipan
 pub struct RefreshParticipantImpl<G>(PhantomData<G>);
 
 impl<G: Group + GroupEncoding + Default> ParticipantImpl<G> for RefreshParticipantImpl<G> {
+    type SecretShare = RefreshShareDelta<G>;
+
+    fn secret_share(share: G::Scalar) -> RefreshShareDelta<G> {
+        RefreshShareDelta(share)
+    }
+
     fn secret(mut _rng: impl RngCore) -> <G as Group>::Scalar {
         G::Scalar::ZERO
     }
@@ -293,6 +1115,69 @@ impl<G: Group + GroupEncoding + Default> ParticipantImpl<G> for RefreshParticipa
     fn check_public_key(key: G, computed: G) -> bool {
         (key.is_identity() & computed.is_identity()).into()
     }
+
+    fn proves_zero_secret() -> bool {
+        true
+    }
+}
+
+/// The share computed by a [`RefreshParticipant`].
+///
+/// When every participant refreshes, the share is a delta that must be
+/// added to the participant's existing share with [`RefreshShareDelta::apply`],
+/// it is not a share of the key by itself.
+#[derive(Clone)]
+pub struct RefreshShareDelta<G: Group + GroupEncoding + Default>(G::Scalar);
+
+impl<G: Group + GroupEncoding + Default> Zeroize for RefreshShareDelta<G> {
+    fn zeroize(&mut self) {
+        utils::zeroize_scalar(&mut self.0);
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Drop for RefreshShareDelta<G> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> ZeroizeOnDrop for RefreshShareDelta<G> {}
+
+impl<G: Group + GroupEncoding + Default> RefreshShareDelta<G> {
+    /// The refreshed share for the existing `share`
+    pub fn apply(&self, share: G::Scalar) -> G::Scalar {
+        share + self.0
+    }
+
+    /// The share of a participant that joins with no existing share
+    /// while the others deal their existing shares with [`Participant::with_secret`]
+    pub fn into_new_share(self) -> G::Scalar {
+        self.0
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for RefreshShareDelta<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RefreshShareDelta(<redacted>)")
+    }
+}
+
+/// The public key computed by this participant in round 4,
+/// returned by [`Participant::provisional_public_key`].
+///
+/// It is unconfirmed until round 5 succeeds, the other participants may
+/// have computed a different key. Use it to prepare for the key, for
+/// example registering it or warming caches, but not to rely on it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProvisionalPublicKey<G: Group + GroupEncoding + Default> {
+    public_key: G,
+}
+
+impl<G: Group + GroupEncoding + Default> ProvisionalPublicKey<G> {
+    /// The unconfirmed public key
+    pub fn unconfirmed(&self) -> G {
+        self.public_key
+    }
 }
 
 #[cfg(test)]
@@ -303,6 +1188,47 @@ mod tests {
     use rand_core::SeedableRng;
     use vsss_rs::{Pedersen, PedersenResult, Share};
 
+    #[test]
+    fn with_secret_invalid_share_ids() {
+        let parameters = Parameters::<G1Projective>::new(
+            NonZeroUsize::new(2).unwrap(),
+            NonZeroUsize::new(3).unwrap(),
+        );
+        let id = NonZeroUsize::new(1).unwrap();
+        let share = Scalar::random(rand_core::OsRng);
+        let one = Scalar::ONE;
+        let two = Scalar::from(2u64);
+
+        for (ids, index) in [
+            (vec![one], 0),
+            (vec![one, two], 2),
+            (vec![one, Scalar::ZERO], 0),
+        ] {
+            assert!(SecretParticipant::<G1Projective>::with_secret(
+                id, parameters, share, &ids, index
+            )
+            .is_err());
+        }
+        assert!(matches!(
+            SecretParticipant::<G1Projective>::with_secret(
+                id,
+                parameters,
+                share,
+                &[one, two, one],
+                0
+            ),
+            Err(Error::DuplicateShareId(2))
+        ));
+        assert!(SecretParticipant::<G1Projective>::with_secret(
+            id,
+            parameters,
+            share,
+            &[one, two],
+            0
+        )
+        .is_ok());
+    }
+
     #[test]
     fn reconstruct_blind_key() {
         let mut rng = ChaCha12Rng::from_seed([1u8; 32]);
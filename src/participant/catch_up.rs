@@ -0,0 +1,59 @@
+use super::*;
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Rejoin a run after missing some rounds by replaying the archived broadcast data.
+    ///
+    /// This participant must be restored from a checkpoint taken after its
+    /// round 1 data was sent. `p2p_data` is the round 1 peer data it received
+    /// keyed by sender. Every round whose input is in `archive` is computed
+    /// again which verifies all the archived data. When the archive contains
+    /// this participant's own data for a round, the recomputed data must match it.
+    ///
+    /// Returns the data from the last computed round that still has to be sent,
+    /// [`RoundOutput::Complete`] if round 5 succeeded or [`None`] if there
+    /// is nothing to send until more data arrives.
+    pub fn catch_up(
+        &mut self,
+        archive: &BroadcastArchive<G>,
+        p2p_data: BTreeMap<usize, Round1P2PData>,
+    ) -> DkgResult<Option<RoundOutput<G>>> {
+        if self.round < Round::Two || self.round == Round::Aborted {
+            return Err(self.invalid_round(Round::Two));
+        }
+        loop {
+            let round = self.round;
+            let output = match round {
+                Round::Two if !archive.round1_broadcast.is_empty() => {
+                    let broadcast_data = archive
+                        .round1_broadcast
+                        .iter()
+                        .filter(|(id, _)| **id != self.id)
+                        .map(|(id, bdata)| (*id, bdata.clone()))
+                        .collect();
+                    RoundOutput::Round2(self.round2(broadcast_data, p2p_data.clone())?)
+                }
+                Round::Three if !archive.round2_echo.is_empty() => {
+                    RoundOutput::Round3(self.round3(&archive.round2_echo)?)
+                }
+                Round::Four if !archive.round3_broadcast.is_empty() => {
+                    RoundOutput::Round4(self.round4(&archive.round3_broadcast)?)
+                }
+                Round::Five if !archive.round4_echo.is_empty() => {
+                    self.round5(&archive.round4_echo)?;
+                    return Ok(Some(RoundOutput::Complete));
+                }
+                _ => return Ok(None),
+            };
+            match archive.matches(self.id, &output) {
+                None => return Ok(Some(output)),
+                Some(false) => {
+                    return Err(Error::RoundError(
+                        round.into(),
+                        "The archived data from this participant doesn't match".to_string(),
+                    ))
+                }
+                Some(true) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use super::*;
+
+/// A participant in a key import ceremony
+pub type ImportParticipant<G> = Participant<ImportParticipantImpl<G>, G>;
+
+/// Key Import Participant Implementation.
+///
+/// Converts an existing single signer key into a threshold sharing.
+/// The key owner deals the key with [`Participant::key_owner`] while the
+/// other participants deal zero with [`Participant::key_holder`] so the
+/// ceremony randomizes the sharing without changing the key. In round 4
+/// every participant checks the owner's feldman commitment is the known
+/// public key and every other commitment is the identity, the
+/// output is the same [`DkgOutput`] as a new key gen.
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct ImportParticipantImpl<G>(PhantomData<G>);
+
+impl<G: Group + GroupEncoding + Default> ParticipantImpl<G> for ImportParticipantImpl<G> {
+    type SecretShare = G::Scalar;
+
+    fn secret_share(share: G::Scalar) -> G::Scalar {
+        share
+    }
+
+    fn secret(mut _rng: impl RngCore) -> <G as Group>::Scalar {
+        G::Scalar::ZERO
+    }
+
+    fn check_feldman_verifier(_verifier: G) -> bool {
+        // Checked against the imported key in round 4
+        true
+    }
+
+    fn check_public_key(key: G, computed: G) -> bool {
+        key == computed
+    }
+}
+
+/// The key being imported and the participant that owns it
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct KeyImport<G: Group + GroupEncoding + Default> {
+    pub(crate) owner: usize,
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub(crate) public_key: G,
+}
+
+impl<G: Group + GroupEncoding + Default> KeyImport<G> {
+    /// The owner commits to the key, everyone else to the identity
+    pub(crate) fn check_commitment(&self, id: usize, commitment: G) -> bool {
+        if id == self.owner {
+            commitment == self.public_key
+        } else {
+            commitment.is_identity().into()
+        }
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> Participant<ImportParticipantImpl<G>, G> {
+    /// Create the participant that owns `key` and deals it to the others
+    pub fn key_owner(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        key: G::Scalar,
+    ) -> DkgResult<Self> {
+        if key.is_zero().into() {
+            return Err(Error::InitializationError(
+                "the imported key cannot be zero".to_string(),
+            ));
+        }
+        let mut rng = rand_core::OsRng;
+        let blinder = G::Scalar::random(&mut rng);
+        let mut participant = Self::initialize(id, parameters, key, blinder, rng)?;
+        participant.set_key_import(id, parameters.message_generator * key)?;
+        Ok(participant)
+    }
+
+    /// Create a participant that receives a share of the key owned by
+    /// `owner` whose public key is `public_key`
+    pub fn key_holder(
+        id: NonZeroUsize,
+        parameters: Parameters<G>,
+        owner: NonZeroUsize,
+        public_key: G,
+    ) -> DkgResult<Self> {
+        if id == owner {
+            return Err(Error::InitializationError(
+                "the key owner must be created with key_owner".to_string(),
+            ));
+        }
+        let mut participant = Self::new(id, parameters)?;
+        participant.set_key_import(owner, public_key)?;
+        Ok(participant)
+    }
+
+    fn set_key_import(&mut self, owner: NonZeroUsize, public_key: G) -> DkgResult<()> {
+        if owner.get() > self.limit {
+            return Err(Error::InitializationError(format!(
+                "the key owner {} is not between 1 and {}",
+                owner, self.limit
+            )));
+        }
+        if public_key.is_identity().into() {
+            return Err(Error::InitializationError(
+                "the imported public key cannot be the identity".to_string(),
+            ));
+        }
+        self.transcript.append(
+            Round::One,
+            0,
+            "key_owner",
+            &(owner.get() as u64).to_be_bytes(),
+        );
+        self.transcript
+            .append_points(Round::One, 0, "imported_key", &[public_key]);
+        self.key_import = Some(KeyImport {
+            owner: owner.get(),
+            public_key,
+        });
+        Ok(())
+    }
+
+    /// The id of the participant that owns the imported key
+    pub fn get_key_owner(&self) -> Option<usize> {
+        self.key_import.map(|import| import.owner)
+    }
+}
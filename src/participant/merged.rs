@@ -0,0 +1,144 @@
+use super::*;
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Computes round 2 of the merged round variant for this participant.
+    ///
+    /// Works the same as [`Participant::round2`] but also returns the
+    /// feldman commitments normally sent in round 3 so both are sent
+    /// in a single broadcast. Only use this variant over a broadcast channel
+    /// where all participants are trusted to deliver the same data to everyone,
+    /// otherwise use the default 5 round flow.
+    ///
+    /// # Key bias
+    ///
+    /// The feldman commitments reveal `message_generator * secret` for every
+    /// dealer before the set of qualified dealers is fixed. A rushing
+    /// adversary that waits for the honest commitments can then choose which
+    /// of its own dealers to disqualify, for example by sending bad shares
+    /// to one participant, and so choose between several public keys. The
+    /// public key is no longer uniformly random, which invalidates any
+    /// security argument that assumes a uniform key. The 5 round flow only reveals the feldman
+    /// commitments after the valid set is agreed in round 3 and has no such
+    /// bias. Only use this variant when a biased key is acceptable.
+    ///
+    /// Throws an error if this participant is not in round 2.
+    pub fn round2_merged(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+        p2p_data: BTreeMap<usize, Round1P2PData>,
+    ) -> DkgResult<MergedRound2Data<G>> {
+        let echo = self.round2(broadcast_data, p2p_data)?;
+        Ok(MergedRound2Data {
+            echo,
+            commitments: Round3BroadcastData {
+                commitments: self.components.feldman_verifier_set.verifiers().to_vec(),
+            },
+        })
+    }
+
+    /// Computes round 3 of the merged round variant for this participant.
+    ///
+    /// Checks the echoed valid participant ids as in [`Participant::round3`]
+    /// then checks the feldman commitments as in [`Participant::round4`].
+    /// The returned public key is checked by [`Participant::round5`] as usual.
+    ///
+    /// Throws an error if this participant is not in round 3.
+    pub fn round3_merged(
+        &mut self,
+        data: &BTreeMap<usize, MergedRound2Data<G>>,
+    ) -> DkgResult<Round4EchoBroadcastData<G>> {
+        let echo_data = data
+            .iter()
+            .map(|(id, d)| (*id, d.echo.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let broadcast_data = data
+            .iter()
+            .map(|(id, d)| (*id, d.commitments.clone()))
+            .collect::<BTreeMap<_, _>>();
+        self.round3(&echo_data)?;
+        self.round4(&broadcast_data)
+    }
+
+    /// Computes round 3 of the merged round variant taking the fast path
+    /// when every participant responded honestly.
+    ///
+    /// If data from all participants arrived and each reports every
+    /// participant as valid, the public key echo of round 4 and 5 and the
+    /// echoes of the valid sets and commitments are batched into one
+    /// confirmation. Broadcast it and check the confirmations received
+    /// with [`Participant::confirm_fast`].
+    ///
+    /// Throws an error if this participant is not in round 3. If the fast path
+    /// can't be taken an error is thrown without changing this participant,
+    /// use [`Participant::round3_merged`] with the same data instead.
+    pub fn round3_fast(
+        &mut self,
+        data: &BTreeMap<usize, MergedRound2Data<G>>,
+    ) -> DkgResult<FastConfirmationData<G>> {
+        if !matches!(self.round, Round::Three) {
+            return Err(self.invalid_round(Round::Three));
+        }
+        let all_ids = (1..=self.limit).collect::<BTreeSet<usize>>();
+        let fast_path = data.len() == self.limit
+            && self.valid_participant_ids == all_ids
+            && data
+                .iter()
+                .all(|(id, d)| all_ids.contains(id) && d.echo.valid_participant_ids == all_ids);
+        if !fast_path {
+            return Err(Error::InsufficientData(
+                Round::Three.into(),
+                "The fast path needs valid data from all participants".to_string(),
+            ));
+        }
+        let echo = self.round3_merged(data)?;
+        Ok(FastConfirmationData {
+            public_key: echo.public_key,
+            transcript_hash: self.get_transcript_hash(),
+        })
+    }
+
+    /// Check the confirmations from [`Participant::round3_fast`].
+    ///
+    /// Every valid participant must confirm the same public key and transcript,
+    /// in place of [`Participant::round5`].
+    ///
+    /// Throws an error if this participant is not in round 5.
+    pub fn confirm_fast(
+        &mut self,
+        confirmations: &BTreeMap<usize, FastConfirmationData<G>>,
+    ) -> DkgResult<()> {
+        if !matches!(self.round, Round::Five) {
+            return Err(self.invalid_round(Round::Five));
+        }
+        let transcript_hash = self.get_transcript_hash();
+        for id in &self.valid_participant_ids {
+            if *id == self.id {
+                continue;
+            }
+            let Some(confirmation) = confirmations.get(id) else {
+                return Err(Error::InsufficientData(
+                    Round::Five.into(),
+                    format!("Missing the confirmation from participant {}", id),
+                ));
+            };
+            let error = if confirmation.public_key != self.public_key {
+                ParticipantError::MismatchedPublicKey(*id)
+            } else if confirmation.transcript_hash != transcript_hash {
+                ParticipantError::MismatchedTranscript(*id)
+            } else {
+                self.message_verified(Round::Five, *id);
+                continue;
+            };
+            self.message_rejected(Round::Five, error);
+            return Err(Error::SenderError {
+                round: Round::Five.into(),
+                error,
+            });
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(participant = self.id, "protocol complete on the fast path");
+        self.confirmed = true;
+        self.notify(|o| o.on_complete(self.id, self.public_key.to_bytes().as_ref()));
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use std::ops::Deref;
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Return the ids of the dealers disqualified in round 4 whose
+    /// contribution must be recovered before round 5
+    pub fn get_disqualified_dealer_ids(&self) -> &BTreeSet<usize> {
+        &self.disqualified_dealer_ids
+    }
+
+    /// The shares this participant received from the disqualified dealers.
+    ///
+    /// The dealers already failed so revealing their shares doesn't
+    /// leak anything about the honest participants' secrets.
+    ///
+    /// Throws an error if this participant has not completed round 4.
+    pub fn recovery_shares(&self) -> DkgResult<RecoveryShareData> {
+        if !matches!(self.round, Round::Five) {
+            return Err(self.invalid_round(Round::Five));
+        }
+        let mut shares = BTreeMap::new();
+        for id in &self.disqualified_dealer_ids {
+            shares.insert(*id, self.unprotect_round1_p2p_data(id)?);
+        }
+        Ok(RecoveryShareData { shares })
+    }
+
+    /// Reconstruct the secrets of the disqualified dealers from the shares
+    /// revealed by the valid participants and add their contribution to the public key.
+    ///
+    /// Each revealed share is checked against the dealer's pedersen commitments
+    /// from round 1, invalid ones are ignored. At least `threshold` valid shares
+    /// are required for each dealer.
+    ///
+    /// Returns the public key that should be echo broadcast in place of the round 4 output.
+    ///
+    /// Throws an error if this participant has not completed round 4.
+    pub fn recover_disqualified_dealers(
+        &mut self,
+        recovery_data: &BTreeMap<usize, RecoveryShareData>,
+    ) -> DkgResult<Round4EchoBroadcastData<G>> {
+        if !matches!(self.round, Round::Five) {
+            return Err(self.invalid_round(Round::Five));
+        }
+
+        let mut secrets = BTreeMap::new();
+        for dealer in &self.disqualified_dealer_ids {
+            let bdata = self.round1_broadcast_data.require(dealer, Round::Five)?;
+            let verifier = Vec::<G>::pedersen_set_with_generators_and_verifiers(
+                bdata.message_generator,
+                bdata.blinder_generator,
+                &bdata.pedersen_commitments,
+            );
+            let mut shares = vec![self.unprotect_round1_p2p_data(dealer)?.secret_share.clone()];
+            for (id, data) in recovery_data {
+                if *id == self.id || !self.valid_participant_ids.contains(id) {
+                    continue;
+                }
+                let Some(p2p) = data.shares.get(dealer) else {
+                    continue;
+                };
+                // A share must be the one the dealer sent to the revealing participant
+                if p2p.secret_share.identifier() as usize != *id
+                    || verifier
+                        .verify_share_and_blinder(&p2p.secret_share, &p2p.blind_share)
+                        .is_err()
+                {
+                    self.message_rejected(Round::Five, ParticipantError::NoVerifyShares(*id));
+                    continue;
+                }
+                shares.push(p2p.secret_share.clone());
+            }
+            if shares.len() < self.threshold {
+                return Err(Error::InsufficientData(
+                    Round::Five.into(),
+                    format!("Not enough valid shares to recover dealer {}", dealer),
+                ));
+            }
+            let secret =
+                vsss_rs::combine_shares::<G::Scalar, u8, InnerShare>(&shares[..self.threshold])?;
+            shares.zeroize();
+            secrets.insert(*dealer, secret);
+        }
+
+        let generator = self.components.feldman_verifier_set.generator();
+        for (dealer, mut secret) in secrets {
+            let public_share = generator * secret;
+            utils::zeroize_scalar(&mut secret);
+            self.public_key += public_share;
+            self.blind_key -= public_share;
+            self.transcript.append_points(
+                Round::Four,
+                dealer,
+                "recovered_public_share",
+                &[public_share],
+            );
+        }
+        self.transcript
+            .append_points(Round::Four, 0, "public_key", &[self.public_key]);
+        self.disqualified_dealer_ids.clear();
+        self.round1_p2p_data = Slots::default();
+        self.round1_broadcast_data = Slots::default();
+
+        Ok(Round4EchoBroadcastData {
+            public_key: self.public_key,
+        })
+    }
+
+    fn unprotect_round1_p2p_data(&self, id: &usize) -> DkgResult<Round1P2PData> {
+        let value = self.round1_p2p_data.get(id).ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), format!("missing shares from {}", id))
+        })?;
+        let mut protected = value
+            .deref()
+            .lock()
+            .map_err(|_e| Error::RoundError(Round::Five.into(), "unable to lock".to_string()))?;
+        let unprotected = protected.unprotect().ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid secret unprotected".to_string())
+        })?;
+        unprotected
+            .serde::<Round1P2PData>()
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
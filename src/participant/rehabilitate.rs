@@ -0,0 +1,108 @@
+use super::*;
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Accept the round 1 data from participant `id` that arrived after
+    /// it was dropped as absent in round 2.
+    ///
+    /// The data must pass the same checks as in [`Participant::round2`], then
+    /// `id` is valid again and its shares are included. This is only allowed
+    /// when [`Parameters::grace_period`] is non-zero and before round 3 is
+    /// computed, so the round 3 echo still covers the late data. Round 3
+    /// tolerates `id` missing from the valid sets echoed by the other
+    /// participants, which were sent before the data arrived.
+    /// All honest participants must accept the same late data, otherwise
+    /// they compute different public keys and round 5 fails.
+    pub fn rehabilitate(
+        &mut self,
+        id: usize,
+        broadcast_data: Round1BroadcastData<G>,
+        p2p_data: Round1P2PData,
+    ) -> DkgResult<()> {
+        if self.round != Round::Three || self.grace_period == 0 {
+            return Err(self.invalid_round(Round::Three));
+        }
+        if !self.absent_participant_ids.contains(&id)
+            || self.round1_broadcast_data.contains_key(&id)
+        {
+            return Err(Error::InvalidParameters(format!(
+                "participant {} was not dropped as absent in round 2",
+                id
+            )));
+        }
+
+        let sender_error = |error| Error::SenderError {
+            round: Round::Two.into(),
+            error,
+        };
+        if broadcast_data.blinder_generator
+            != self.components.pedersen_verifier_set.blinder_generator()
+            || broadcast_data.message_generator
+                != self.components.pedersen_verifier_set.secret_generator()
+            || broadcast_data.pedersen_commitments.len() != self.threshold
+        {
+            return Err(sender_error(ParticipantError::MismatchedParameters(id)));
+        }
+        if broadcast_data
+            .pedersen_commitments
+            .iter()
+            .any(|c| c.is_identity().into())
+        {
+            return Err(sender_error(
+                ParticipantError::IdentityElementPedersenCommitments(id),
+            ));
+        }
+        if !self.check_zero_secret_proof(id, &self.transcript.setup_hash(), &broadcast_data) {
+            return Err(sender_error(ParticipantError::InvalidZeroSecretProof(id)));
+        }
+        if (p2p_data.secret_share.is_zero() | p2p_data.blind_share.is_zero()).into() {
+            return Err(sender_error(ParticipantError::ZeroValueShares(id)));
+        }
+        let verifier = Vec::<G>::pedersen_set_with_generators_and_verifiers(
+            broadcast_data.message_generator,
+            broadcast_data.blinder_generator,
+            &broadcast_data.pedersen_commitments,
+        );
+        if p2p_data.secret_share.identifier() as usize != self.id
+            || verifier
+                .verify_share_and_blinder(&p2p_data.secret_share, &p2p_data.blind_share)
+                .is_err()
+        {
+            return Err(sender_error(ParticipantError::NoVerifyShares(id)));
+        }
+
+        let secret_share = self.computed_secret_share().ok_or_else(|| {
+            Error::RoundError(Round::Two.into(), "invalid secret share".to_string())
+        })? + p2p_data.secret_share.as_field_element::<G::Scalar>()?;
+        let blind_share = self.get_blind_share().ok_or_else(|| {
+            Error::RoundError(Round::Two.into(), "invalid blind share".to_string())
+        })? + p2p_data.blind_share.as_field_element::<G::Scalar>()?;
+        self.secret_share = Arc::new(Mutex::new(Protected::field_element(secret_share)));
+        self.blind_share = Arc::new(Mutex::new(Protected::field_element(blind_share)));
+        self.blind_key += broadcast_data.pedersen_commitments[0];
+        self.transcript.append(Round::Two, id, "rehabilitated", &[]);
+        self.transcript.append_points(
+            Round::One,
+            id,
+            "pedersen_commitments",
+            &broadcast_data.pedersen_commitments,
+        );
+        if broadcast_data.zero_secret_proof.is_some() {
+            self.transcript.append(Round::One, id, "zero_secret", &[]);
+        }
+
+        self.audit(Round::Two, id, &(&broadcast_data, &p2p_data));
+        let p2p = Protected::serde(&p2p_data).map_err(|_e| {
+            Error::RoundError(Round::Two.into(), "unable to protect shares".to_string())
+        })?;
+        self.round1_p2p_data
+            .insert(id, self.limit, Arc::new(Mutex::new(p2p)))?;
+        self.round1_broadcast_data
+            .insert(id, self.limit, broadcast_data)?;
+        self.absent_participant_ids.remove(&id);
+        self.invalidated.remove(&id);
+        self.rehabilitated_ids.insert(id);
+        self.valid_participant_ids.insert(id);
+        self.message_verified(Round::Two, id);
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
+    /// Resume a participant from deserialized `state` with the
+    /// parameters it was created with.
+    ///
+    /// The state is migrated with [`Participant::migrate`] then checked against
+    /// `parameters` and the invariants of its round so an inconsistent
+    /// checkpoint is refused instead of failing later in the protocol.
+    /// Custom quorum policies cannot be serialized so a custom policy in
+    /// `parameters` replaces the serialized one, any other policy must match.
+    /// The round deadlines are taken from `parameters`. The observer,
+    /// audit sink and clock are not serialized either and must be set again.
+    ///
+    /// Throws [`Error::Aborted`] if the state was aborted and
+    /// [`Error::InvalidParameters`] if it is inconsistent.
+    pub fn resume(state: Self, parameters: Parameters<G>) -> DkgResult<Self> {
+        parameters.validate()?;
+        let mut state = state.migrate()?;
+        if let Some(reason) = &state.abort_reason {
+            return Err(Error::Aborted(reason.clone()));
+        }
+        let inconsistent = |reason: &str| {
+            Error::InvalidParameters(format!(
+                "cannot resume participant {}: {}",
+                state.id, reason
+            ))
+        };
+
+        if state.threshold != parameters.threshold
+            || state.limit != parameters.limit
+            || state.reconciliation_policy != parameters.reconciliation_policy
+            || state.grace_period != parameters.grace_period
+            || state.full_participation != parameters.full_participation
+            || state.threshold_policy != parameters.threshold_policy
+            || state.namespace != parameters.namespace
+            || state.open_blinder != parameters.blinder_opening
+        {
+            return Err(inconsistent("the parameters do not match"));
+        }
+        let pedersen = &state.components.pedersen_verifier_set;
+        let feldman = &state.components.feldman_verifier_set;
+        if pedersen.secret_generator() != parameters.message_generator
+            || pedersen.blinder_generator() != parameters.blinder_generator
+            || feldman.generator() != parameters.message_generator
+        {
+            return Err(inconsistent("the generators do not match"));
+        }
+        if state.id == 0 || state.id > state.limit {
+            return Err(inconsistent("the id is out of range"));
+        }
+        if pedersen.blind_verifiers().len() != state.threshold
+            || feldman.verifiers().len() != state.threshold
+        {
+            return Err(inconsistent("the commitments do not match the threshold"));
+        }
+
+        match state.round {
+            Round::Zero | Round::Aborted => {
+                return Err(inconsistent("the round is not resumable"));
+            }
+            Round::One | Round::Two => {
+                // The shares are dealt until round 2 completes
+                let dealt_shares = DealtShares::unprotect(&state.dealt_shares)?;
+                if dealt_shares.secret_shares.len() != state.limit
+                    || dealt_shares.blinder_shares.len() != state.limit
+                    || pedersen
+                        .verify_share_and_blinder(
+                            &dealt_shares.secret_shares[state.id - 1],
+                            &dealt_shares.blinder_shares[state.id - 1],
+                        )
+                        .is_err()
+                {
+                    return Err(inconsistent("the dealt shares do not verify"));
+                }
+            }
+            Round::Three | Round::Four | Round::Five => {
+                if !state.valid_participant_ids.contains(&state.id)
+                    || state.valid_participant_ids.len() < state.threshold
+                {
+                    return Err(inconsistent("the valid participants are inconsistent"));
+                }
+                let secret_share = state
+                    .computed_secret_share()
+                    .ok_or_else(|| inconsistent("the secret share is missing"))?;
+                if secret_share.is_zero().into() {
+                    return Err(inconsistent("the secret share is zero"));
+                }
+            }
+        }
+        if state.round == Round::Five && !I::check_feldman_verifier(state.public_key) {
+            return Err(inconsistent("the public key is invalid"));
+        }
+        match (state.quorum_policy, parameters.quorum_policy) {
+            (_, QuorumPolicy::Custom(_))
+            | (QuorumPolicy::WaitForAll, QuorumPolicy::WaitForAll)
+            | (QuorumPolicy::Threshold, QuorumPolicy::Threshold) => {}
+            _ => return Err(inconsistent("the quorum policy does not match")),
+        }
+        state.quorum_policy = parameters.quorum_policy;
+        state.round_deadlines = parameters.round_deadlines;
+        Ok(state)
+    }
+}
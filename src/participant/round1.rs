@@ -4,21 +4,24 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     /// Compute round1 for this participant.
     ///
     /// Throws an error if this participant is not in round 1.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(participant = self.id, round = 1))
+    )]
     pub fn round1(
         &mut self,
     ) -> DkgResult<(Round1BroadcastData<G>, BTreeMap<usize, Round1P2PData>)> {
         if !matches!(self.round, Round::One) {
-            return Err(Error::RoundError(
-                Round::One.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
+            return Err(self.invalid_round(Round::One));
         }
+        self.notify(|o| o.on_round_started(self.id, Round::One));
+        let timer = telemetry::RoundTimer::start(Round::One);
+        let dealt_shares = DealtShares::for_round(&self.dealt_shares, self.limit, Round::One)?;
         let mut map = BTreeMap::new();
-        for (s, b) in self
-            .components
+        for (s, b) in dealt_shares
             .secret_shares
             .iter()
-            .zip(self.components.blinder_shares.iter())
+            .zip(dealt_shares.blinder_shares.iter())
         {
             let id = s.identifier() as usize;
             if id == self.id {
@@ -34,8 +37,9 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             );
         }
 
-        self.round = Round::Two;
-        let bdata = Round1BroadcastData {
+        self.start_round(Round::Two);
+        self.started_at = now();
+        let mut bdata = Round1BroadcastData {
             blinder_generator: self.components.pedersen_verifier_set.blinder_generator(),
             message_generator: self.components.pedersen_verifier_set.secret_generator(),
             pedersen_commitments: self
@@ -43,8 +47,22 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 .pedersen_verifier_set
                 .blind_verifiers()
                 .to_vec(),
+            zero_secret_proof: None,
         };
+        if I::proves_zero_secret() {
+            bdata.zero_secret_proof = Some(ZeroSecretProof::prove(
+                self.id,
+                &self.transcript.setup_hash(),
+                &bdata,
+                self.components.blinder,
+            )?);
+        }
 
+        #[cfg(feature = "testing")]
+        self.faults
+            .iter()
+            .for_each(|f| f.round1(&mut bdata, &mut map));
+        timer.stop();
         Ok((bdata, map))
     }
 }
@@ -23,35 +23,43 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     ///
     /// Throws an error if this participant is not in round 2.
     ///
+    /// Received share values are handled in constant time: zero checks and
+    /// comparisons use `subtle` and the shares are verified as one combined
+    /// equation. The only branches taken are on whether a sender's data is
+    /// well formed or verifies, which is public once the round completes.
+    ///
     /// Returns the data needed for round 2
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(participant = self.id, round = 2))
+    )]
     pub fn round2(
         &mut self,
         broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
         p2p_data: BTreeMap<usize, Round1P2PData>,
     ) -> DkgResult<Round2EchoBroadcastData> {
         if !matches!(self.round, Round::Two) {
-            return Err(Error::RoundError(
-                Round::Two.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
+            return Err(self.invalid_round(Round::Two));
         }
+        self.notify(|o| o.on_round_started(self.id, Round::Two));
+        let timer = telemetry::RoundTimer::start(Round::Two);
 
         if broadcast_data.is_empty() {
-            return Err(Error::RoundError(
+            return Err(Error::InsufficientData(
                 Round::Two.into(),
                 "Missing broadcast data from other participants".to_string(),
             ));
         }
         if p2p_data.is_empty() {
-            return Err(Error::RoundError(
+            return Err(Error::InsufficientData(
                 Round::Two.into(),
                 "Missing peer-to-peer data from other participants".to_string(),
             ));
         }
-        // Allow -1 since including this participant
+        // Allow +1 since including this participant
         // This round doesn't expect this participant data included in the broadcast_data map
-        if broadcast_data.len() < self.threshold - 1 {
-            return Err(Error::RoundError(
+        if !self.quorum_satisfied(broadcast_data.len() + 1, self.limit) {
+            return Err(Error::InsufficientData(
                 Round::Two.into(),
                 format!(
                     "Not enough secret_participant data. Expected {}, received {}",
@@ -60,22 +68,23 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 ),
             ));
         }
-        if p2p_data.len() < self.threshold - 1 {
-            return Err(Error::RoundError(
+        if !self.quorum_satisfied(p2p_data.len() + 1, self.limit) {
+            return Err(Error::InsufficientData(
                 Round::Two.into(),
                 format!(
                     "Not enough secret_participant data. Expected {}, received {}",
                     self.threshold,
-                    broadcast_data.len()
+                    p2p_data.len()
                 ),
             ));
         }
 
         self.valid_participant_ids.clear();
+        let dealt_shares = DealtShares::for_round(&self.dealt_shares, self.limit, Round::Two)?;
         let mut secret_share =
-            self.components.secret_shares[self.id - 1].as_field_element::<G::Scalar>()?;
+            dealt_shares.secret_shares[self.id - 1].as_field_element::<G::Scalar>()?;
         let mut blind_share =
-            self.components.blinder_shares[self.id - 1].as_field_element::<G::Scalar>()?;
+            dealt_shares.blinder_shares[self.id - 1].as_field_element::<G::Scalar>()?;
         let mut blind_key = self.components.pedersen_verifier_set.blind_verifiers()[0];
         let og_secret = secret_share;
         let og_blind = blind_share;
@@ -86,15 +95,25 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             .copied()
             .chain(p2p_data.keys().copied())
             .collect::<BTreeSet<usize>>();
+        let context = self.transcript.setup_hash();
+        let mut rejected = BTreeMap::new();
+        let mut pending = Vec::with_capacity(pids.len());
         for pid in &pids {
+            // Data claiming to come from outside the participants is never used
+            if *pid == 0 || *pid > self.limit || *pid == self.id {
+                rejected.insert(*pid, ParticipantError::UnexpectedBroadcast(*pid));
+                continue;
+            }
             // resolve bid != pid where bid might exist or pid might exist in the other
             // probably didn't receive the data, not necessarily malicious
             let opt_bdata = broadcast_data.get(pid);
             if opt_bdata.is_none() {
+                rejected.insert(*pid, ParticipantError::MissingBroadcastData(*pid));
                 continue;
             }
             let opt_p2p_data = p2p_data.get(pid);
             if opt_p2p_data.is_none() {
+                rejected.insert(*pid, ParticipantError::MissingP2PData(*pid));
                 continue;
             }
 
@@ -106,6 +125,7 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                     != self.components.pedersen_verifier_set.secret_generator()
                 || bdata.pedersen_commitments.len() != self.threshold
             {
+                rejected.insert(*pid, ParticipantError::MismatchedParameters(*pid));
                 continue;
             }
 
@@ -114,6 +134,14 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 .iter()
                 .any(|c| c.is_identity().into())
             {
+                rejected.insert(
+                    *pid,
+                    ParticipantError::IdentityElementPedersenCommitments(*pid),
+                );
+                continue;
+            }
+            if !self.check_zero_secret_proof(*pid, &context, bdata) {
+                rejected.insert(*pid, ParticipantError::InvalidZeroSecretProof(*pid));
                 continue;
             }
             let p2p = opt_p2p_data.expect("to unwrap p2p_data");
@@ -122,38 +150,73 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             let p2p_blind_share = &p2p.blind_share; // serde_bare::from_slice::<InnerShare>(&p2p.blind_share)
                                                     // .map_err(|e| Error::RoundError(Round::Two.into(), e.to_string()))?;
             if (p2p_secret_share.is_zero() | p2p_blind_share.is_zero()).into() {
+                rejected.insert(*pid, ParticipantError::ZeroValueShares(*pid));
                 continue;
             }
 
-            let verifier = Vec::<G>::pedersen_set_with_generators_and_verifiers(
-                bdata.message_generator,
-                bdata.blinder_generator,
-                &bdata.pedersen_commitments,
-            );
-
-            if verifier
-                .verify_share_and_blinder(p2p_secret_share, p2p_blind_share)
-                .is_err()
-            {
+            let (Ok(s), Ok(b)) = (
+                p2p_secret_share.as_field_element::<G::Scalar>(),
+                p2p_blind_share.as_field_element::<G::Scalar>(),
+            ) else {
+                rejected.insert(*pid, ParticipantError::BadFormatShare(*pid));
                 continue;
+            };
+            pending.push((*pid, s, b));
+        }
+
+        // Check all shares at once and only check each individually
+        // to find the culprits if that fails
+        let openings = pending
+            .iter()
+            .map(|(pid, s, b)| (broadcast_data[pid].pedersen_commitments.as_slice(), *s, *b))
+            .collect::<Vec<_>>();
+        let id = G::Scalar::from(self.id as u64);
+        let all_valid = utils::batch_verify_pedersen_shares(
+            self.components.pedersen_verifier_set.secret_generator(),
+            self.components.pedersen_verifier_set.blinder_generator(),
+            id,
+            &openings,
+            rand_core::OsRng,
+        );
+        for (pid, s, b) in pending {
+            let bdata = &broadcast_data[&pid];
+            if !all_valid {
+                let p2p = &p2p_data[&pid];
+                let verifier = Vec::<G>::pedersen_set_with_generators_and_verifiers(
+                    bdata.message_generator,
+                    bdata.blinder_generator,
+                    &bdata.pedersen_commitments,
+                );
+                if verifier
+                    .verify_share_and_blinder(&p2p.secret_share, &p2p.blind_share)
+                    .is_err()
+                {
+                    rejected.insert(pid, ParticipantError::NoVerifyShares(pid));
+                    continue;
+                }
             }
-            if let Ok(s) = p2p_secret_share.as_field_element::<G::Scalar>() {
-                secret_share += s;
-                self.valid_participant_ids.insert(*pid);
-            }
-            if let Ok(b) = p2p_blind_share.as_field_element::<G::Scalar>() {
-                blind_share += b;
-            }
+            secret_share += s;
+            blind_share += b;
             blind_key += bdata.pedersen_commitments[0];
+            self.valid_participant_ids.insert(pid);
+        }
+        for pid in &self.valid_participant_ids {
+            self.message_verified(Round::Two, *pid);
+            if let (Some(bdata), Some(p2p)) = (broadcast_data.get(pid), p2p_data.get(pid)) {
+                self.audit(Round::Two, *pid, &(bdata, p2p));
+            }
+        }
+        for reason in rejected.into_values() {
+            self.participant_invalidated(Round::Two, reason);
         }
 
-        if secret_share.is_zero().into() || secret_share == og_secret {
+        if (secret_share.is_zero() | secret_share.ct_eq(&og_secret)).into() {
             return Err(Error::RoundError(
                 Round::Two.into(),
                 "The resulting secret key share is invalid".to_string(),
             ));
         }
-        if blind_share.is_zero().into() || blind_share == og_blind {
+        if (blind_share.is_zero() | blind_share.ct_eq(&og_blind)).into() {
             return Err(Error::RoundError(
                 Round::Two.into(),
                 "The resulting blind key share is invalid".to_string(),
@@ -167,25 +230,103 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             ));
         }
 
-        self.round = Round::Three;
+        if self.force_advance {
+            let absent = (1..=self.limit)
+                .filter(|id| *id != self.id && !pids.contains(id))
+                .collect::<Vec<usize>>();
+            for id in absent {
+                self.transcript.append(Round::Two, id, "absent", &[]);
+                self.absent_participant_ids.insert(id);
+                self.participant_invalidated(Round::Two, ParticipantError::Absent(id));
+            }
+            self.force_advance = false;
+        }
+
+        for id in &self.valid_participant_ids {
+            let (pedersen_commitments, zero_secret) = if *id == self.id {
+                (
+                    self.components.pedersen_verifier_set.blind_verifiers(),
+                    I::proves_zero_secret(),
+                )
+            } else {
+                let bdata = &broadcast_data[id];
+                (
+                    bdata.pedersen_commitments.as_slice(),
+                    bdata.zero_secret_proof.is_some(),
+                )
+            };
+            self.transcript.append_points(
+                Round::One,
+                *id,
+                "pedersen_commitments",
+                pedersen_commitments,
+            );
+            if zero_secret {
+                self.transcript.append(Round::One, *id, "zero_secret", &[]);
+            }
+        }
+
+        if self.open_blinder {
+            // The blinder commitments are opened after round 3 and need the
+            // blinder polynomial, which only the dealt shares determine
+            let mut blinder_shares = dealt_shares
+                .blinder_shares
+                .iter()
+                .take(self.threshold)
+                .map(|s| {
+                    Ok((
+                        G::Scalar::from(s.identifier() as u64),
+                        s.as_field_element::<G::Scalar>()?,
+                    ))
+                })
+                .collect::<DkgResult<Vec<_>>>()?;
+            let blinder_opening = BlinderOpening::prove(
+                self.id,
+                self.components.pedersen_verifier_set.blinder_generator(),
+                self.components.pedersen_verifier_set.blind_verifiers(),
+                self.components.feldman_verifier_set.verifiers(),
+                &blinder_shares,
+            );
+            for (_, value) in blinder_shares.iter_mut() {
+                utils::zeroize_scalar(value);
+            }
+            self.blinder_opening = Some(blinder_opening?);
+        }
+        // Shares for the other participants were sent in round 1
+        // and this participant's shares are now combined so none are needed anymore
+        self.dealt_shares = dealing::empty();
+        self.check_full_participation(Round::Two)?;
+        self.start_round(Round::Three);
         // Include own id in valid set
-        self.round1_p2p_data = p2p_data
-            .iter()
-            .map(|(key, value)| {
-                let val = Arc::new(Mutex::new(
-                    Protected::serde(value).expect("to unwrap protected"),
-                ));
-                (*key, val)
-            })
-            .collect();
-        self.round1_broadcast_data = broadcast_data;
+        self.round1_p2p_data = Slots::from_entries(
+            p2p_data
+                .iter()
+                .filter(|(key, _)| (1..=self.limit).contains(*key))
+                .map(|(key, value)| {
+                    let val = Arc::new(Mutex::new(
+                        Protected::serde(value).expect("to unwrap protected"),
+                    ));
+                    (*key, val)
+                }),
+            self.limit,
+        )?;
+        self.round1_broadcast_data = Slots::from_entries(
+            broadcast_data
+                .into_iter()
+                .filter(|(key, _)| (1..=self.limit).contains(key)),
+            self.limit,
+        )?;
 
-        let echo_data = Round2EchoBroadcastData {
+        #[allow(unused_mut)]
+        let mut echo_data = Round2EchoBroadcastData {
             valid_participant_ids: self.valid_participant_ids.clone(),
         };
         self.secret_share = Arc::new(Mutex::new(Protected::field_element(secret_share)));
         self.blind_share = Arc::new(Mutex::new(Protected::field_element(blind_share)));
         self.blind_key = blind_key;
+        #[cfg(feature = "testing")]
+        self.faults.iter().for_each(|f| f.round2(&mut echo_data));
+        timer.stop();
 
         Ok(echo_data)
     }
@@ -9,32 +9,39 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     ///
     /// If all reported ids match this participant's expectations
     /// the round will succeed and continue to the next round.
+    /// Echoed sets with ids outside `1..=limit` are always rejected.
+    /// With [`ReconciliationPolicy::Intersection`] the participants missing
+    /// from any reported set are dropped instead.
     ///
     /// Throws an error if this participant is not in round 3.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(participant = self.id, round = 3))
+    )]
     pub fn round3(
         &mut self,
         echo_data: &BTreeMap<usize, Round2EchoBroadcastData>,
     ) -> DkgResult<Round3BroadcastData<G>> {
         if !matches!(self.round, Round::Three) {
-            return Err(Error::RoundError(
-                Round::Three.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
+            return Err(self.invalid_round(Round::Three));
         }
+        self.notify(|o| o.on_round_started(self.id, Round::Three));
+        let timer = telemetry::RoundTimer::start(Round::Three);
 
         if echo_data.is_empty() {
-            return Err(Error::RoundError(
+            return Err(Error::InsufficientData(
                 Round::Three.into(),
                 "Missing broadcast data from other participants. Echo data is empty".to_string(),
             ));
         }
-        if echo_data.len() < self.threshold {
-            return Err(Error::RoundError(
+        if !self.quorum_satisfied(echo_data.len(), self.valid_participant_ids.len()) {
+            return Err(Error::InsufficientData(
                 Round::Three.into(),
                 "Missing broadcast data from other participants. Non-sufficient echo data provided.".to_string(),
             ));
         }
 
+        let mut uncommon = BTreeSet::new();
         for (id, echo) in echo_data {
             if self.id == *id {
                 continue;
@@ -42,27 +49,117 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             if !self.valid_participant_ids.contains(id) {
                 continue;
             }
-            if self
+            if echo
                 .valid_participant_ids
-                .difference(&echo.valid_participant_ids)
-                .count()
-                != 0
+                .iter()
+                .any(|v| *v == 0 || *v > self.limit)
             {
-                return Err(Error::RoundError(
-                    Round::Three.into(),
-                    format!(
-                        "Received data from malicious secret_participant {}. Valid sets don't match.",
-                        *id
-                    ),
-                ));
+                let error = ParticipantError::MismatchedValidParticipants(*id);
+                self.participant_invalidated(Round::Three, error);
+                return Err(Error::SenderError {
+                    round: Round::Three.into(),
+                    error,
+                });
             }
+            // Participants rehabilitated after round 2 are missing from the echoed sets,
+            // including this participant if its data was late
+            let mut missing = self
+                .valid_participant_ids
+                .difference(&echo.valid_participant_ids)
+                .filter(|id| {
+                    !self.rehabilitated_ids.contains(id)
+                        && (self.grace_period == 0 || **id != self.id)
+                })
+                .peekable();
+            if missing.peek().is_some() {
+                if self.reconciliation_policy == ReconciliationPolicy::Intersection {
+                    uncommon.extend(missing);
+                    self.message_verified(Round::Three, *id);
+                    self.audit(Round::Three, *id, echo);
+                    continue;
+                }
+                let error = ParticipantError::MismatchedValidParticipants(*id);
+                self.participant_invalidated(Round::Three, error);
+                return Err(Error::SenderError {
+                    round: Round::Three.into(),
+                    error,
+                });
+            }
+            self.message_verified(Round::Three, *id);
+            self.audit(Round::Three, *id, echo);
+        }
+        if uncommon.contains(&self.id) {
+            return Err(Error::RoundError(
+                Round::Three.into(),
+                "This participant is not in the common valid set".to_string(),
+            ));
+        }
+        for id in uncommon {
+            self.remove_dealer(id)?;
+            self.participant_invalidated(Round::Three, ParticipantError::NotCommonlyValid(id));
+        }
+        let received = echo_data.keys().copied().collect::<BTreeSet<usize>>();
+        self.remove_absent_participants(&received)?;
+        if self.valid_participant_ids.len() < self.threshold {
+            return Err(Error::RoundError(
+                Round::Three.into(),
+                "Not enough valid participants to continue".to_string(),
+            ));
         }
 
-        let round3_bdata = Round3BroadcastData {
+        #[allow(unused_mut)]
+        let mut round3_bdata = Round3BroadcastData {
             commitments: self.components.feldman_verifier_set.verifiers().to_vec(),
         };
-        self.round = Round::Four;
+        let valid_participant_ids = self
+            .valid_participant_ids
+            .iter()
+            .flat_map(|id| (*id as u64).to_be_bytes())
+            .collect::<Vec<u8>>();
+        self.transcript.append(
+            Round::Two,
+            0,
+            "valid_participant_ids",
+            &valid_participant_ids,
+        );
+        self.check_full_participation(Round::Three)?;
+        self.start_round(Round::Four);
+        #[cfg(feature = "testing")]
+        self.faults.iter().for_each(|f| f.round3(&mut round3_bdata));
+        timer.stop();
 
         Ok(round3_bdata)
     }
+
+    /// Remove the contribution of dealer `id` from the shares combined in round 2
+    pub(super) fn remove_dealer(&mut self, id: usize) -> DkgResult<()> {
+        let value = self.round1_p2p_data.get(&id).ok_or_else(|| {
+            Error::RoundError(self.round.into(), format!("missing shares from {}", id))
+        })?;
+        let p2p = {
+            let mut protected = value
+                .lock()
+                .map_err(|_e| Error::RoundError(self.round.into(), "unable to lock".to_string()))?;
+            let unprotected = protected.unprotect().ok_or_else(|| {
+                Error::RoundError(self.round.into(), "invalid secret unprotected".to_string())
+            })?;
+            unprotected
+                .serde::<Round1P2PData>()
+                .map_err(|e| Error::SerializationError(e.to_string()))?
+        };
+        let secret_share = self.computed_secret_share().ok_or_else(|| {
+            Error::RoundError(self.round.into(), "invalid secret share".to_string())
+        })? - p2p.secret_share.as_field_element::<G::Scalar>()?;
+        let blind_share = self.get_blind_share().ok_or_else(|| {
+            Error::RoundError(self.round.into(), "invalid blind share".to_string())
+        })? - p2p.blind_share.as_field_element::<G::Scalar>()?;
+        self.secret_share = Arc::new(Mutex::new(Protected::field_element(secret_share)));
+        self.blind_share = Arc::new(Mutex::new(Protected::field_element(blind_share)));
+        self.blind_key -= self
+            .round1_broadcast_data
+            .require(&id, self.round)?
+            .pedersen_commitments[0];
+        self.valid_participant_ids.remove(&id);
+        Ok(())
+    }
 }
@@ -11,27 +11,36 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
     ///
     /// The public key should be echo broadcast to all valid participants to be checked.
     ///
+    /// Dealers whose shares passed round 2 but don't match their feldman commitments
+    /// are disqualified and listed by [`Participant::get_disqualified_dealer_ids`].
+    /// Their contribution is still part of the secret shares so the public key
+    /// returned here must not be sent, instead run [`Participant::recovery_shares`]
+    /// and [`Participant::recover_disqualified_dealers`] to include it.
+    ///
     /// Throws an error if this participant is not in round 4.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(participant = self.id, round = 4))
+    )]
     pub fn round4(
         &mut self,
         broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
     ) -> DkgResult<Round4EchoBroadcastData<G>> {
         if !matches!(self.round, Round::Four) {
-            return Err(Error::RoundError(
-                Round::Four.into(),
-                format!("Invalid Round, use round{}", self.round),
-            ));
+            return Err(self.invalid_round(Round::Four));
         }
+        self.notify(|o| o.on_round_started(self.id, Round::Four));
+        let timer = telemetry::RoundTimer::start(Round::Four);
 
         if broadcast_data.is_empty() {
-            return Err(Error::RoundError(
+            return Err(Error::InsufficientData(
                 Round::Four.into(),
                 "Missing broadcast data from other participants. Broadcast data is empty"
                     .to_string(),
             ));
         }
-        if broadcast_data.len() < self.threshold {
-            return Err(Error::RoundError(
+        if !self.quorum_satisfied(broadcast_data.len(), self.valid_participant_ids.len()) {
+            return Err(Error::InsufficientData(
                 Round::Four.into(),
                 "Missing broadcast data from other participants. Non-sufficient data provided."
                     .to_string(),
@@ -39,6 +48,8 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
         }
 
         self.public_key = self.components.feldman_verifier_set.verifiers()[0];
+        let checked_ids = self.valid_participant_ids.clone();
+        let mut rejected = BTreeMap::new();
 
         for (id, bdata) in broadcast_data {
             if self.id == *id {
@@ -52,6 +63,7 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 // Round 2 removed all invalid participants
                 // Round 3 sent echo broadcast to double-check valid participants
                 self.valid_participant_ids.remove(id);
+                rejected.insert(*id, ParticipantError::MissingP2PDataRound1(*id));
                 continue;
             }
             if !self.round1_broadcast_data.contains_key(id) {
@@ -59,6 +71,7 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
                 // Round 2 removed all invalid participants
                 // Round 3 sent echo broadcast to double-check valid participants
                 self.valid_participant_ids.remove(id);
+                rejected.insert(*id, ParticipantError::MissingBroadcastDataRound1(*id));
                 continue;
             }
             if bdata
@@ -69,13 +82,28 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             // || !I::check_feldman_verifier(bdata.commitments[0])
             {
                 self.valid_participant_ids.remove(id);
+                self.disqualified_dealer_ids.insert(*id);
+                rejected.insert(
+                    *id,
+                    ParticipantError::IdentityElementFeldmanCommitments(*id),
+                );
                 continue;
             }
+            if let Some(import) = &self.key_import {
+                if !import.check_commitment(*id, bdata.commitments[0]) {
+                    let error = ParticipantError::MismatchedImportedKey(*id);
+                    self.message_rejected(Round::Four, error);
+                    return Err(Error::SenderError {
+                        round: Round::Four.into(),
+                        error,
+                    });
+                }
+            }
             let verifier = Vec::<G>::feldman_set_with_generator_and_verifiers(
                 self.components.feldman_verifier_set.generator(),
                 &bdata.commitments,
             );
-            let value = &self.round1_p2p_data[id];
+            let value = self.round1_p2p_data.require(id, Round::Four)?;
             let mut protected_share = value.deref().lock().map_err(|_e| {
                 Error::RoundError(Round::Four.into(), "unable to lock".to_string())
             })?;
@@ -90,6 +118,8 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             //     .map_err(|e| Error::RoundError(Round::Four.into(), e.to_string()))?;
             if verifier.verify_share(p2p_secret_share).is_err() {
                 self.valid_participant_ids.remove(id);
+                self.disqualified_dealer_ids.insert(*id);
+                rejected.insert(*id, ParticipantError::NoVerifyShares(*id));
                 continue;
             }
 
@@ -103,7 +133,9 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             self.public_key += bdata.commitments[0];
 
             // Double-check the blinder shares in case the user wants to use them
-            let blinder_verifiers = self.round1_broadcast_data[id]
+            let blinder_verifiers = self
+                .round1_broadcast_data
+                .require(id, Round::Four)?
                 .pedersen_commitments
                 .iter()
                 .zip(bdata.commitments.iter())
@@ -119,14 +151,77 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
 
             if verifier.verify_share(p2p_blind_share).is_err() {
                 self.valid_participant_ids.remove(id);
+                rejected.insert(*id, ParticipantError::NoVerifyShares(*id));
                 continue;
             }
         }
+        let my_id = self.id;
+        for id in checked_ids.iter().filter(|id| **id != my_id) {
+            if let Some(reason) = rejected.remove(id) {
+                self.participant_invalidated(Round::Four, reason);
+            } else if let Some(bdata) = broadcast_data.get(id) {
+                self.message_verified(Round::Four, *id);
+                self.audit(Round::Four, *id, bdata);
+            }
+        }
+        let received = broadcast_data.keys().copied().collect::<BTreeSet<usize>>();
+        self.remove_absent_participants(&received)?;
+        if self.valid_participant_ids.len() < self.threshold {
+            return Err(Error::RoundError(
+                Round::Four.into(),
+                "Not enough valid participants to continue".to_string(),
+            ));
+        }
+        if let Some(import) = &self.key_import {
+            // Recovering the key owner would reveal the key to everyone
+            if !self.valid_participant_ids.contains(&import.owner)
+                || self.public_key != import.public_key
+            {
+                return Err(Error::RoundError(
+                    Round::Four.into(),
+                    "The computed public key does not match the imported key".to_string(),
+                ));
+            }
+        }
+        for id in &self.valid_participant_ids {
+            let commitments = if *id == self.id {
+                self.components.feldman_verifier_set.verifiers()
+            } else if let Some(bdata) = broadcast_data.get(id) {
+                bdata.commitments.as_slice()
+            } else {
+                continue;
+            };
+            self.transcript
+                .append_points(Round::Three, *id, "feldman_commitments", commitments);
+        }
+        self.transcript
+            .append_points(Round::Four, 0, "public_key", &[self.public_key]);
         self.blind_key -= self.public_key;
-        self.round = Round::Five;
-
-        Ok(Round4EchoBroadcastData {
+        // The round 1 data is only needed to verify the commitments
+        // which is done, dropping it bounds memory for large committees
+        // and removes the other participants' shares.
+        // Only the data from disqualified dealers is kept for their recovery
+        let mut round1_p2p_data = Slots::default();
+        let mut round1_broadcast_data = Slots::default();
+        for id in &self.disqualified_dealer_ids {
+            let p2p = self.round1_p2p_data.require(id, Round::Four)?;
+            round1_p2p_data.insert(*id, self.limit, p2p.clone())?;
+            let bdata = self.round1_broadcast_data.require(id, Round::Four)?;
+            round1_broadcast_data.insert(*id, self.limit, bdata.clone())?;
+        }
+        self.round1_p2p_data = round1_p2p_data;
+        self.round1_broadcast_data = round1_broadcast_data;
+        self.check_full_participation(Round::Four)?;
+        self.start_round(Round::Five);
+        self.finished_at = now();
+        #[allow(unused_mut)]
+        let mut echo_data = Round4EchoBroadcastData {
             public_key: self.public_key,
-        })
+        };
+        #[cfg(feature = "testing")]
+        self.faults.iter().for_each(|f| f.round4(&mut echo_data));
+        timer.stop();
+
+        Ok(echo_data)
     }
 }
@@ -3,28 +3,38 @@ use super::*;
 impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Participant<I, G> {
     /// Computes round 5 for this participant.
     ///
-    /// Checks if all participants computed the same public key.
+    /// Checks if all participants computed the same public key
+    /// and marks this participant as complete.
     ///
     /// Throws an error if this participant is not in round 5.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, err, fields(participant = self.id, round = 5))
+    )]
     pub fn round5(
-        &self,
+        &mut self,
         broadcast_data: &BTreeMap<usize, Round4EchoBroadcastData<G>>,
     ) -> DkgResult<()> {
         if !matches!(self.round, Round::Five) {
+            return Err(self.invalid_round(Round::Five));
+        }
+        if !self.disqualified_dealer_ids.is_empty() {
             return Err(Error::RoundError(
                 Round::Five.into(),
-                format!("Invalid Round, use round{}", self.round),
+                "Disqualified dealers must be recovered first".to_string(),
             ));
         }
+        self.notify(|o| o.on_round_started(self.id, Round::Five));
+        let timer = telemetry::RoundTimer::start(Round::Five);
         if broadcast_data.is_empty() {
-            return Err(Error::RoundError(
+            return Err(Error::InsufficientData(
                 Round::Five.into(),
                 "Missing broadcast data from other participants. Broadcast data is empty"
                     .to_string(),
             ));
         }
-        if broadcast_data.len() < self.threshold {
-            return Err(Error::RoundError(
+        if !self.quorum_satisfied(broadcast_data.len(), self.valid_participant_ids.len()) {
+            return Err(Error::InsufficientData(
                 Round::Five.into(),
                 "Missing broadcast data from other participants. Non-sufficient data provided."
                     .to_string(),
@@ -38,31 +48,46 @@ impl<I: ParticipantImpl<G> + Default, G: Group + GroupEncoding + Default> Partic
             if !self.valid_participant_ids.contains(id) {
                 continue;
             }
-            if !self.round1_p2p_data.contains_key(id) {
-                // How would this happen?
-                // Round 2 removed all invalid participants
-                // Round 3 sent echo broadcast to double check valid participants
-                // Round 4 also removed all invalid participants
-                continue;
-            }
-            if !self.round1_broadcast_data.contains_key(id) {
-                // How would this happen?
-                // Round 2 removed all invalid participants
-                // Round 3 sent echo broadcast to double check valid participants
-                // Round 4 also removed all invalid participants
-                continue;
-            }
             if bdata.public_key != self.public_key {
-                return Err(Error::RoundError(
-                    Round::Five.into(),
-                    format!(
-                        "Public key from secret_participant {} does not match.  Expected {:?}, found {:?}",
-                        id, self.public_key, bdata.public_key
-                    ),
-                ));
+                let error = ParticipantError::MismatchedPublicKey(*id);
+                self.message_rejected(Round::Five, error);
+                return Err(Error::SenderError {
+                    round: Round::Five.into(),
+                    error,
+                });
             }
+            self.message_verified(Round::Five, *id);
+            self.audit(Round::Five, *id, bdata);
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::info!(participant = self.id, "protocol complete");
+        self.confirmed = true;
+        self.notify(|o| o.on_complete(self.id, self.public_key.to_bytes().as_ref()));
+        timer.stop();
         Ok(())
     }
+
+    /// Computes round 5 for this participant and signs the transcript
+    /// hash and public key with the secret share.
+    ///
+    /// The signatures from all participants are combined with
+    /// [`DkgCertificate::aggregate`].
+    pub fn round5_signed(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round4EchoBroadcastData<G>>,
+    ) -> DkgResult<CertificateSignature<G>> {
+        self.round5(broadcast_data)?;
+        let secret_share = self.computed_secret_share().ok_or_else(|| {
+            Error::RoundError(Round::Five.into(), "invalid secret share".to_string())
+        })?;
+        CertificateSignature::sign(
+            self.id,
+            self.threshold,
+            self.components.pedersen_verifier_set.secret_generator(),
+            self.public_key,
+            self.get_transcript_hash(),
+            secret_share,
+        )
+    }
 }
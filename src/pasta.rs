@@ -0,0 +1,22 @@
+//! Run the DKG on the Pallas and Vesta curves.
+//!
+//! The participants are generic over any group so the points from
+//! `pasta_curves` are used directly, this module re-exports the crate
+//! and names the participants for each curve.
+pub use pasta_curves;
+
+use crate::*;
+use pasta_curves::{pallas, vesta};
+
+/// A participant generating a new key on Pallas
+pub type PallasSecretParticipant = SecretParticipant<pallas::Point>;
+/// A participant refreshing a key on Pallas
+pub type PallasRefreshParticipant = RefreshParticipant<pallas::Point>;
+/// The parameters for a ceremony on Pallas
+pub type PallasParameters = Parameters<pallas::Point>;
+/// A participant generating a new key on Vesta
+pub type VestaSecretParticipant = SecretParticipant<vesta::Point>;
+/// A participant refreshing a key on Vesta
+pub type VestaRefreshParticipant = RefreshParticipant<vesta::Point>;
+/// The parameters for a ceremony on Vesta
+pub type VestaParameters = Parameters<vesta::Point>;
@@ -1,15 +1,17 @@
-use crate::{deserialize_g_vec, deserialize_scalar, serialize_g_vec, serialize_scalar};
+use crate::{deserialize_g_vec, deserialize_scalar, serialize_g_vec, serialize_scalar, utils};
 use serde::{Deserialize, Serialize};
 use vsss_rs::{
     elliptic_curve::{group::GroupEncoding, Group},
     *,
 };
+use zeroize::Zeroize;
 
 /// The inner representation of the secret shares
 pub type InnerShare = Vec<u8>;
 
 /// The pedersen result used by the DKG
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "unsafe_debug", derive(Debug))]
 pub struct GennaroDkgPedersenResult<G: Group + GroupEncoding + Default> {
     /// The blinder used to blind the secret shares
     #[serde(
@@ -92,3 +94,26 @@ impl<G: Group + GroupEncoding + Default> From<StdPedersenResult<G, u8, InnerShar
         }
     }
 }
+
+/// The result isn't wiped on drop so its fields can still be moved out,
+/// [`Participant`](crate::Participant) wipes the result it holds when dropped.
+impl<G: Group + GroupEncoding + Default> Zeroize for GennaroDkgPedersenResult<G> {
+    fn zeroize(&mut self) {
+        utils::zeroize_scalar(&mut self.blinder);
+        self.secret_shares.zeroize();
+        self.blinder_shares.zeroize();
+    }
+}
+
+#[cfg(not(feature = "unsafe_debug"))]
+impl<G: Group + GroupEncoding + Default> std::fmt::Debug for GennaroDkgPedersenResult<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GennaroDkgPedersenResult")
+            .field("blinder", &"<redacted>")
+            .field("secret_shares", &self.secret_shares.len())
+            .field("blinder_shares", &self.blinder_shares.len())
+            .field("feldman_verifier_set", &self.feldman_verifier_set)
+            .field("pedersen_verifier_set", &self.pedersen_verifier_set)
+            .finish()
+    }
+}
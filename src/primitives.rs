@@ -0,0 +1,93 @@
+//! Schnorr proofs of knowledge and Lagrange interpolation shared by the
+//! certificates, export approvals and the proofs sent during the ceremony.
+use super::*;
+use std::iter::Sum;
+use std::ops::Mul;
+use vsss_rs::elliptic_curve::ff::Field;
+
+/// Prove knowledge of `secret` with `generator * secret`.
+///
+/// Returns the nonce commitment `r` and the response `s = k + c * secret`
+/// where `c` is computed from `r` by `challenge`.
+pub(crate) fn prove<G, C>(
+    generator: G,
+    secret: G::Scalar,
+    challenge: C,
+) -> DkgResult<(G, G::Scalar)>
+where
+    G: Group + GroupEncoding + Default,
+    C: FnOnce(G) -> DkgResult<G::Scalar>,
+{
+    let mut k = G::Scalar::random(rand_core::OsRng);
+    let r = generator * k;
+    let s = challenge(r).map(|c| k + c * secret);
+    utils::zeroize_scalar(&mut k);
+    Ok((r, s?))
+}
+
+/// Check `generator * s == r + public * c` where `c` is computed from `r`
+/// by `challenge`. An identity `public` or `r` is rejected.
+pub(crate) fn verify<G, C>(generator: G, public: G, r: G, s: G::Scalar, challenge: C) -> bool
+where
+    G: Group + GroupEncoding + Default,
+    C: FnOnce(G) -> DkgResult<G::Scalar>,
+{
+    if (public.is_identity() | r.is_identity()).into() {
+        return false;
+    }
+    match challenge(r) {
+        Ok(c) => generator * s == r + public * c,
+        Err(_) => false,
+    }
+}
+
+/// Hash the `ids`, `points` and `data` into a challenge with `dst`
+/// as the domain separation tag
+pub(crate) fn challenge<G: Group + GroupEncoding + Default>(
+    dst: &[u8],
+    ids: &[usize],
+    points: &[G],
+    data: &[u8],
+) -> DkgResult<G::Scalar> {
+    let mut msg = Vec::new();
+    for id in ids {
+        msg.extend_from_slice(&(*id as u64).to_be_bytes());
+    }
+    for point in points {
+        msg.extend_from_slice(point.to_bytes().as_ref());
+    }
+    msg.extend_from_slice(data);
+    utils::hash_to_scalar(dst, &msg)
+}
+
+/// The Lagrange coefficient of `xs[i]` to interpolate at `x`.
+///
+/// Returns the index of another identifier equal to `xs[i]` if there is one.
+pub(crate) fn lagrange_basis<F: PrimeField>(xs: &[F], i: usize, x: F) -> Result<F, usize> {
+    let mut basis = F::ONE;
+    for (j, x_j) in xs.iter().enumerate() {
+        if j == i {
+            continue;
+        }
+        let denominator = Option::<F>::from((xs[i] - *x_j).invert()).ok_or(j)?;
+        basis *= (x - *x_j) * denominator;
+    }
+    Ok(basis)
+}
+
+/// Evaluate the polynomial through `points` at `x`, the values are
+/// either scalars or the points they were multiplied with.
+///
+/// Returns the index of a duplicate identifier if there is one.
+pub(crate) fn interpolate<F, Y>(points: &[(F, Y)], x: F) -> Result<Y, usize>
+where
+    F: PrimeField,
+    Y: Copy + Mul<F, Output = Y> + Sum,
+{
+    let xs = points.iter().map(|(x_i, _)| *x_i).collect::<Vec<_>>();
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (_, y))| Ok(*y * lagrange_basis(&xs, i, x)?))
+        .sum()
+}
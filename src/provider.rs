@@ -0,0 +1,30 @@
+use super::*;
+
+/// Supplies the dealing values of a participant and stores its resulting
+/// shares outside of this crate, for example in an HSM or a cloud KMS.
+///
+/// The provider only decides where the secrets come from and where the
+/// resulting shares go, it does not keep them out of this process.
+/// The values returned by [`SecretProvider::dealing_secret`] and
+/// [`SecretProvider::dealing_blinder`] are exported to the DKG which
+/// evaluates the polynomials and combines the received shares in process,
+/// so a key that must never leave an HSM can't be used as a dealing secret.
+/// The values are held in protected memory while the rounds run and
+/// zeroized once they are no longer needed. Only the commitments, the
+/// public key and the stored shares leave the participant, the latter
+/// only through [`SecretProvider::store_shares`].
+pub trait SecretProvider<G: Group + GroupEncoding + Default> {
+    /// Generate or load the secret dealt by participant `id`
+    fn dealing_secret(&mut self, id: usize) -> DkgResult<G::Scalar>;
+    /// Generate or load the blinder used by participant `id`
+    fn dealing_blinder(&mut self, id: usize) -> DkgResult<G::Scalar>;
+    /// Store the secret and blind shares computed by participant `id`
+    /// for the group `public_key`
+    fn store_shares(
+        &mut self,
+        id: usize,
+        secret_share: G::Scalar,
+        blind_share: G::Scalar,
+        public_key: G,
+    ) -> DkgResult<()>;
+}
@@ -0,0 +1,183 @@
+use super::*;
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeMap;
+
+/// A complete ceremony between honest participants whose randomness is
+/// derived from a seed, kept as a known answer.
+///
+/// Replaying the ceremony with [`CeremonyRecord::verify`] after upgrading
+/// dependencies detects any change in the arithmetic or encodings since
+/// the record was generated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CeremonyRecord<G: Group + GroupEncoding + Default> {
+    /// The parameters of the ceremony
+    #[serde(bound(serialize = "Parameters<G>: Serialize"))]
+    #[serde(bound(deserialize = "Parameters<G>: Deserialize<'de>"))]
+    pub parameters: Parameters<G>,
+    /// The seed of each participant's randomness
+    pub seed: [u8; 32],
+    /// All broadcast data keyed by sender
+    #[serde(bound(serialize = "BroadcastArchive<G>: Serialize"))]
+    #[serde(bound(deserialize = "BroadcastArchive<G>: Deserialize<'de>"))]
+    pub broadcast: BroadcastArchive<G>,
+    /// Round 1 peer data keyed by sender then recipient
+    pub round1_p2p: BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+    /// The final output keyed by participant
+    #[serde(bound(serialize = "CeremonyOutput<G>: Serialize"))]
+    #[serde(bound(deserialize = "CeremonyOutput<G>: Deserialize<'de>"))]
+    pub outputs: BTreeMap<usize, CeremonyOutput<G>>,
+}
+
+/// The final output of a participant in a [`CeremonyRecord`]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CeremonyOutput<G: Group + GroupEncoding + Default> {
+    /// The computed public key
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub public_key: G,
+    /// The computed secret share
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub secret_share: G::Scalar,
+    /// The transcript hash
+    pub transcript_hash: [u8; 32],
+}
+
+impl<G: Group + GroupEncoding + Default> fmt::Debug for CeremonyOutput<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CeremonyOutput")
+            .field("public_key", &self.public_key)
+            .field("secret_share", &"<redacted>")
+            .field("transcript_hash", &self.transcript_hash)
+            .finish()
+    }
+}
+
+impl<G: Group + GroupEncoding + Default> CeremonyRecord<G> {
+    /// Run and record a ceremony between `parameters.limit` participants
+    pub fn generate(parameters: Parameters<G>, seed: [u8; 32]) -> DkgResult<Self> {
+        let mut participants = (1..=parameters.limit)
+            .map(|id| {
+                let mut rng = ChaCha8Rng::from_seed(seed);
+                rng.set_stream(id as u64);
+                SecretParticipant::<G>::with_rng(
+                    NonZeroUsize::new(id).expect("non zero"),
+                    parameters,
+                    rng,
+                )
+            })
+            .collect::<DkgResult<Vec<_>>>()?;
+
+        let mut broadcast = BroadcastArchive::default();
+        let mut round1_p2p = BTreeMap::new();
+        for p in participants.iter_mut() {
+            let (bdata, p2p) = p.round1()?;
+            broadcast.round1_broadcast.insert(p.get_id(), bdata);
+            round1_p2p.insert(p.get_id(), p2p);
+        }
+        for p in participants.iter_mut() {
+            let id = p.get_id();
+            let bdata = broadcast
+                .round1_broadcast
+                .iter()
+                .filter(|(sender, _)| **sender != id)
+                .map(|(sender, b)| (*sender, b.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let p2p = round1_p2p
+                .iter()
+                .filter(|(sender, _)| **sender != id)
+                .map(|(sender, p2p)| (*sender, p2p[&id].clone()))
+                .collect::<BTreeMap<_, _>>();
+            let echo = p.round2(bdata, p2p)?;
+            broadcast.round2_echo.insert(id, echo);
+        }
+        for p in participants.iter_mut() {
+            let bdata = p.round3(&broadcast.round2_echo)?;
+            broadcast.round3_broadcast.insert(p.get_id(), bdata);
+        }
+        for p in participants.iter_mut() {
+            let echo = p.round4(&broadcast.round3_broadcast)?;
+            broadcast.round4_echo.insert(p.get_id(), echo);
+        }
+        let mut outputs = BTreeMap::new();
+        for p in participants.iter_mut() {
+            p.round5(&broadcast.round4_echo)?;
+            let (public_key, secret_share) = p
+                .get_public_key()
+                .zip(p.get_secret_share())
+                .ok_or_else(|| {
+                    Error::RoundError(Round::Five.into(), "missing output".to_string())
+                })?;
+            outputs.insert(
+                p.get_id(),
+                CeremonyOutput {
+                    public_key,
+                    secret_share,
+                    transcript_hash: p.get_transcript_hash(),
+                },
+            );
+        }
+        Ok(Self {
+            parameters,
+            seed,
+            broadcast,
+            round1_p2p,
+            outputs,
+        })
+    }
+
+    /// Replay the ceremony and check every message and output
+    /// matches this record.
+    ///
+    /// Throws an error naming the first round that differs.
+    pub fn verify(&self) -> DkgResult<()> {
+        let replay = Self::generate(self.parameters, self.seed)?;
+        let rounds = [
+            (
+                Round::One,
+                to_bytes(&self.broadcast.round1_broadcast)?
+                    != to_bytes(&replay.broadcast.round1_broadcast)?
+                    || to_bytes(&self.round1_p2p)? != to_bytes(&replay.round1_p2p)?,
+            ),
+            (
+                Round::Two,
+                to_bytes(&self.broadcast.round2_echo)? != to_bytes(&replay.broadcast.round2_echo)?,
+            ),
+            (
+                Round::Three,
+                to_bytes(&self.broadcast.round3_broadcast)?
+                    != to_bytes(&replay.broadcast.round3_broadcast)?,
+            ),
+            (
+                Round::Four,
+                to_bytes(&self.broadcast.round4_echo)? != to_bytes(&replay.broadcast.round4_echo)?,
+            ),
+            (
+                Round::Five,
+                to_bytes(&self.outputs)? != to_bytes(&replay.outputs)?,
+            ),
+        ];
+        match rounds.iter().find(|(_, differs)| *differs) {
+            Some((round, _)) => Err(Error::RoundError(
+                (*round).into(),
+                "the replayed ceremony does not match the record".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Encode this record as a portable artifact
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        to_bytes(self)
+    }
+
+    /// Decode a record from an artifact produced by [`CeremonyRecord::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        serde_bare::from_slice(bytes).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
+
+fn to_bytes<T: Serialize + ?Sized>(value: &T) -> DkgResult<Vec<u8>> {
+    serde_bare::to_vec(value).map_err(|e| Error::SerializationError(e.to_string()))
+}
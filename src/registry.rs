@@ -0,0 +1,106 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// The external identity of a participant
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantIdentity {
+    /// A human readable name
+    pub label: String,
+    /// Where the participant can be reached on the network
+    #[serde(default)]
+    pub address: Option<String>,
+    /// The key the participant signs its messages with
+    #[serde(default)]
+    pub verifying_key: Option<Vec<u8>>,
+    /// Who to contact about the participant
+    #[serde(default)]
+    pub contact: Option<String>,
+}
+
+impl Display for ParticipantIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)?;
+        if let Some(address) = &self.address {
+            write!(f, " at {}", address)?;
+        }
+        if let Some(contact) = &self.contact {
+            write!(f, ", contact {}", contact)?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps participant ids to their external identities.
+///
+/// It is carried alongside the [`Parameters`] so errors and reports
+/// can name the participant behind an id.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantRegistry {
+    identities: BTreeMap<usize, ParticipantIdentity>,
+}
+
+impl ParticipantRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the identity of participant `id`
+    pub fn with_identity(mut self, id: usize, identity: ParticipantIdentity) -> Self {
+        self.insert(id, identity);
+        self
+    }
+
+    /// Add the identity of participant `id`, returning the identity
+    /// previously registered for it if any
+    pub fn insert(
+        &mut self,
+        id: usize,
+        identity: ParticipantIdentity,
+    ) -> Option<ParticipantIdentity> {
+        self.identities.insert(id, identity)
+    }
+
+    /// The identity of participant `id`
+    pub fn get(&self, id: usize) -> Option<&ParticipantIdentity> {
+        self.identities.get(&id)
+    }
+
+    /// Iterate over the registered identities in id order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &ParticipantIdentity)> {
+        self.identities.iter().map(|(id, identity)| (*id, identity))
+    }
+
+    /// Check every id is a participant allowed by `parameters`
+    pub fn validate<G: Group + GroupEncoding + Default>(
+        &self,
+        parameters: &Parameters<G>,
+    ) -> DkgResult<()> {
+        match self
+            .identities
+            .keys()
+            .find(|id| **id == 0 || **id > parameters.limit)
+        {
+            Some(id) => Err(Error::InvalidParameters(format!(
+                "registered id {} is not between 1 and {}",
+                id, parameters.limit
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The identity of the participant that caused `error`, see [`Error::sender`]
+    pub fn blame(&self, error: &Error) -> Option<&ParticipantIdentity> {
+        self.get(error.sender()?)
+    }
+
+    /// Describe `error` including the identity of the participant that caused it
+    pub fn describe(&self, error: &Error) -> String {
+        match (error.sender(), self.blame(error)) {
+            (Some(id), Some(identity)) => {
+                format!("{} (participant {} is {})", error, id, identity)
+            }
+            _ => error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A summary of a completed ceremony from the view of one participant
+/// for operations teams and compliance archives.
+///
+/// Returned by [`DkgOutput::report`], it contains no secrets.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DkgReport {
+    /// The participant that produced the report
+    pub participant: usize,
+    /// The threshold
+    pub threshold: usize,
+    /// The number of participants the ceremony started with
+    pub limit: usize,
+    /// The participants whose shares make up the key
+    pub participants: BTreeSet<usize>,
+    /// The participants dropped during the ceremony
+    pub dropped: Vec<DroppedParticipant>,
+    /// The dealers whose secrets were reconstructed by the others
+    pub disqualified_dealers: BTreeSet<usize>,
+    /// The dealers whose secrets make up the key with the hex encoded
+    /// SHA-256 hash of their round 1 Pedersen commitments
+    #[serde(default)]
+    pub contributions: BTreeMap<usize, String>,
+    /// The hex encoded SHA-256 hash of the public key
+    pub public_key_fingerprint: String,
+    /// The hex encoded SHA-256 hash of the public share for each of
+    /// this party's shares, keyed by share identifier
+    pub public_share_fingerprints: BTreeMap<usize, String>,
+    /// The hex encoded transcript hash
+    pub transcript_hash: String,
+    /// When round 1 was computed in milliseconds since the unix epoch
+    pub started_at: Option<u64>,
+    /// When round 4 was computed in milliseconds since the unix epoch
+    pub finished_at: Option<u64>,
+}
+
+/// A participant dropped during the ceremony
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DroppedParticipant {
+    /// The id of the dropped participant
+    pub id: usize,
+    /// The round in which it was dropped
+    pub round: Round,
+    /// Why it was dropped
+    pub reason: String,
+    /// The external identity of the dropped participant if registered
+    #[serde(default)]
+    pub identity: Option<ParticipantIdentity>,
+}
+
+pub(crate) fn fingerprint<G: GroupEncoding>(point: &G) -> String {
+    digest(point.to_bytes().as_ref())
+}
+
+pub(crate) fn digest(data: &[u8]) -> String {
+    data_encoding::HEXLOWER.encode(&Sha256::digest(data))
+}
+
+pub(crate) fn now() -> Option<u64> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as u64)
+}
@@ -0,0 +1,573 @@
+use super::*;
+use std::collections::BTreeMap;
+
+/// Data sent by another participant for a specific session
+#[derive(Clone, Debug)]
+pub struct Envelope<K, G: Group + GroupEncoding + Default> {
+    /// The session the data belongs to
+    pub session: K,
+    /// The id of the participant that sent the data
+    pub sender: usize,
+    /// The data
+    pub payload: SessionPayload<G>,
+}
+
+/// The data carried by an [`Envelope`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SessionPayload<G: Group + GroupEncoding + Default> {
+    /// Round 1 broadcast data
+    #[serde(bound(serialize = "Round1BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round1BroadcastData<G>: Deserialize<'de>"))]
+    Round1Broadcast(Round1BroadcastData<G>),
+    /// Round 1 peer-to-peer data addressed to the receiving participant
+    Round1P2P(Round1P2PData),
+    /// Round 2 echo broadcast data
+    Round2Echo(Round2EchoBroadcastData),
+    /// Round 3 broadcast data
+    #[serde(bound(serialize = "Round3BroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round3BroadcastData<G>: Deserialize<'de>"))]
+    Round3Broadcast(Round3BroadcastData<G>),
+    /// Round 4 echo broadcast data
+    #[serde(bound(serialize = "Round4EchoBroadcastData<G>: Serialize"))]
+    #[serde(bound(deserialize = "Round4EchoBroadcastData<G>: Deserialize<'de>"))]
+    Round4Echo(Round4EchoBroadcastData<G>),
+}
+
+impl<G: Group + GroupEncoding + Default> SessionPayload<G> {
+    /// The round that uses this data as input
+    pub fn round(&self) -> Round {
+        match self {
+            Self::Round1Broadcast(_) | Self::Round1P2P(_) => Round::Two,
+            Self::Round2Echo(_) => Round::Three,
+            Self::Round3Broadcast(_) => Round::Four,
+            Self::Round4Echo(_) => Round::Five,
+        }
+    }
+
+    /// The name of the kind of data, each kind is counted separately
+    /// against [`ReceiveLimits::messages_per_round`]
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Round1Broadcast(_) => "round1_broadcast",
+            Self::Round1P2P(_) => "round1_p2p",
+            Self::Round2Echo(_) => "round2_echo",
+            Self::Round3Broadcast(_) => "round3_broadcast",
+            Self::Round4Echo(_) => "round4_echo",
+        }
+    }
+}
+
+/// The data produced when a session computes a round
+#[derive(Clone, Debug)]
+pub enum RoundOutput<G: Group + GroupEncoding + Default> {
+    /// Round 1 broadcast data and the peer-to-peer data keyed by recipient
+    Round1 {
+        /// Send to all other participants
+        broadcast: Round1BroadcastData<G>,
+        /// Send each entry only to the participant it is keyed by
+        p2p: BTreeMap<usize, Round1P2PData>,
+    },
+    /// Round 2 echo broadcast data
+    Round2(Round2EchoBroadcastData),
+    /// Round 3 broadcast data
+    Round3(Round3BroadcastData<G>),
+    /// Round 4 echo broadcast data
+    Round4(Round4EchoBroadcastData<G>),
+    /// Round 5 succeeded, the session is complete
+    Complete,
+}
+
+/// The state of a session managed by a [`SessionManager`]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Hash)]
+pub enum SessionStatus {
+    /// The session is still running
+    Active,
+    /// All rounds completed successfully
+    Complete,
+    /// The session failed with a non-retryable error or was aborted
+    Aborted,
+}
+
+/// Bounds on the data a [`SessionManager`] accepts for each session so a
+/// peer can't exhaust memory or processing by flooding a participant
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct ReceiveLimits {
+    /// The most envelopes accepted from one sender for each kind of data
+    /// in a round, including duplicates
+    pub messages_per_round: usize,
+    /// The most bytes of data buffered for one session. Each sender can
+    /// use an equal part, `buffered_bytes / limit`, and the data of a round
+    /// is released once the session computed the round
+    pub buffered_bytes: usize,
+}
+
+impl Default for ReceiveLimits {
+    fn default() -> Self {
+        Self {
+            messages_per_round: 8,
+            buffered_bytes: 1 << 20,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Inbox<G: Group + GroupEncoding + Default> {
+    round1_broadcast: BTreeMap<usize, Round1BroadcastData<G>>,
+    round1_p2p: BTreeMap<usize, Round1P2PData>,
+    round2_echo: BTreeMap<usize, Round2EchoBroadcastData>,
+    round3_broadcast: BTreeMap<usize, Round3BroadcastData<G>>,
+    round4_echo: BTreeMap<usize, Round4EchoBroadcastData<G>>,
+}
+
+impl<G: Group + GroupEncoding + Default> Inbox<G> {
+    /// Drop all data received from `sender`
+    fn remove(&mut self, sender: usize) {
+        self.round1_broadcast.remove(&sender);
+        self.round1_p2p.remove(&sender);
+        self.round2_echo.remove(&sender);
+        self.round3_broadcast.remove(&sender);
+        self.round4_echo.remove(&sender);
+    }
+
+    /// Drop the data used by `round` once it was computed
+    fn release(&mut self, round: Round) {
+        match round {
+            Round::Two => {
+                self.round1_broadcast.clear();
+                self.round1_p2p.clear();
+            }
+            Round::Three => self.round2_echo.clear(),
+            Round::Four => self.round3_broadcast.clear(),
+            Round::Five => self.round4_echo.clear(),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Session<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
+    participant: Participant<I, G>,
+    inbox: Inbox<G>,
+    status: SessionStatus,
+    invalid_senders: BTreeMap<usize, ParticipantError>,
+    equivocations: Vec<EquivocationEvidence<G>>,
+    received: BTreeMap<(usize, &'static str), usize>,
+    buffered_bytes: BTreeMap<(usize, Round), usize>,
+}
+
+/// Owns many concurrent participants keyed by session id.
+///
+/// Incoming data is buffered per session with [`SessionManager::receive`]
+/// and each session computes its next round with [`SessionManager::advance`]
+/// once enough data has arrived. Finished and aborted sessions are removed
+/// with [`SessionManager::gc`]. The data accepted per session is bounded
+/// by [`ReceiveLimits`].
+#[derive(Clone, Debug)]
+pub struct SessionManager<K: Ord + Clone, I: ParticipantImpl<G>, G: Group + GroupEncoding + Default>
+{
+    sessions: BTreeMap<K, Session<I, G>>,
+    limits: ReceiveLimits,
+}
+
+impl<K, I, G> Default for SessionManager<K, I, G>
+where
+    K: Ord + Clone,
+    I: ParticipantImpl<G>,
+    G: Group + GroupEncoding + Default,
+{
+    fn default() -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+            limits: ReceiveLimits::default(),
+        }
+    }
+}
+
+impl<K, I, G> SessionManager<K, I, G>
+where
+    K: Ord + Clone,
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+{
+    /// Create an empty session manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty session manager that accepts data up to `limits`
+    pub fn with_limits(limits: ReceiveLimits) -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+            limits,
+        }
+    }
+
+    /// Add a participant for `session`, returning the participant
+    /// previously stored for that session if any
+    pub fn insert(
+        &mut self,
+        session: K,
+        participant: Participant<I, G>,
+    ) -> Option<Participant<I, G>> {
+        self.sessions
+            .insert(
+                session,
+                Session {
+                    participant,
+                    inbox: Inbox::default(),
+                    status: SessionStatus::Active,
+                    invalid_senders: BTreeMap::new(),
+                    equivocations: Vec::new(),
+                    received: BTreeMap::new(),
+                    buffered_bytes: BTreeMap::new(),
+                },
+            )
+            .map(|s| s.participant)
+    }
+
+    /// Remove `session` and return its participant
+    pub fn remove(&mut self, session: &K) -> Option<Participant<I, G>> {
+        self.sessions.remove(session).map(|s| s.participant)
+    }
+
+    /// The participant for `session`
+    pub fn get(&self, session: &K) -> Option<&Participant<I, G>> {
+        self.sessions.get(session).map(|s| &s.participant)
+    }
+
+    /// The status of `session`
+    pub fn status(&self, session: &K) -> Option<SessionStatus> {
+        self.sessions.get(session).map(|s| s.status)
+    }
+
+    /// The records of the conflicting broadcast data received in `session`,
+    /// see [`EquivocationEvidence`] for what they do and do not show
+    pub fn equivocations(&self, session: &K) -> &[EquivocationEvidence<G>] {
+        self.sessions
+            .get(session)
+            .map(|s| s.equivocations.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The ids of all managed sessions
+    pub fn session_ids(&self) -> impl Iterator<Item = &K> {
+        self.sessions.keys()
+    }
+
+    /// The number of managed sessions
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Returns true if no sessions are managed
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Buffer the data in `envelope` for its session.
+    ///
+    /// Data is accepted up to one round ahead of the session and applied
+    /// once the session advances to the round using it. Data further ahead
+    /// returns a retryable [`Error::InvalidRound`] and should be sent again later.
+    /// Data for a round the session already computed is ignored.
+    /// Data from a sender outside `1..=limit` is rejected with
+    /// [`ParticipantError::UnexpectedBroadcast`].
+    /// Receiving the same data from a sender again is a no-op, receiving
+    /// different data for a round the sender already sent data for is
+    /// rejected with [`ParticipantError::ConflictingBroadcast`]. The sender
+    /// is then marked invalid, all its data is dropped and the evidence is
+    /// kept in [`SessionManager::equivocations`]. Data from a sender marked
+    /// invalid is rejected with the reason it was marked invalid.
+    ///
+    /// Envelopes beyond [`ReceiveLimits::messages_per_round`] for a sender and
+    /// kind of data are rejected with [`ParticipantError::TooManyMessages`]
+    /// before being looked at, data that would exceed the sender's part of
+    /// [`ReceiveLimits::buffered_bytes`] is rejected with [`Error::RoundError`].
+    pub fn receive(&mut self, envelope: Envelope<K, G>) -> DkgResult<()> {
+        let limits = self.limits;
+        let session = self
+            .sessions
+            .get_mut(&envelope.session)
+            .ok_or(Error::UnknownSession)?;
+        if let Some(reason) = session.participant.get_abort_reason() {
+            return Err(Error::Aborted(reason.to_string()));
+        }
+        let sender = envelope.sender;
+        let sent = match &envelope.payload {
+            SessionPayload::Round1Broadcast(_) | SessionPayload::Round1P2P(_) => Round::One,
+            SessionPayload::Round2Echo(_) => Round::Two,
+            SessionPayload::Round3Broadcast(_) => Round::Three,
+            SessionPayload::Round4Echo(_) => Round::Four,
+        };
+        if sender == 0 || sender > session.participant.get_limit() {
+            return Err(Error::SenderError {
+                round: sent.into(),
+                error: ParticipantError::UnexpectedBroadcast(sender),
+            });
+        }
+        if let Some(error) = session.invalid_senders.get(&sender) {
+            return Err(Error::SenderError {
+                round: sent.into(),
+                error: *error,
+            });
+        }
+        let current = session.participant.get_round();
+        let round = envelope.payload.round();
+        if current.next().is_some_and(|next| round > next) {
+            return Err(Error::InvalidRound {
+                round: round.into(),
+                current: current.into(),
+            });
+        }
+        if round < current || session.status == SessionStatus::Complete {
+            return Ok(());
+        }
+        let count = session
+            .received
+            .entry((sender, envelope.payload.label()))
+            .or_default();
+        if *count >= limits.messages_per_round {
+            return Err(Error::SenderError {
+                round: sent.into(),
+                error: ParticipantError::TooManyMessages(sender),
+            });
+        }
+        *count += 1;
+        let mut budget = Budget {
+            round,
+            sender,
+            buffered: &mut session.buffered_bytes,
+            limit: limits.buffered_bytes / session.participant.get_limit(),
+        };
+        let inbox = &mut session.inbox;
+        let conflicting = match envelope.payload {
+            SessionPayload::Round1Broadcast(data) => {
+                buffer(&mut inbox.round1_broadcast, &mut budget, data)?
+                    .map(|(a, b)| Some((BoardMessage::Round1(a), BoardMessage::Round1(b))))
+            }
+            SessionPayload::Round1P2P(data) => {
+                buffer(&mut inbox.round1_p2p, &mut budget, data)?.map(|_| None)
+            }
+            SessionPayload::Round2Echo(data) => buffer(&mut inbox.round2_echo, &mut budget, data)?
+                .map(|(a, b)| Some((BoardMessage::Round2(a), BoardMessage::Round2(b)))),
+            SessionPayload::Round3Broadcast(data) => {
+                buffer(&mut inbox.round3_broadcast, &mut budget, data)?
+                    .map(|(a, b)| Some((BoardMessage::Round3(a), BoardMessage::Round3(b))))
+            }
+            SessionPayload::Round4Echo(data) => buffer(&mut inbox.round4_echo, &mut budget, data)?
+                .map(|(a, b)| Some((BoardMessage::Round4(a), BoardMessage::Round4(b)))),
+        };
+        let Some(messages) = conflicting else {
+            return Ok(());
+        };
+        let error = ParticipantError::ConflictingBroadcast(sender);
+        session.invalidate(sent, error);
+        if let Some((first, second)) = messages {
+            session.equivocations.push(EquivocationEvidence {
+                sender,
+                first,
+                second,
+            });
+        }
+        Err(Error::SenderError {
+            round: sent.into(),
+            error,
+        })
+    }
+
+    /// Try to compute the next round for `session` with the data received so far.
+    ///
+    /// Returns [`None`] if the session is no longer active or more data
+    /// is needed. If a sender's data is rejected with a [`Severity::SenderFault`]
+    /// after the participant removed the sender from its valid set, the error
+    /// is returned, the sender is marked invalid and its data is dropped so a
+    /// later call computes the round without it once the quorum allows.
+    /// Any other error aborts the session, including a sender fault for a
+    /// sender whose dealing is still part of this participant's shares,
+    /// like a mismatched public key. If the deadline for
+    /// the round has passed the round is forced to advance, see
+    /// [`Participant::force_advance_if_expired`].
+    pub fn advance(&mut self, session: &K) -> DkgResult<Option<RoundOutput<G>>> {
+        let s = self
+            .sessions
+            .get_mut(session)
+            .ok_or(Error::UnknownSession)?;
+        let Session {
+            participant,
+            inbox,
+            status,
+            buffered_bytes,
+            ..
+        } = &mut *s;
+        if *status != SessionStatus::Active {
+            return Ok(None);
+        }
+        participant.force_advance_if_expired();
+        let computed = participant.get_round();
+        let result = match computed {
+            Round::Zero | Round::One => participant
+                .round1()
+                .map(|(broadcast, p2p)| RoundOutput::Round1 { broadcast, p2p }),
+            Round::Two => participant
+                .round2(inbox.round1_broadcast.clone(), inbox.round1_p2p.clone())
+                .map(RoundOutput::Round2),
+            Round::Three => participant
+                .round3(&inbox.round2_echo)
+                .map(RoundOutput::Round3),
+            Round::Four => participant
+                .round4(&inbox.round3_broadcast)
+                .map(RoundOutput::Round4),
+            Round::Five => participant
+                .round5(&inbox.round4_echo)
+                .map(|_| RoundOutput::Complete),
+            Round::Aborted => Err(Error::Aborted(
+                participant
+                    .get_abort_reason()
+                    .unwrap_or_default()
+                    .to_string(),
+            )),
+        };
+        match result {
+            Ok(output) => {
+                if matches!(output, RoundOutput::Complete) {
+                    *status = SessionStatus::Complete;
+                }
+                inbox.release(computed);
+                buffered_bytes.retain(|(_, round), _| *round > computed);
+                Ok(Some(output))
+            }
+            Err(e) if e.severity() == Severity::Retryable => Ok(None),
+            Err(Error::SenderError { round, error })
+                if !participant
+                    .get_valid_participant_ids()
+                    .contains(&error.participant_id()) =>
+            {
+                s.invalidate(computed, error);
+                Err(Error::SenderError { round, error })
+            }
+            Err(e) => {
+                *status = SessionStatus::Aborted;
+                participant.abort(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Mark `session` as aborted so it is removed by the next [`SessionManager::gc`]
+    pub fn abort(&mut self, session: &K) -> DkgResult<()> {
+        let s = self
+            .sessions
+            .get_mut(session)
+            .ok_or(Error::UnknownSession)?;
+        s.status = SessionStatus::Aborted;
+        s.participant.abort("aborted by the session manager");
+        Ok(())
+    }
+
+    /// The state of the ceremony in `session` for display
+    pub fn dkg_status(&self, session: &K) -> DkgResult<DkgStatus> {
+        let s = self.sessions.get(session).ok_or(Error::UnknownSession)?;
+        Ok(s.participant.status(s.received()))
+    }
+
+    /// The progress of `session` with the data buffered so far
+    pub fn progress(&self, session: &K) -> DkgResult<DkgProgress> {
+        let s = self.sessions.get(session).ok_or(Error::UnknownSession)?;
+        Ok(s.participant.progress(s.received()))
+    }
+
+    /// Remove all complete and aborted sessions, returning their ids
+    pub fn gc(&mut self) -> Vec<K> {
+        let finished = self
+            .sessions
+            .iter()
+            .filter(|(_, s)| s.status != SessionStatus::Active)
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        for id in &finished {
+            self.sessions.remove(id);
+        }
+        finished
+    }
+}
+
+impl<I, G> Session<I, G>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+{
+    /// The number of participants whose data for the current round
+    /// is buffered, including this participant
+    fn received(&self) -> usize {
+        let inbox = &self.inbox;
+        match self.participant.get_round() {
+            // This participant's own round 1 data is never in the inbox
+            Round::Two => inbox.round1_broadcast.len().min(inbox.round1_p2p.len()) + 1,
+            Round::Three => inbox.round2_echo.len(),
+            Round::Four => inbox.round3_broadcast.len(),
+            Round::Five => inbox.round4_echo.len(),
+            _ => 0,
+        }
+    }
+
+    /// Drop all data from the sender of `error` and ignore it from now on
+    fn invalidate(&mut self, round: Round, error: ParticipantError) {
+        let sender = error.participant_id();
+        self.inbox.remove(sender);
+        self.buffered_bytes.retain(|(id, _), _| *id != sender);
+        self.invalid_senders.insert(sender, error);
+        if !self.participant.is_invalidated(sender) {
+            self.participant.participant_invalidated(round, error);
+        }
+    }
+}
+
+/// The bytes buffered per sender and round for a session
+/// and the most each sender can buffer
+struct Budget<'a> {
+    round: Round,
+    sender: usize,
+    buffered: &'a mut BTreeMap<(usize, Round), usize>,
+    limit: usize,
+}
+
+/// Store `data` from the sender unless it already sent data for the round.
+/// Returns both messages if the data conflicts with the data received before.
+fn buffer<T: Serialize>(
+    inbox: &mut BTreeMap<usize, T>,
+    budget: &mut Budget<'_>,
+    data: T,
+) -> DkgResult<Option<(T, T)>> {
+    let sender = budget.sender;
+    let bytes = serialize_payload(&data)?;
+    if let Some(received) = inbox.get(&sender) {
+        if serialize_payload(received)? == bytes {
+            return Ok(None);
+        }
+        let received = inbox.remove(&sender).expect("to unwrap received data");
+        return Ok(Some((received, data)));
+    }
+    let buffered = budget
+        .buffered
+        .iter()
+        .filter(|((id, _), _)| *id == sender)
+        .map(|(_, len)| *len)
+        .sum::<usize>()
+        + bytes.len();
+    if buffered > budget.limit {
+        return Err(Error::RoundError(
+            budget.round.into(),
+            format!(
+                "data from {} exceeds its limit of {} buffered bytes",
+                sender, budget.limit
+            ),
+        ));
+    }
+    *budget.buffered.entry((sender, budget.round)).or_default() += bytes.len();
+    inbox.insert(sender, data);
+    Ok(None)
+}
+
+fn serialize_payload<T: Serialize>(data: &T) -> DkgResult<Vec<u8>> {
+    serde_bare::to_vec(data).map_err(|e| Error::SerializationError(e.to_string()))
+}
@@ -0,0 +1,220 @@
+//! An in-process ceremony that runs every participant to completion
+//! so a configuration can be validated before it is used in production.
+//!
+//! Messages are delivered through a simulated network that can lose
+//! or reorder them. A reordered message arrives after the receiver has
+//! already computed the round it belongs to. Late round 1 data is
+//! accepted with [`Participant::rehabilitate`] when the [`Parameters::grace_period`]
+//! allows it, any other late message is ignored like a lost one.
+use crate::*;
+use rand_chacha::ChaCha8Rng;
+use rand_core::RngCore;
+use std::collections::BTreeMap;
+
+/// How a simulated participant behaves
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Behavior {
+    /// Follows the protocol
+    #[default]
+    Honest,
+    /// Never sends any message
+    Offline,
+    /// Follows the protocol but stops before computing the round
+    Crash(Round),
+    /// Follows the protocol but corrupts its own data
+    #[cfg(feature = "testing")]
+    Faulty(testing::Fault),
+}
+
+/// Runs a ceremony in process
+#[derive(Copy, Clone, Debug)]
+pub struct Simulator {
+    packet_loss: f64,
+    reordering: f64,
+    seed: u64,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self {
+            packet_loss: 0.0,
+            reordering: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+enum Delivery {
+    OnTime,
+    Late,
+    Lost,
+}
+
+impl Simulator {
+    /// Create a new simulator with a reliable network
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the probability between 0 and 1 that a message is lost
+    pub fn with_packet_loss(mut self, packet_loss: f64) -> Self {
+        self.packet_loss = packet_loss.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the probability between 0 and 1 that a message arrives late
+    pub fn with_reordering(mut self, reordering: f64) -> Self {
+        self.reordering = reordering.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the seed for the network decisions so a run can be repeated
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Run a ceremony with a reliable network.
+    ///
+    /// See [`Simulator::simulate`].
+    pub fn run<G: Group + GroupEncoding + Default>(
+        parameters: Parameters<G>,
+        behaviors: &[Behavior],
+    ) -> DkgResult<Vec<DkgOutput<G>>> {
+        Self::default().simulate(parameters, behaviors)
+    }
+
+    /// Run a ceremony with one participant per entry in `behaviors`.
+    ///
+    /// Participant ids start at 1 in the order of `behaviors`. Returns the
+    /// output of every participant that completed round 5, the others stop
+    /// at the first error they encounter.
+    pub fn simulate<G: Group + GroupEncoding + Default>(
+        &self,
+        parameters: Parameters<G>,
+        behaviors: &[Behavior],
+    ) -> DkgResult<Vec<DkgOutput<G>>> {
+        if behaviors.len() != parameters.limit {
+            return Err(Error::InvalidParameters(format!(
+                "expected {} behaviors, got {}",
+                parameters.limit,
+                behaviors.len()
+            )));
+        }
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut participants = BTreeMap::new();
+        for (i, behavior) in behaviors.iter().enumerate() {
+            let id = NonZeroUsize::new(i + 1).expect("non zero");
+            #[allow(unused_mut)]
+            let mut participant = SecretParticipant::<G>::new(id, parameters)?;
+            #[cfg(feature = "testing")]
+            if let Behavior::Faulty(fault) = behavior {
+                participant.inject_fault(*fault);
+            }
+            if !matches!(behavior, Behavior::Offline) {
+                participants.insert(id.get(), (participant, *behavior));
+            }
+        }
+        let active = |participants: &BTreeMap<usize, (SecretParticipant<G>, Behavior)>,
+                      round: Round| {
+            participants
+                .iter()
+                .filter(|(_, (_, b))| !matches!(b, Behavior::Crash(r) if *r <= round))
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+        };
+
+        let mut r1bdata = BTreeMap::new();
+        let mut r1p2pdata = BTreeMap::new();
+        for id in active(&participants, Round::One) {
+            let (p, _) = participants.get_mut(&id).expect("active participant");
+            if let Ok((bdata, p2p)) = p.round1() {
+                r1bdata.insert(id, bdata);
+                r1p2pdata.insert(id, p2p);
+            }
+        }
+
+        let mut late = BTreeMap::new();
+        let mut r2bdata = BTreeMap::new();
+        for id in active(&participants, Round::Two) {
+            let mut bdata = BTreeMap::new();
+            let mut p2pdata = BTreeMap::new();
+            for (sender, sender_bdata) in r1bdata.iter().filter(|(s, _)| **s != id) {
+                let p2p = r1p2pdata[sender][&id].clone();
+                match self.deliver(&mut rng) {
+                    Delivery::OnTime => {
+                        bdata.insert(*sender, sender_bdata.clone());
+                        p2pdata.insert(*sender, p2p);
+                    }
+                    Delivery::Late => {
+                        late.insert((id, *sender), (sender_bdata.clone(), p2p));
+                    }
+                    Delivery::Lost => {}
+                }
+            }
+            let (p, _) = participants.get_mut(&id).expect("active participant");
+            if let Ok(echo) = p.round2(bdata, p2pdata) {
+                r2bdata.insert(id, echo);
+            }
+        }
+        for ((id, sender), (bdata, p2p)) in late {
+            if let Some((p, _)) = participants.get_mut(&id) {
+                let _ = p.rehabilitate(sender, bdata, p2p);
+            }
+        }
+
+        let mut r3bdata = BTreeMap::new();
+        for id in active(&participants, Round::Three) {
+            let echo_data = self.receive(&mut rng, id, &r2bdata);
+            let (p, _) = participants.get_mut(&id).expect("active participant");
+            if let Ok(bdata) = p.round3(&echo_data) {
+                r3bdata.insert(id, bdata);
+            }
+        }
+
+        let mut r4bdata = BTreeMap::new();
+        for id in active(&participants, Round::Four) {
+            let bdata = self.receive(&mut rng, id, &r3bdata);
+            let (p, _) = participants.get_mut(&id).expect("active participant");
+            if let Ok(echo) = p.round4(&bdata) {
+                r4bdata.insert(id, echo);
+            }
+        }
+
+        let mut outputs = Vec::new();
+        for id in active(&participants, Round::Five) {
+            let echo_data = self.receive(&mut rng, id, &r4bdata);
+            let (p, _) = participants.get_mut(&id).expect("active participant");
+            if p.round5(&echo_data).is_err() {
+                continue;
+            }
+            outputs.extend(p.output());
+        }
+        Ok(outputs)
+    }
+
+    /// The messages from `data` that reach `id` in time. A participant
+    /// always receives its own message.
+    fn receive<T: Clone>(
+        &self,
+        rng: &mut ChaCha8Rng,
+        id: usize,
+        data: &BTreeMap<usize, T>,
+    ) -> BTreeMap<usize, T> {
+        data.iter()
+            .filter(|(sender, _)| **sender == id || matches!(self.deliver(rng), Delivery::OnTime))
+            .map(|(sender, d)| (*sender, d.clone()))
+            .collect()
+    }
+
+    fn deliver(&self, rng: &mut ChaCha8Rng) -> Delivery {
+        let mut sample = || rng.next_u32() as f64 / u32::MAX as f64;
+        if sample() < self.packet_loss {
+            Delivery::Lost
+        } else if sample() < self.reordering {
+            Delivery::Late
+        } else {
+            Delivery::OnTime
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{DkgResult, Error, Round};
+use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Share identifiers are a single byte so no participant id is larger
+pub(crate) const MAX_ID: usize = u8::MAX as usize;
+
+/// Received data indexed by participant id.
+///
+/// Participant ids are `1..=limit` so a vector avoids the lookups
+/// and allocations of a map. Serializes the same as a
+/// `BTreeMap<usize, T>` to keep the stored format unchanged.
+#[derive(Clone, Debug)]
+pub(crate) struct Slots<T> {
+    slots: Vec<Option<T>>,
+}
+
+impl<T> Default for Slots<T> {
+    fn default() -> Self {
+        Self { slots: Vec::new() }
+    }
+}
+
+impl<T> Slots<T> {
+    /// Store `value` for participant `id`.
+    ///
+    /// Throws an error if `id` is not in `1..=limit` so the slots
+    /// never grow past the number of participants.
+    pub(crate) fn insert(&mut self, id: usize, limit: usize, value: T) -> DkgResult<()> {
+        if id == 0 || id > limit {
+            return Err(Error::InvalidParameters(format!(
+                "participant id {} is not in 1..={}",
+                id, limit
+            )));
+        }
+        if self.slots.len() < id {
+            self.slots.resize_with(id, || None);
+        }
+        self.slots[id - 1] = Some(value);
+        Ok(())
+    }
+
+    /// Collect `entries` whose ids must all be in `1..=limit`
+    pub(crate) fn from_entries<I: IntoIterator<Item = (usize, T)>>(
+        entries: I,
+        limit: usize,
+    ) -> DkgResult<Self> {
+        let mut slots = Self::default();
+        for (id, value) in entries {
+            slots.insert(id, limit, value)?;
+        }
+        Ok(slots)
+    }
+
+    pub(crate) fn get(&self, id: &usize) -> Option<&T> {
+        id.checked_sub(1)
+            .and_then(|i| self.slots.get(i))
+            .and_then(Option::as_ref)
+    }
+
+    /// The data for participant `id` needed to compute `round`
+    pub(crate) fn require(&self, id: &usize, round: Round) -> DkgResult<&T> {
+        self.get(id).ok_or_else(|| {
+            Error::RoundError(
+                round.into(),
+                format!("missing round 1 data for participant {}", id),
+            )
+        })
+    }
+
+    pub(crate) fn contains_key(&self, id: &usize) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.as_ref().map(|v| (i + 1, v)))
+    }
+}
+
+impl<T: Serialize> Serialize for Slots<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        // Some formats need the length up front
+        let mut map = s.serialize_map(Some(self.iter().count()))?;
+        for (id, value) in self.iter() {
+            map.serialize_entry(&id, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Slots<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let map = BTreeMap::<usize, T>::deserialize(d)?;
+        Self::from_entries(map, MAX_ID).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,81 @@
+//! Metrics emitted when the `metrics` feature is enabled.
+//! Without the feature every function here is a no-op.
+use super::*;
+
+/// Records how long a round took to compute
+pub(crate) struct RoundTimer {
+    #[cfg(feature = "metrics")]
+    round: Round,
+    #[cfg(feature = "metrics")]
+    start: std::time::Instant,
+}
+
+impl RoundTimer {
+    pub(crate) fn start(_round: Round) -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            round: _round,
+            #[cfg(feature = "metrics")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn stop(self) {
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("gennaro_dkg_round_duration_seconds", "round" => self.round.to_string())
+            .record(self.start.elapsed());
+    }
+}
+
+pub(crate) fn message_verified(_round: Round) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("gennaro_dkg_messages_verified_total", "round" => _round.to_string())
+        .increment(1);
+}
+
+pub(crate) fn message_rejected(_round: Round, _reason: &ParticipantError) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "gennaro_dkg_messages_rejected_total",
+        "round" => _round.to_string(),
+        "reason" => reason_label(_reason),
+    )
+    .increment(1);
+}
+
+pub(crate) fn participant_invalidated(_round: Round) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("gennaro_dkg_participants_invalidated_total", "round" => _round.to_string())
+        .increment(1);
+}
+
+#[cfg(feature = "metrics")]
+fn reason_label(reason: &ParticipantError) -> &'static str {
+    match reason {
+        ParticipantError::MissingP2PData(_) => "missing_p2p_data",
+        ParticipantError::MissingBroadcastData(_) => "missing_broadcast_data",
+        ParticipantError::MismatchedParameters(_) => "mismatched_parameters",
+        ParticipantError::IdentityElementPedersenCommitments(_) => {
+            "identity_element_pedersen_commitments"
+        }
+        ParticipantError::ZeroValueShares(_) => "zero_value_shares",
+        ParticipantError::NoVerifyShares(_) => "no_verify_shares",
+        ParticipantError::BadFormatShare(_) => "bad_format_share",
+        ParticipantError::UnexpectedBroadcast(_) => "unexpected_broadcast",
+        ParticipantError::MissingP2PDataRound1(_) => "missing_p2p_data_round1",
+        ParticipantError::MissingBroadcastDataRound1(_) => "missing_broadcast_data_round1",
+        ParticipantError::IdentityElementFeldmanCommitments(_) => {
+            "identity_element_feldman_commitments"
+        }
+        ParticipantError::MismatchedValidParticipants(_) => "mismatched_valid_participants",
+        ParticipantError::MismatchedPublicKey(_) => "mismatched_public_key",
+        ParticipantError::Absent(_) => "absent",
+        ParticipantError::NotCommonlyValid(_) => "not_commonly_valid",
+        ParticipantError::ConflictingBroadcast(_) => "conflicting_broadcast",
+        ParticipantError::MismatchedImportedKey(_) => "mismatched_imported_key",
+        ParticipantError::TooManyMessages(_) => "too_many_messages",
+        ParticipantError::InvalidEncryptionKey(_) => "invalid_encryption_key",
+        ParticipantError::InvalidZeroSecretProof(_) => "invalid_zero_secret_proof",
+        ParticipantError::MismatchedTranscript(_) => "mismatched_transcript",
+    }
+}
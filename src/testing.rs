@@ -0,0 +1,223 @@
+//! Helpers for testing how applications handle misbehaving participants
+
+pub mod strategies;
+
+use crate::*;
+use std::collections::BTreeMap;
+use vsss_rs::{elliptic_curve::ff::Field, Share};
+
+/// A misbehavior of a [`ByzantineParticipant`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Attack {
+    /// Send round 1 shares to the participant with this id that don't verify
+    WrongShares(usize),
+    /// Send round 3 feldman commitments that don't match the round 1 shares
+    InconsistentCommitments,
+    /// Echo a valid set without the participant with this id in round 2
+    MismatchedValidSet(usize),
+    /// Send different round 1 pedersen commitments to the participant with this id
+    /// than to everyone else
+    Equivocate(usize),
+    /// Echo a wrong public key in round 4
+    WrongPublicKey,
+}
+
+/// Corrupts a field of a participant's own data right before a round returns it,
+/// see [`Participant::inject_fault`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Change the round 1 pedersen commitment at this index
+    FlipPedersenCommitment(usize),
+    /// Send a zero round 1 secret share to the participant with this id
+    ZeroShare(usize),
+    /// Remove the participant with this id from the round 2 echoed valid set
+    DropValidParticipant(usize),
+    /// Change the round 3 feldman commitment at this index
+    FlipFeldmanCommitment(usize),
+    /// Change the round 4 public key
+    FlipPublicKey,
+}
+
+impl Fault {
+    pub(crate) fn round1<G: Group + GroupEncoding + Default>(
+        &self,
+        bdata: &mut Round1BroadcastData<G>,
+        p2p: &mut BTreeMap<usize, Round1P2PData>,
+    ) {
+        match self {
+            Self::FlipPedersenCommitment(index) => {
+                if let Some(c) = bdata.pedersen_commitments.get_mut(*index) {
+                    *c += G::generator();
+                }
+            }
+            Self::ZeroShare(id) => {
+                if let Some(data) = p2p.get_mut(id) {
+                    data.secret_share = InnerShare::from_field_element(*id as u8, G::Scalar::ZERO)
+                        .expect("valid share");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn round2(&self, echo: &mut Round2EchoBroadcastData) {
+        if let Self::DropValidParticipant(id) = self {
+            echo.valid_participant_ids.remove(id);
+        }
+    }
+
+    pub(crate) fn round3<G: Group + GroupEncoding + Default>(
+        &self,
+        bdata: &mut Round3BroadcastData<G>,
+    ) {
+        if let Self::FlipFeldmanCommitment(index) = self {
+            if let Some(c) = bdata.commitments.get_mut(*index) {
+                *c += G::generator();
+            }
+        }
+    }
+
+    pub(crate) fn round4<G: Group + GroupEncoding + Default>(
+        &self,
+        echo: &mut Round4EchoBroadcastData<G>,
+    ) {
+        if let Self::FlipPublicKey = self {
+            echo.public_key += G::generator();
+        }
+    }
+}
+
+/// Wraps a [`Participant`] to misbehave as described by its [`Attack`]s.
+///
+/// All outputs are keyed by recipient so broadcasts can differ per recipient.
+/// Honest participants should receive each entry as if it was broadcast.
+#[derive(Clone, Debug)]
+pub struct ByzantineParticipant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
+    participant: Participant<I, G>,
+    attacks: Vec<Attack>,
+    #[allow(clippy::type_complexity)]
+    round1: Option<(
+        BTreeMap<usize, Round1BroadcastData<G>>,
+        BTreeMap<usize, Round1P2PData>,
+    )>,
+}
+
+impl<I, G> ByzantineParticipant<I, G>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+{
+    /// Wrap `participant` which behaves honestly until attacks are added
+    pub fn new(participant: Participant<I, G>) -> Self {
+        Self {
+            participant,
+            attacks: Vec::new(),
+            round1: None,
+        }
+    }
+
+    /// Add an attack
+    pub fn with_attack(mut self, attack: Attack) -> Self {
+        self.attacks.push(attack);
+        self
+    }
+
+    /// The wrapped participant
+    pub fn participant(&self) -> &Participant<I, G> {
+        &self.participant
+    }
+
+    /// The identifier of the wrapped participant
+    pub fn get_id(&self) -> usize {
+        self.participant.get_id()
+    }
+
+    fn recipients(&self) -> impl Iterator<Item = usize> + '_ {
+        let id = self.get_id();
+        (1..=self.participant.get_limit()).filter(move |i| *i != id)
+    }
+
+    /// Computes round 1, see [`Participant::round1`]
+    #[allow(clippy::type_complexity)]
+    pub fn round1(
+        &mut self,
+    ) -> DkgResult<(
+        BTreeMap<usize, Round1BroadcastData<G>>,
+        BTreeMap<usize, Round1P2PData>,
+    )> {
+        let (bdata, mut p2p) = self.participant.round1()?;
+        let mut broadcast = self
+            .recipients()
+            .map(|id| (id, bdata.clone()))
+            .collect::<BTreeMap<_, _>>();
+        for attack in &self.attacks {
+            match attack {
+                Attack::WrongShares(id) => {
+                    if let Some(data) = p2p.get_mut(id) {
+                        let share = G::Scalar::random(rand_core::OsRng);
+                        data.secret_share =
+                            InnerShare::from_field_element(*id as u8, share).expect("valid share");
+                    }
+                }
+                Attack::Equivocate(id) => {
+                    if let Some(data) = broadcast.get_mut(id) {
+                        data.pedersen_commitments[0] += G::generator();
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.round1 = Some((broadcast.clone(), p2p.clone()));
+        Ok((broadcast, p2p))
+    }
+
+    /// Send the round 1 data again to test duplicate handling
+    #[allow(clippy::type_complexity)]
+    pub fn replay_round1(
+        &self,
+    ) -> Option<(
+        BTreeMap<usize, Round1BroadcastData<G>>,
+        BTreeMap<usize, Round1P2PData>,
+    )> {
+        self.round1.clone()
+    }
+
+    /// Computes round 2, see [`Participant::round2`]
+    pub fn round2(
+        &mut self,
+        broadcast_data: BTreeMap<usize, Round1BroadcastData<G>>,
+        p2p_data: BTreeMap<usize, Round1P2PData>,
+    ) -> DkgResult<BTreeMap<usize, Round2EchoBroadcastData>> {
+        let mut echo = self.participant.round2(broadcast_data, p2p_data)?;
+        for attack in &self.attacks {
+            if let Attack::MismatchedValidSet(id) = attack {
+                echo.valid_participant_ids.remove(id);
+            }
+        }
+        Ok(self.recipients().map(|id| (id, echo.clone())).collect())
+    }
+
+    /// Computes round 3, see [`Participant::round3`]
+    pub fn round3(
+        &mut self,
+        echo_data: &BTreeMap<usize, Round2EchoBroadcastData>,
+    ) -> DkgResult<BTreeMap<usize, Round3BroadcastData<G>>> {
+        let mut bdata = self.participant.round3(echo_data)?;
+        if self.attacks.contains(&Attack::InconsistentCommitments) {
+            bdata.commitments[1] += G::generator();
+        }
+        Ok(self.recipients().map(|id| (id, bdata.clone())).collect())
+    }
+
+    /// Computes round 4, see [`Participant::round4`]
+    pub fn round4(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
+    ) -> DkgResult<BTreeMap<usize, Round4EchoBroadcastData<G>>> {
+        let mut echo = self.participant.round4(broadcast_data)?;
+        if self.attacks.contains(&Attack::WrongPublicKey) {
+            echo.public_key += G::generator();
+        }
+        Ok(self.recipients().map(|id| (id, echo)).collect())
+    }
+}
@@ -0,0 +1,156 @@
+//! [`proptest`] strategies generating round data.
+//!
+//! Each round has a strategy for structurally valid data, which has
+//! the expected shape but won't verify, and one for structurally invalid
+//! data such as identity elements, wrong lengths or malformed shares.
+
+use crate::*;
+use proptest::prelude::*;
+use rand_chacha::ChaChaRng;
+use vsss_rs::{elliptic_curve::ff::Field, Share};
+
+/// Random group elements
+pub fn point<G: Group + GroupEncoding + Default>() -> impl Strategy<Value = G> {
+    any::<[u8; 32]>().prop_map(|seed| G::random(ChaChaRng::from_seed(seed)))
+}
+
+/// Random non-zero scalars
+pub fn scalar<G: Group + GroupEncoding + Default>() -> impl Strategy<Value = G::Scalar> {
+    any::<[u8; 32]>()
+        .prop_map(|seed| G::Scalar::random(ChaChaRng::from_seed(seed)))
+        .prop_filter("scalar is zero", |s| !bool::from(s.is_zero()))
+}
+
+/// Round 1 broadcast data using the generators from `parameters`
+pub fn round1_broadcast_data<G: Group + GroupEncoding + Default>(
+    parameters: Parameters<G>,
+) -> impl Strategy<Value = Round1BroadcastData<G>> {
+    proptest::collection::vec(point::<G>(), parameters.threshold).prop_map(move |commitments| {
+        Round1BroadcastData {
+            message_generator: parameters.message_generator,
+            blinder_generator: parameters.blinder_generator,
+            pedersen_commitments: commitments,
+            zero_secret_proof: None,
+        }
+    })
+}
+
+/// Round 1 broadcast data with identity commitments, the wrong number
+/// of commitments or different generators than `parameters`
+pub fn invalid_round1_broadcast_data<G: Group + GroupEncoding + Default>(
+    parameters: Parameters<G>,
+) -> impl Strategy<Value = Round1BroadcastData<G>> {
+    let threshold = parameters.threshold;
+    (
+        round1_broadcast_data(parameters),
+        0..threshold.max(1),
+        point::<G>(),
+        0..4usize,
+    )
+        .prop_map(move |(mut data, index, point, kind)| {
+            match kind {
+                0 => data.pedersen_commitments[index % threshold] = G::identity(),
+                1 => data.pedersen_commitments.truncate(index),
+                2 => data.pedersen_commitments.push(point),
+                _ => data.message_generator = point,
+            }
+            data
+        })
+}
+
+/// Round 1 peer data with well formed shares for the participant `id`
+pub fn round1_p2p_data<G: Group + GroupEncoding + Default>(
+    id: usize,
+) -> impl Strategy<Value = Round1P2PData> {
+    (scalar::<G>(), scalar::<G>()).prop_map(move |(secret, blind)| Round1P2PData {
+        secret_share: InnerShare::from_field_element(id as u8, secret).expect("valid share"),
+        blind_share: InnerShare::from_field_element(id as u8, blind).expect("valid share"),
+    })
+}
+
+/// Round 1 peer data with zero, empty or arbitrary bytes for shares
+pub fn invalid_round1_p2p_data<G: Group + GroupEncoding + Default>(
+    id: usize,
+) -> impl Strategy<Value = Round1P2PData> {
+    (
+        round1_p2p_data::<G>(id),
+        proptest::collection::vec(any::<u8>(), 0..64),
+        0..3usize,
+    )
+        .prop_map(move |(mut data, bytes, kind)| {
+            match kind {
+                0 => {
+                    data.secret_share = InnerShare::from_field_element(id as u8, G::Scalar::ZERO)
+                        .expect("valid share")
+                }
+                1 => data.blind_share = Vec::new(),
+                _ => data.secret_share = bytes,
+            }
+            data
+        })
+}
+
+/// Round 2 echo data with valid sets drawn from `1..=limit`
+pub fn round2_echo_broadcast_data(limit: usize) -> impl Strategy<Value = Round2EchoBroadcastData> {
+    proptest::collection::btree_set(1..=limit, 0..=limit).prop_map(|valid_participant_ids| {
+        Round2EchoBroadcastData {
+            valid_participant_ids,
+        }
+    })
+}
+
+/// Round 2 echo data with valid sets containing ids outside of `1..=limit`
+pub fn invalid_round2_echo_broadcast_data(
+    limit: usize,
+) -> impl Strategy<Value = Round2EchoBroadcastData> {
+    (
+        round2_echo_broadcast_data(limit),
+        prop_oneof![Just(0usize), (limit + 1)..usize::MAX],
+    )
+        .prop_map(|(mut data, id)| {
+            data.valid_participant_ids.insert(id);
+            data
+        })
+}
+
+/// Round 3 broadcast data with `threshold` commitments
+pub fn round3_broadcast_data<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+) -> impl Strategy<Value = Round3BroadcastData<G>> {
+    proptest::collection::vec(point::<G>(), threshold)
+        .prop_map(|commitments| Round3BroadcastData { commitments })
+}
+
+/// Round 3 broadcast data with identity commitments or the wrong number of commitments
+pub fn invalid_round3_broadcast_data<G: Group + GroupEncoding + Default>(
+    threshold: usize,
+) -> impl Strategy<Value = Round3BroadcastData<G>> {
+    (
+        round3_broadcast_data::<G>(threshold),
+        0..threshold.max(1),
+        point::<G>(),
+        0..3usize,
+    )
+        .prop_map(move |(mut data, index, point, kind)| {
+            match kind {
+                0 => data.commitments[index % threshold] = G::identity(),
+                1 => data.commitments.truncate(index),
+                _ => data.commitments.push(point),
+            }
+            data
+        })
+}
+
+/// Round 4 echo data with random public keys
+pub fn round4_echo_broadcast_data<G: Group + GroupEncoding + Default>(
+) -> impl Strategy<Value = Round4EchoBroadcastData<G>> {
+    point::<G>().prop_map(|public_key| Round4EchoBroadcastData { public_key })
+}
+
+/// Round 4 echo data with the identity as public key
+pub fn invalid_round4_echo_broadcast_data<G: Group + GroupEncoding + Default>(
+) -> impl Strategy<Value = Round4EchoBroadcastData<G>> {
+    Just(Round4EchoBroadcastData {
+        public_key: G::identity(),
+    })
+}
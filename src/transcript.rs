@@ -0,0 +1,123 @@
+use super::*;
+use sha2::{Digest, Sha256};
+
+/// A single entry absorbed into the [`Transcript`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptItem {
+    /// The round in which the entry was absorbed
+    pub round: Round,
+    /// The participant the data belongs to
+    pub participant: usize,
+    /// What the data represents
+    pub label: String,
+    /// The absorbed data
+    pub data: Vec<u8>,
+}
+
+/// An ordered record of the public data each participant agreed on
+/// during the protocol.
+///
+/// Only broadcast data is absorbed, in participant order, so all honest
+/// participants that reach the same round compute the same transcript.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    items: Vec<TranscriptItem>,
+}
+
+impl Transcript {
+    pub(crate) fn append(&mut self, round: Round, participant: usize, label: &str, data: &[u8]) {
+        self.items.push(TranscriptItem {
+            round,
+            participant,
+            label: label.to_string(),
+            data: data.to_vec(),
+        });
+    }
+
+    pub(crate) fn append_points<G: Group + GroupEncoding + Default>(
+        &mut self,
+        round: Round,
+        participant: usize,
+        label: &str,
+        points: &[G],
+    ) {
+        let mut data = Vec::with_capacity(points.len() * G::Repr::default().as_ref().len());
+        for p in points {
+            data.extend_from_slice(p.to_bytes().as_ref());
+        }
+        self.append(round, participant, label, &data);
+    }
+
+    /// The absorbed entries in the order they were added
+    pub fn items(&self) -> &[TranscriptItem] {
+        &self.items
+    }
+
+    /// Compute the SHA-256 hash of all absorbed entries
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash_with::<Sha256Transcript>()
+    }
+
+    /// Compute the SHA-256 hash of the entries describing the ceremony,
+    /// which are absorbed before any dealer's data and are the same
+    /// for every participant from round 1 on
+    pub(crate) fn setup_hash(&self) -> [u8; 32] {
+        let mut transcript = Sha256Transcript::default();
+        for item in self.items.iter().filter(|item| {
+            item.round == Round::Zero || (item.round == Round::One && item.participant == 0)
+        }) {
+            transcript.absorb(item);
+        }
+        transcript.finalize()
+    }
+
+    /// Compute the digest of all absorbed entries with the backend `T`
+    pub fn hash_with<T: DkgTranscript>(&self) -> T::Output {
+        let mut transcript = T::default();
+        for item in &self.items {
+            transcript.absorb(item);
+        }
+        transcript.finalize()
+    }
+}
+
+/// A backend that commits to the transcript entries
+pub trait DkgTranscript: Default {
+    /// The digest produced
+    type Output;
+
+    /// Absorb the next entry
+    fn absorb(&mut self, item: &TranscriptItem);
+
+    /// Compute the digest of all absorbed entries
+    fn finalize(self) -> Self::Output;
+}
+
+/// The default transcript backend using SHA-256
+#[derive(Clone, Debug)]
+pub struct Sha256Transcript(Sha256);
+
+impl Default for Sha256Transcript {
+    fn default() -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"gennaro-dkg transcript");
+        Self(hasher)
+    }
+}
+
+impl DkgTranscript for Sha256Transcript {
+    type Output = [u8; 32];
+
+    fn absorb(&mut self, item: &TranscriptItem) {
+        self.0.update([u8::from(item.round)]);
+        self.0.update((item.participant as u64).to_be_bytes());
+        self.0.update((item.label.len() as u64).to_be_bytes());
+        self.0.update(item.label.as_bytes());
+        self.0.update((item.data.len() as u64).to_be_bytes());
+        self.0.update(&item.data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
@@ -0,0 +1,663 @@
+use super::*;
+#[cfg(feature = "quic")]
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+#[cfg(feature = "mqtt")]
+use std::sync::mpsc;
+use std::time::Duration;
+#[cfg(any(feature = "mqtt", feature = "nats"))]
+use std::time::Instant;
+#[cfg(feature = "quic")]
+use std::{collections::BTreeMap, net::SocketAddr};
+
+/// The point-to-point and broadcast channels between the participants
+/// of one ceremony.
+///
+/// Implementations carry [`TransportFrame`]s so every transport uses the
+/// same encoding. A transport only moves data, the receiver checks it
+/// with a [`SessionManager`] or the round methods as usual. Data is never
+/// delivered back to its sender, so the caller passes its own data from
+/// round 2 on to its [`SessionManager`] itself. The sender
+/// id of a frame is not authenticated by the frame itself, use a transport
+/// that authenticates its peers or seal the peer-to-peer data.
+pub trait Transport<G: Group + GroupEncoding + Default> {
+    /// Send `payload` to all other participants
+    fn broadcast(&mut self, payload: &SessionPayload<G>) -> DkgResult<()>;
+
+    /// Send `payload` only to `recipient`
+    fn send(&mut self, recipient: usize, payload: &SessionPayload<G>) -> DkgResult<()>;
+
+    /// Wait up to `timeout` for the next data sent to this participant.
+    ///
+    /// Returns the sender and the data or [`None`] if nothing arrived in time.
+    fn receive(&mut self, timeout: Duration) -> DkgResult<Option<(usize, SessionPayload<G>)>>;
+}
+
+/// The data sent by a [`Transport`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransportFrame<G: Group + GroupEncoding + Default> {
+    /// The id of the participant that sent the data
+    #[serde(with = "ordinal")]
+    pub sender: usize,
+    /// The data
+    #[serde(bound(serialize = "SessionPayload<G>: Serialize"))]
+    #[serde(bound(deserialize = "SessionPayload<G>: Deserialize<'de>"))]
+    pub payload: SessionPayload<G>,
+}
+
+impl<G: Group + GroupEncoding + Default> TransportFrame<G> {
+    /// Serialize the frame to bytes
+    pub fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        serde_bare::to_vec(self).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Deserialize a frame from bytes
+    pub fn from_bytes(bytes: &[u8]) -> DkgResult<Self> {
+        serde_bare::from_slice(bytes).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
+
+/// What [`RelayFilter::validate_envelope`] learned about a frame
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnvelopeHeader {
+    /// The id of the participant that claims to have sent the frame
+    pub sender: usize,
+    /// The round that uses the data, see [`SessionPayload::round`]
+    pub round: Round,
+    /// True if the data is addressed to one participant
+    pub p2p: bool,
+}
+
+/// Checks [`TransportFrame`]s for relays that forward data
+/// between participants without taking part in the ceremony.
+///
+/// Only the frame header is read so a relay never decodes the shares in
+/// peer-to-peer data. Passing the filter doesn't make a frame valid,
+/// the participants still check everything they receive, it only lets
+/// a relay drop junk before forwarding it.
+#[derive(Clone, Debug)]
+pub struct RelayFilter {
+    participants: BTreeSet<usize>,
+    max_length: usize,
+}
+
+impl RelayFilter {
+    /// The number of bytes before the payload body, the sender and the payload kind
+    const HEADER_LENGTH: usize = 5;
+
+    /// Accept frames from `participants` up to the default
+    /// [`ReceiveLimits::buffered_bytes`] in length
+    pub fn new<I: IntoIterator<Item = usize>>(participants: I) -> Self {
+        Self {
+            participants: participants.into_iter().collect(),
+            max_length: ReceiveLimits::default().buffered_bytes,
+        }
+    }
+
+    /// Accept frames up to `max_length` bytes
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Check the length, the header and the sender of the serialized frame in `bytes`.
+    ///
+    /// Throws [`Error::SerializationError`] if the frame is too short,
+    /// too long or the payload kind is unknown, and
+    /// [`ParticipantError::UnexpectedBroadcast`] if the sender is not a participant.
+    pub fn validate_envelope(&self, bytes: &[u8]) -> DkgResult<EnvelopeHeader> {
+        if bytes.len() <= Self::HEADER_LENGTH {
+            return Err(Error::SerializationError(
+                "the frame is too short".to_string(),
+            ));
+        }
+        if bytes.len() > self.max_length {
+            return Err(Error::SerializationError(format!(
+                "the frame is longer than {} bytes",
+                self.max_length
+            )));
+        }
+        let sender = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        // The variant index of the payload in the order of SessionPayload
+        let (sent, round, p2p) = match bytes[4] {
+            0 => (Round::One, Round::Two, false),
+            1 => (Round::One, Round::Two, true),
+            2 => (Round::Two, Round::Three, false),
+            3 => (Round::Three, Round::Four, false),
+            4 => (Round::Four, Round::Five, false),
+            kind => {
+                return Err(Error::SerializationError(format!(
+                    "unknown payload kind {}",
+                    kind
+                )))
+            }
+        };
+        if !self.participants.contains(&sender) {
+            return Err(Error::SenderError {
+                round: sent.into(),
+                error: ParticipantError::UnexpectedBroadcast(sender),
+            });
+        }
+        Ok(EnvelopeHeader { sender, round, p2p })
+    }
+}
+
+#[cfg(any(feature = "mqtt", feature = "nats", feature = "quic"))]
+#[derive(Serialize)]
+struct FrameRef<'a, G: Group + GroupEncoding + Default> {
+    #[serde(with = "ordinal")]
+    sender: usize,
+    #[serde(bound(serialize = "SessionPayload<G>: Serialize"))]
+    payload: &'a SessionPayload<G>,
+}
+
+#[cfg(any(feature = "mqtt", feature = "nats", feature = "quic"))]
+impl<G: Group + GroupEncoding + Default> FrameRef<'_, G> {
+    fn to_bytes(&self) -> DkgResult<Vec<u8>> {
+        serde_bare::to_vec(self).map_err(|e| Error::SerializationError(e.to_string()))
+    }
+}
+
+/// A [`Transport`] over an MQTT broker.
+///
+/// All participants publish broadcast data to `{prefix}/broadcast` and
+/// peer-to-peer data to `{prefix}/p2p/{recipient}`. Each participant
+/// subscribes to the broadcast topic and its own peer-to-peer topic.
+/// The prefix must identify the ceremony, for example
+/// `dkg/{session id}`, so concurrent ceremonies don't mix.
+///
+/// The connection to the broker is driven on a background thread so
+/// publishing never waits for [`Transport::receive`]. Publishes that don't
+/// decode as a [`TransportFrame`] are skipped.
+///
+/// The broker sees the peer-to-peer data, configure it with TLS and
+/// ACLs so only participant `id` can read `{prefix}/p2p/{id}` or seal
+/// the data with a secure channel before sending.
+#[cfg(feature = "mqtt")]
+pub struct MqttTransport {
+    id: usize,
+    prefix: String,
+    client: rumqttc::Client,
+    incoming: mpsc::Receiver<Result<Vec<u8>, rumqttc::ConnectionError>>,
+}
+
+#[cfg(feature = "mqtt")]
+impl fmt::Debug for MqttTransport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MqttTransport")
+            .field("id", &self.id)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl MqttTransport {
+    /// Connect participant `id` to the broker with `options`
+    /// and subscribe to its topics under `prefix`
+    pub fn new<S: Into<String>>(
+        id: usize,
+        options: rumqttc::MqttOptions,
+        prefix: S,
+    ) -> DkgResult<Self> {
+        let (client, connection) = rumqttc::Client::new(options, 64);
+        let (sender, incoming) = mpsc::channel();
+        let transport = Self {
+            id,
+            prefix: prefix.into().trim_end_matches('/').to_string(),
+            client,
+            incoming,
+        };
+        let topics = [transport.broadcast_topic(), transport.p2p_topic(id)];
+        std::thread::Builder::new()
+            .name(format!("mqtt-transport-{}", id))
+            .spawn(move || event_loop(connection, topics, sender))?;
+        transport
+            .client
+            .subscribe(transport.broadcast_topic(), rumqttc::QoS::AtLeastOnce)
+            .map_err(transport_error)?;
+        transport
+            .client
+            .subscribe(transport.p2p_topic(id), rumqttc::QoS::AtLeastOnce)
+            .map_err(transport_error)?;
+        Ok(transport)
+    }
+
+    fn broadcast_topic(&self) -> String {
+        format!("{}/broadcast", self.prefix)
+    }
+
+    fn p2p_topic(&self, recipient: usize) -> String {
+        format!("{}/p2p/{}", self.prefix, recipient)
+    }
+
+    fn publish<G: Group + GroupEncoding + Default>(
+        &mut self,
+        topic: String,
+        payload: &SessionPayload<G>,
+    ) -> DkgResult<()> {
+        let frame = FrameRef {
+            sender: self.id,
+            payload,
+        };
+        self.client
+            .publish(topic, rumqttc::QoS::AtLeastOnce, false, frame.to_bytes()?)
+            .map_err(transport_error)
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl<G: Group + GroupEncoding + Default> Transport<G> for MqttTransport {
+    fn broadcast(&mut self, payload: &SessionPayload<G>) -> DkgResult<()> {
+        self.publish(self.broadcast_topic(), payload)
+    }
+
+    fn send(&mut self, recipient: usize, payload: &SessionPayload<G>) -> DkgResult<()> {
+        self.publish(self.p2p_topic(recipient), payload)
+    }
+
+    fn receive(&mut self, timeout: Duration) -> DkgResult<Option<(usize, SessionPayload<G>)>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let payload = match self.incoming.recv_timeout(remaining) {
+                Ok(payload) => payload.map_err(transport_error)?,
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(Error::IoError(std::io::Error::new(
+                        std::io::ErrorKind::NotConnected,
+                        "the MQTT connection was closed",
+                    )))
+                }
+            };
+            // Anyone allowed to publish on the topics can send junk
+            let Ok(frame) = TransportFrame::<G>::from_bytes(&payload) else {
+                continue;
+            };
+            // The broker delivers this participant's broadcasts back to it
+            if frame.sender != self.id {
+                return Ok(Some((frame.sender, frame.payload)));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl Drop for MqttTransport {
+    fn drop(&mut self) {
+        // Stops the event loop thread
+        let _ = self.client.disconnect();
+    }
+}
+
+/// Poll `connection` until the client disconnects, forwarding the
+/// publishes on `topics` and the connection errors to `sender`
+#[cfg(feature = "mqtt")]
+fn event_loop(
+    mut connection: rumqttc::Connection,
+    topics: [String; 2],
+    sender: mpsc::Sender<Result<Vec<u8>, rumqttc::ConnectionError>>,
+) {
+    for event in connection.iter() {
+        let message = match event {
+            Ok(rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish))) => {
+                if !topics.contains(&publish.topic) {
+                    continue;
+                }
+                Ok(publish.payload.to_vec())
+            }
+            Ok(_) => continue,
+            Err(e) => {
+                // The next poll reconnects, don't spin while the broker is down
+                std::thread::sleep(Duration::from_millis(100));
+                Err(e)
+            }
+        };
+        if sender.send(message).is_err() {
+            return;
+        }
+    }
+}
+
+/// A [`Transport`] over a NATS server.
+///
+/// All participants publish broadcast data to `{prefix}.{session}.broadcast`
+/// and peer-to-peer data to `{prefix}.{session}.p2p.{recipient}`.
+/// Each participant subscribes to the broadcast subject and its own
+/// peer-to-peer subject. The session id must be a single subject token,
+/// so concurrent ceremonies on the same server don't mix.
+///
+/// The server sees the peer-to-peer data, use TLS and permissions so only
+/// participant `id` can subscribe to its subject or seal the data with a
+/// secure channel before sending.
+#[cfg(feature = "nats")]
+pub struct NatsTransport {
+    id: usize,
+    subject: String,
+    connection: nats::Connection,
+    broadcast: nats::Subscription,
+    p2p: nats::Subscription,
+}
+
+#[cfg(feature = "nats")]
+impl fmt::Debug for NatsTransport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NatsTransport")
+            .field("id", &self.id)
+            .field("subject", &self.subject)
+            .finish()
+    }
+}
+
+#[cfg(feature = "nats")]
+impl NatsTransport {
+    /// How long to wait on one subscription before checking the other
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Subscribe participant `id` to the subjects of `session` under `prefix`
+    pub fn new(
+        id: usize,
+        connection: nats::Connection,
+        prefix: &str,
+        session: &str,
+    ) -> DkgResult<Self> {
+        if session.is_empty()
+            || session
+                .chars()
+                .any(|c| matches!(c, '.' | '*' | '>') || c.is_whitespace())
+        {
+            return Err(Error::InvalidParameters(format!(
+                "'{}' is not a valid NATS subject token",
+                session
+            )));
+        }
+        let subject = format!("{}.{}", prefix.trim_end_matches('.'), session);
+        let broadcast = connection.subscribe(&format!("{}.broadcast", subject))?;
+        let p2p = connection.subscribe(&format!("{}.p2p.{}", subject, id))?;
+        Ok(Self {
+            id,
+            subject,
+            connection,
+            broadcast,
+            p2p,
+        })
+    }
+
+    fn publish<G: Group + GroupEncoding + Default>(
+        &self,
+        subject: String,
+        payload: &SessionPayload<G>,
+    ) -> DkgResult<()> {
+        let frame = FrameRef {
+            sender: self.id,
+            payload,
+        };
+        Ok(self.connection.publish(&subject, frame.to_bytes()?)?)
+    }
+}
+
+#[cfg(feature = "nats")]
+impl<G: Group + GroupEncoding + Default> Transport<G> for NatsTransport {
+    fn broadcast(&mut self, payload: &SessionPayload<G>) -> DkgResult<()> {
+        self.publish(format!("{}.broadcast", self.subject), payload)
+    }
+
+    fn send(&mut self, recipient: usize, payload: &SessionPayload<G>) -> DkgResult<()> {
+        self.publish(format!("{}.p2p.{}", self.subject, recipient), payload)
+    }
+
+    fn receive(&mut self, timeout: Duration) -> DkgResult<Option<(usize, SessionPayload<G>)>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let message = match self.p2p.try_next() {
+                Some(message) => message,
+                None => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match self
+                        .broadcast
+                        .next_timeout(remaining.min(Self::POLL_INTERVAL))
+                    {
+                        Ok(message) => message,
+                        Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                            if remaining.is_zero() {
+                                return Ok(None);
+                            }
+                            continue;
+                        }
+                        Err(e) => return Err(Error::IoError(e)),
+                    }
+                }
+            };
+            // Anyone allowed to publish on the subjects can send junk
+            let Ok(frame) = TransportFrame::<G>::from_bytes(&message.data) else {
+                continue;
+            };
+            // The server delivers this participant's broadcasts back to it
+            if frame.sender != self.id {
+                return Ok(Some((frame.sender, frame.payload)));
+            }
+        }
+    }
+}
+
+/// A point-to-point [`Transport`] over QUIC.
+///
+/// Each participant listens on its own address and connects directly to
+/// the others. Every message is sent on a new unidirectional stream, so a
+/// lost packet only delays its own round's data instead of everything
+/// behind it as with TCP. Connections are kept open between rounds, and a
+/// dropped connection is reopened with 0-RTT when the peer issued a session
+/// ticket. Only broadcast data, which carries no secrets, is sent as 0-RTT
+/// early data since early data has no forward secrecy. Peer-to-peer data
+/// waits for the handshake to complete. Early data can also be replayed by
+/// the network, so [`Transport::receive`] drops frames identical to one it
+/// already returned, otherwise each replay would count against the
+/// [`ReceiveLimits`] of the sender.
+///
+/// The TLS configurations must authenticate both sides, for example with
+/// client certificates, since the sender id of a frame is not checked
+/// against the connection.
+#[cfg(feature = "quic")]
+pub struct QuicTransport {
+    id: usize,
+    runtime: tokio::runtime::Runtime,
+    endpoint: quinn::Endpoint,
+    peers: BTreeMap<usize, (SocketAddr, String)>,
+    connections: BTreeMap<usize, quinn::Connection>,
+    incoming: tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+    seen: BTreeSet<[u8; 32]>,
+}
+
+#[cfg(feature = "quic")]
+impl fmt::Debug for QuicTransport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuicTransport")
+            .field("id", &self.id)
+            .field("peers", &self.peers)
+            .finish()
+    }
+}
+
+#[cfg(feature = "quic")]
+impl QuicTransport {
+    /// The largest frame accepted from a peer
+    const MAX_FRAME_LENGTH: usize = 1 << 20;
+
+    /// Listen on `address` for participant `id` with `server` and connect
+    /// to the others with `client`.
+    ///
+    /// `peers` maps the id of each other participant to its address and
+    /// the server name in its certificate.
+    pub fn new(
+        id: usize,
+        address: SocketAddr,
+        server: quinn::ServerConfig,
+        client: quinn::ClientConfig,
+        peers: BTreeMap<usize, (SocketAddr, String)>,
+    ) -> DkgResult<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        let mut endpoint = {
+            let _guard = runtime.enter();
+            quinn::Endpoint::server(server, address)?
+        };
+        endpoint.set_default_client_config(client);
+        let (sender, incoming) = tokio::sync::mpsc::unbounded_channel();
+        runtime.spawn(accept(endpoint.clone(), sender));
+        Ok(Self {
+            id,
+            runtime,
+            endpoint,
+            peers,
+            connections: BTreeMap::new(),
+            incoming,
+            seen: BTreeSet::new(),
+        })
+    }
+
+    /// The address this participant listens on
+    pub fn local_addr(&self) -> DkgResult<SocketAddr> {
+        Ok(self.endpoint.local_addr()?)
+    }
+
+    /// Send `frame` to `recipient`, as 0-RTT early data on a new
+    /// connection only if `early` is set
+    fn send_frame(&mut self, recipient: usize, frame: &[u8], early: bool) -> DkgResult<()> {
+        let (address, server_name) = self.peers.get(&recipient).cloned().ok_or_else(|| {
+            Error::InvalidParameters(format!("no address for participant {}", recipient))
+        })?;
+        let Self {
+            runtime,
+            endpoint,
+            connections,
+            ..
+        } = self;
+        runtime.block_on(async {
+            if let Some(connection) = connections.get(&recipient) {
+                if connection.close_reason().is_none()
+                    && write_stream(connection, frame).await.is_ok()
+                {
+                    return Ok(());
+                }
+            }
+            let connecting = endpoint
+                .connect(address, &server_name)
+                .map_err(transport_error)?;
+            let connecting = if early {
+                connecting.into_0rtt()
+            } else {
+                Err(connecting)
+            };
+            let connection = match connecting {
+                Ok((connection, accepted)) => {
+                    let sent = write_stream(&connection, frame).await.is_ok();
+                    // Send again once the handshake completes if the peer refused the early data
+                    if !(sent && accepted.await) {
+                        write_stream(&connection, frame).await?;
+                    }
+                    connection
+                }
+                Err(connecting) => {
+                    let connection = connecting.await.map_err(transport_error)?;
+                    write_stream(&connection, frame).await?;
+                    connection
+                }
+            };
+            connections.insert(recipient, connection);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "quic")]
+impl<G: Group + GroupEncoding + Default> Transport<G> for QuicTransport {
+    fn broadcast(&mut self, payload: &SessionPayload<G>) -> DkgResult<()> {
+        let frame = FrameRef {
+            sender: self.id,
+            payload,
+        }
+        .to_bytes()?;
+        let recipients = self.peers.keys().copied().collect::<Vec<_>>();
+        for recipient in recipients {
+            self.send_frame(recipient, &frame, true)?;
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, recipient: usize, payload: &SessionPayload<G>) -> DkgResult<()> {
+        let mut frame = FrameRef {
+            sender: self.id,
+            payload,
+        }
+        .to_bytes()?;
+        let result = self.send_frame(recipient, &frame, false);
+        frame.zeroize();
+        result
+    }
+
+    fn receive(&mut self, timeout: Duration) -> DkgResult<Option<(usize, SessionPayload<G>)>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let Self {
+                runtime, incoming, ..
+            } = self;
+            let next = runtime
+                .block_on(async { tokio::time::timeout_at(deadline, incoming.recv()).await });
+            let Ok(Some(bytes)) = next else {
+                return Ok(None);
+            };
+            // Any connected peer can send junk, only remember frames that decode
+            let Ok(frame) = TransportFrame::<G>::from_bytes(&bytes) else {
+                continue;
+            };
+            if !self.seen.insert(Sha256::digest(&bytes).into()) {
+                continue;
+            }
+            if frame.sender != self.id {
+                return Ok(Some((frame.sender, frame.payload)));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "quic")]
+async fn write_stream(connection: &quinn::Connection, frame: &[u8]) -> DkgResult<()> {
+    let mut stream = connection.open_uni().await.map_err(transport_error)?;
+    stream.write_all(frame).await.map_err(transport_error)?;
+    stream.finish().map_err(transport_error)
+}
+
+#[cfg(feature = "quic")]
+async fn accept(endpoint: quinn::Endpoint, sender: tokio::sync::mpsc::UnboundedSender<Vec<u8>>) {
+    while let Some(incoming) = endpoint.accept().await {
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let Ok(connecting) = incoming.accept() else {
+                return;
+            };
+            let connection = match connecting.into_0rtt() {
+                Ok((connection, _)) => connection,
+                Err(connecting) => match connecting.await {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                },
+            };
+            while let Ok(mut stream) = connection.accept_uni().await {
+                let sender = sender.clone();
+                // Read the streams concurrently so one round's data doesn't wait on another
+                tokio::spawn(async move {
+                    if let Ok(frame) = stream.read_to_end(QuicTransport::MAX_FRAME_LENGTH).await {
+                        let _ = sender.send(frame);
+                    }
+                });
+            }
+        });
+    }
+}
+
+#[cfg(any(feature = "mqtt", feature = "quic"))]
+fn transport_error<E: std::error::Error + Send + Sync + 'static>(e: E) -> Error {
+    Error::IoError(std::io::Error::new(std::io::ErrorKind::Other, e))
+}
@@ -0,0 +1,214 @@
+//! Helper functions for working with the output of the DKG
+
+use crate::{DkgResult, Error};
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::sync::atomic;
+use vsss_rs::elliptic_curve::{
+    ff::{Field, PrimeField},
+    group::GroupEncoding,
+    Group,
+};
+
+/// Evaluate the feldman `commitments` at `id`.
+///
+/// Given the commitments from every valid participant, the sum of
+/// the evaluations is the public share of the participant with
+/// identifier `id`, i.e. `G * secret_share`.
+pub fn evaluate_commitments<G: Group + GroupEncoding + Default>(
+    commitments: &[G],
+    id: G::Scalar,
+) -> G {
+    commitments
+        .iter()
+        .rev()
+        .fold(G::identity(), |acc, c| acc * id + *c)
+}
+
+/// Check many pedersen share openings for the participant with identifier `id` at once.
+///
+/// Each opening is the pedersen commitments from a sender with the secret and
+/// blind shares it sent. The equations are combined with random weights so a
+/// single comparison is made instead of one per sender. Returns `true` only if
+/// every opening is valid (except with negligible probability), if `false`
+/// the openings must be checked individually to find the invalid ones.
+///
+/// The shares are only combined with field arithmetic and the final check
+/// is a constant-time identity test, no branch depends on their values.
+pub fn batch_verify_pedersen_shares<G: Group + GroupEncoding + Default>(
+    message_generator: G,
+    blinder_generator: G,
+    id: G::Scalar,
+    openings: &[(&[G], G::Scalar, G::Scalar)],
+    mut rng: impl RngCore + CryptoRng,
+) -> bool {
+    let mut secret = G::Scalar::ZERO;
+    let mut blinder = G::Scalar::ZERO;
+    let mut rhs = G::identity();
+    for (commitments, secret_share, blind_share) in openings {
+        let r = G::Scalar::random(&mut rng);
+        secret += r * secret_share;
+        blinder += r * blind_share;
+        rhs += evaluate_commitments(commitments, id) * r;
+    }
+    (message_generator * secret + blinder_generator * blinder - rhs)
+        .is_identity()
+        .into()
+}
+
+/// Check many feldman share openings for the participant with identifier `id` at once.
+///
+/// Works the same as [`batch_verify_pedersen_shares`] for feldman commitments.
+pub fn batch_verify_feldman_shares<G: Group + GroupEncoding + Default>(
+    generator: G,
+    id: G::Scalar,
+    openings: &[(&[G], G::Scalar)],
+    mut rng: impl RngCore + CryptoRng,
+) -> bool {
+    let mut secret = G::Scalar::ZERO;
+    let mut rhs = G::identity();
+    for (commitments, share) in openings {
+        let r = G::Scalar::random(&mut rng);
+        secret += r * share;
+        rhs += evaluate_commitments(commitments, id) * r;
+    }
+    (generator * secret - rhs).is_identity().into()
+}
+
+/// Hash `msg` to a field element using expand_message_xmd with SHA-256
+/// as described in RFC 9380 and `dst` as the domain separation tag.
+///
+/// 128 extra bits are expanded beyond the size of the field so
+/// the reduction is unbiased.
+pub fn hash_to_scalar<F: PrimeField>(dst: &[u8], msg: &[u8]) -> DkgResult<F> {
+    let len = (F::NUM_BITS as usize + 128).div_ceil(8);
+    let bytes = expand_message_xmd(dst, msg, len)?;
+    let radix = F::from(256);
+    Ok(bytes
+        .iter()
+        .fold(F::ZERO, |acc, b| acc * radix + F::from(*b as u64)))
+}
+
+/// Overwrite `scalar` with zero before its memory is released.
+///
+/// The field traits don't require [`zeroize::Zeroize`] so the write
+/// is passed through [`std::hint::black_box`] and followed by a fence
+/// to keep the compiler from removing it as a dead store.
+pub(crate) fn zeroize_scalar<F: Field>(scalar: &mut F) {
+    *scalar = F::ZERO;
+    std::hint::black_box(scalar);
+    atomic::compiler_fence(atomic::Ordering::SeqCst);
+}
+
+/// Encode the public key from a DKG over edwards25519 as an Ed25519 verifying key.
+///
+/// Standard Ed25519 signing keys are a 32 byte seed that is hashed and
+/// clamped to get the secret scalar. The DKG secret is a uniformly random
+/// scalar that is never clamped and has no seed, so the shares cannot be
+/// loaded into a regular Ed25519 signer. They must be used by a threshold
+/// Schnorr signer that works with the scalar directly (e.g. FROST Ed25519).
+/// Signatures made that way verify with any standard Ed25519 verifier
+/// using the key returned here.
+#[cfg(feature = "curve25519")]
+pub fn ed25519_verifying_key(public_key: &vsss_rs::curve25519::WrappedEdwards) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out.copy_from_slice(public_key.to_bytes().as_ref());
+    out
+}
+
+/// Derive a nonzero scalar identifier from an external identity
+/// such as a TLS public key, DID or hostname.
+///
+/// Participants in the protocol use the sequential ids `1..=limit`,
+/// this is meant for protocols built on the output that identify
+/// shares by scalar, e.g. the `shares_ids` passed to
+/// [`crate::Participant::with_secret`].
+pub fn participant_id_from_bytes<G: Group + GroupEncoding + Default>(
+    identity: &[u8],
+) -> DkgResult<G::Scalar> {
+    derive_participant_id::<G>(b"gennaro-dkg participant id", identity)
+}
+
+/// Derive a nonzero scalar identifier from an external identity
+/// within a tenant or application namespace.
+///
+/// The same identity gets unrelated identifiers in different namespaces
+/// so an operator can use one identity in many tenants' ceremonies.
+/// `namespace` is the 32 byte hash set with [`crate::Parameters::with_namespace`].
+pub fn namespaced_participant_id_from_bytes<G: Group + GroupEncoding + Default>(
+    namespace: &[u8; 32],
+    identity: &[u8],
+) -> DkgResult<G::Scalar> {
+    derive_participant_id::<G>(
+        b"gennaro-dkg namespaced participant id",
+        &[namespace.as_slice(), identity].concat(),
+    )
+}
+
+fn derive_participant_id<G: Group + GroupEncoding + Default>(
+    dst: &[u8],
+    identity: &[u8],
+) -> DkgResult<G::Scalar> {
+    let mut counter = 0u8;
+    loop {
+        let id = hash_to_scalar::<G::Scalar>(dst, &[identity, &[counter]].concat())?;
+        if !bool::from(id.is_zero()) {
+            return Ok(id);
+        }
+        counter = counter
+            .checked_add(1)
+            .ok_or_else(|| Error::InvalidParameters("unable to derive a nonzero id".to_string()))?;
+    }
+}
+
+pub(crate) fn expand_message_xmd(dst: &[u8], msg: &[u8], len: usize) -> DkgResult<Vec<u8>> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+
+    let ell = len.div_ceil(B_IN_BYTES);
+    if ell > 255 || len > u16::MAX as usize {
+        return Err(Error::InvalidParameters(format!(
+            "cannot expand message to {} bytes",
+            len
+        )));
+    }
+    if dst.len() > 255 {
+        return Err(Error::InvalidParameters(
+            "domain separation tag is longer than 255 bytes".to_string(),
+        ));
+    }
+    let dst_len = [dst.len() as u8];
+
+    let b0 = Sha256::new()
+        .chain_update([0u8; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+    let mut bi = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut out = Vec::with_capacity(ell * B_IN_BYTES);
+    out.extend_from_slice(&bi);
+    for i in 2..=ell {
+        let mut xored = [0u8; B_IN_BYTES];
+        for (x, (l, r)) in xored.iter_mut().zip(b0.iter().zip(bi.iter())) {
+            *x = l ^ r;
+        }
+        bi = Sha256::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        out.extend_from_slice(&bi);
+    }
+    out.truncate(len);
+    Ok(out)
+}
@@ -0,0 +1,24 @@
+use super::*;
+
+/// The version of the serialized [`Participant`] and [`DkgOutput`] layouts.
+///
+/// State serialized before the version was added reads as version 0,
+/// use [`Participant::migrate`] to upgrade it. The fields added since
+/// then follow the original fields and read as their defaults when they
+/// are missing, which only works with self-describing formats like JSON.
+/// Version 0 state written with a binary format like serde_bare can't be read.
+pub const STATE_VERSION: u16 = 1;
+
+pub(crate) fn check(version: u16) -> DkgResult<u16> {
+    if version > STATE_VERSION {
+        return Err(Error::SerializationError(format!(
+            "state version {} is newer than the supported version {}",
+            version, STATE_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u16, D::Error> {
+    check(u16::deserialize(d)?).map_err(DError::custom)
+}
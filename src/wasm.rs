@@ -0,0 +1,108 @@
+//! WebAssembly bindings for running a secp256k1 participant in the browser.
+//!
+//! Every payload is a `Uint8Array` encoded with serde_bare.
+//! Data received from several participants is passed as one encoded
+//! `BTreeMap<usize, Vec<u8>>` from sender id to that sender's message.
+use crate::encoded::*;
+use wasm_bindgen::prelude::*;
+
+fn js_error(e: crate::Error) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// A secp256k1 DKG participant
+#[wasm_bindgen]
+pub struct Secp256k1Participant(EncodedParticipant);
+
+#[wasm_bindgen]
+impl Secp256k1Participant {
+    /// Create a new participant with identifier `id`
+    #[wasm_bindgen(constructor)]
+    pub fn new(id: usize, threshold: usize, limit: usize) -> Result<Secp256k1Participant, JsError> {
+        EncodedParticipant::new(id, threshold, limit)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    /// Restore a participant saved with `toBytes`
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Secp256k1Participant, JsError> {
+        EncodedParticipant::from_bytes(bytes)
+            .map(Self)
+            .map_err(js_error)
+    }
+
+    /// Save the participant state, this contains secret data
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        self.0.to_bytes().map_err(js_error)
+    }
+
+    /// The participant's identifier
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> usize {
+        self.0.id()
+    }
+
+    /// The round the participant is waiting to compute
+    #[wasm_bindgen(getter)]
+    pub fn round(&self) -> u8 {
+        self.0.round().into()
+    }
+
+    /// Compute round 1, returns the broadcast message
+    /// and the encoded map of peer-to-peer messages by recipient
+    pub fn round1(&mut self) -> Result<Round1Output, JsError> {
+        let output = self.0.round1().map_err(js_error)?;
+        Ok(Round1Output {
+            broadcast: output.broadcast,
+            p2p: encode_map(&output.p2p).map_err(js_error)?,
+        })
+    }
+
+    /// Compute round 2 with the round 1 messages received, returns the echo broadcast
+    pub fn round2(&mut self, broadcast: &[u8], p2p: &[u8]) -> Result<Vec<u8>, JsError> {
+        let broadcast = decode_map(broadcast).map_err(js_error)?;
+        let p2p = decode_map(p2p).map_err(js_error)?;
+        self.0.round2(&broadcast, &p2p).map_err(js_error)
+    }
+
+    /// Compute round 3 with the round 2 echo broadcasts, returns the broadcast
+    pub fn round3(&mut self, echo: &[u8]) -> Result<Vec<u8>, JsError> {
+        let echo = decode_map(echo).map_err(js_error)?;
+        self.0.round3(&echo).map_err(js_error)
+    }
+
+    /// Compute round 4 with the round 3 broadcasts, returns the echo broadcast
+    pub fn round4(&mut self, broadcast: &[u8]) -> Result<Vec<u8>, JsError> {
+        let broadcast = decode_map(broadcast).map_err(js_error)?;
+        self.0.round4(&broadcast).map_err(js_error)
+    }
+
+    /// Check the round 4 echo broadcasts all computed the same public key
+    pub fn round5(&mut self, echo: &[u8]) -> Result<(), JsError> {
+        let echo = decode_map(echo).map_err(js_error)?;
+        self.0.round5(&echo).map_err(js_error)
+    }
+
+    /// The secret share, available after round 2
+    #[wasm_bindgen(js_name = secretShare)]
+    pub fn secret_share(&self) -> Option<Vec<u8>> {
+        self.0.secret_share()
+    }
+
+    /// The compressed public key, available after round 5
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self) -> Option<Vec<u8>> {
+        self.0.public_key()
+    }
+}
+
+/// The messages produced by round 1
+#[wasm_bindgen(getter_with_clone)]
+pub struct Round1Output {
+    /// Send to all other participants
+    pub broadcast: Vec<u8>,
+    /// Encoded map of messages to send to each participant by id
+    pub p2p: Vec<u8>,
+}
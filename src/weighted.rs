@@ -0,0 +1,203 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// The share identifiers assigned to each party when every party
+/// receives as many shares as its weight.
+///
+/// Parties are ordered by id and receive consecutive identifiers
+/// starting at 1, so the total weight is the `limit` of the [`Parameters`].
+pub fn weighted_share_ids(
+    weights: &BTreeMap<usize, NonZeroUsize>,
+) -> BTreeMap<usize, RangeInclusive<usize>> {
+    let mut start = 1;
+    let mut ids = BTreeMap::new();
+    for (party, weight) in weights {
+        let end = start + weight.get() - 1;
+        ids.insert(*party, start..=end);
+        start = end + 1;
+    }
+    ids
+}
+
+/// A party holding as many shares as its weight.
+///
+/// The party runs one [`Participant`] per share identifier assigned by
+/// [`weighted_share_ids`]. Round data is keyed by share identifier so each
+/// entry must be delivered as if it were from or to a separate participant,
+/// including the entries between the party's own participants.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightedParticipant<I: ParticipantImpl<G>, G: Group + GroupEncoding + Default> {
+    party: usize,
+    #[serde(bound(serialize = "Participant<I, G>: Serialize"))]
+    #[serde(bound(deserialize = "Participant<I, G>: Deserialize<'de>"))]
+    participants: Vec<Participant<I, G>>,
+}
+
+/// Weighted secret participant type
+pub type WeightedSecretParticipant<G> = WeightedParticipant<SecretParticipantImpl<G>, G>;
+
+impl<I, G> WeightedParticipant<I, G>
+where
+    I: ParticipantImpl<G> + Default,
+    G: Group + GroupEncoding + Default,
+{
+    /// Create a new party `party` with the shares assigned by `weights`.
+    ///
+    /// The `limit` in `parameters` must be the total weight.
+    pub fn new(
+        party: usize,
+        weights: &BTreeMap<usize, NonZeroUsize>,
+        parameters: Parameters<G>,
+    ) -> DkgResult<Self> {
+        let total = weights.values().map(|w| w.get()).sum::<usize>();
+        if total != parameters.limit {
+            return Err(Error::InvalidParameters(format!(
+                "the total weight {} is not the limit {}",
+                total, parameters.limit
+            )));
+        }
+        let ids = weighted_share_ids(weights)
+            .remove(&party)
+            .ok_or_else(|| Error::InvalidParameters(format!("unknown party {}", party)))?;
+        let participants = ids
+            .map(|id| Participant::new(NonZeroUsize::new(id).expect("ids start at 1"), parameters))
+            .collect::<DkgResult<Vec<_>>>()?;
+        Ok(Self {
+            party,
+            participants,
+        })
+    }
+
+    /// The identifier of this party
+    pub fn get_party(&self) -> usize {
+        self.party
+    }
+
+    /// The share identifiers held by this party
+    pub fn get_share_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.participants.iter().map(|p| p.get_id())
+    }
+
+    /// The current round of this party
+    pub fn get_round(&self) -> Round {
+        self.participants[0].get_round()
+    }
+
+    /// Returns true if this party is complete
+    pub fn completed(&self) -> bool {
+        self.participants.iter().all(|p| p.completed())
+    }
+
+    /// The participants holding each share
+    pub fn participants(&self) -> &[Participant<I, G>] {
+        &self.participants
+    }
+
+    /// The public key and all the secret shares of this party, or [`None`] until complete
+    pub fn output(&self) -> Option<DkgOutput<G>> {
+        if !self.completed() {
+            return None;
+        }
+        let mut output = self.participants[0].output()?;
+        for p in &self.participants[1..] {
+            let mut share = p.output()?;
+            output.secret_shares.append(&mut share.secret_shares);
+            output
+                .report
+                .public_share_fingerprints
+                .extend(share.report.public_share_fingerprints);
+        }
+        Some(output)
+    }
+
+    /// Computes round 1 for each share.
+    ///
+    /// Returns the broadcast data keyed by sender and
+    /// the peer data keyed by sender then by recipient.
+    ///
+    /// See [`Participant::round1`]
+    #[allow(clippy::type_complexity)]
+    pub fn round1(
+        &mut self,
+    ) -> DkgResult<(
+        BTreeMap<usize, Round1BroadcastData<G>>,
+        BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+    )> {
+        let mut broadcast = BTreeMap::new();
+        let mut p2p = BTreeMap::new();
+        for participant in self.participants.iter_mut() {
+            let (b, p) = participant.round1()?;
+            broadcast.insert(participant.get_id(), b);
+            p2p.insert(participant.get_id(), p);
+        }
+        Ok((broadcast, p2p))
+    }
+
+    /// Computes round 2 for each share.
+    ///
+    /// `broadcast_data` is keyed by sender and `p2p_data` by sender then by recipient,
+    /// only the entries for this party's shares are used.
+    ///
+    /// See [`Participant::round2`]
+    pub fn round2(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round1BroadcastData<G>>,
+        p2p_data: &BTreeMap<usize, BTreeMap<usize, Round1P2PData>>,
+    ) -> DkgResult<BTreeMap<usize, Round2EchoBroadcastData>> {
+        let mut output = BTreeMap::new();
+        for participant in self.participants.iter_mut() {
+            let id = participant.get_id();
+            let bdata = broadcast_data
+                .iter()
+                .filter(|(sender, _)| **sender != id)
+                .map(|(sender, data)| (*sender, data.clone()))
+                .collect();
+            let p2pdata = p2p_data
+                .iter()
+                .filter(|(sender, _)| **sender != id)
+                .filter_map(|(sender, data)| Some((*sender, data.get(&id)?.clone())))
+                .collect();
+            output.insert(id, participant.round2(bdata, p2pdata)?);
+        }
+        Ok(output)
+    }
+
+    /// Computes round 3 for each share.
+    ///
+    /// See [`Participant::round3`]
+    pub fn round3(
+        &mut self,
+        echo_data: &BTreeMap<usize, Round2EchoBroadcastData>,
+    ) -> DkgResult<BTreeMap<usize, Round3BroadcastData<G>>> {
+        self.participants
+            .iter_mut()
+            .map(|p| Ok((p.get_id(), p.round3(echo_data)?)))
+            .collect()
+    }
+
+    /// Computes round 4 for each share.
+    ///
+    /// See [`Participant::round4`]
+    pub fn round4(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round3BroadcastData<G>>,
+    ) -> DkgResult<BTreeMap<usize, Round4EchoBroadcastData<G>>> {
+        self.participants
+            .iter_mut()
+            .map(|p| Ok((p.get_id(), p.round4(broadcast_data)?)))
+            .collect()
+    }
+
+    /// Computes round 5 for each share.
+    ///
+    /// See [`Participant::round5`]
+    pub fn round5(
+        &mut self,
+        broadcast_data: &BTreeMap<usize, Round4EchoBroadcastData<G>>,
+    ) -> DkgResult<()> {
+        self.participants
+            .iter_mut()
+            .try_for_each(|p| p.round5(broadcast_data))
+    }
+}
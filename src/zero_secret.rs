@@ -0,0 +1,71 @@
+use super::*;
+
+const ZERO_SECRET_DST: &[u8] = b"gennaro-dkg zero secret proof";
+
+/// A proof that a dealer's secret is zero, sent with the round 1 data
+/// of a [`RefreshParticipant`].
+///
+/// The first Pedersen commitment is `message_generator * secret +
+/// blinder_generator * blinder`. This is a Schnorr proof of knowledge of
+/// a `blinder` with `pedersen_commitments[0] = blinder_generator * blinder`,
+/// which a dealer can only make if its secret is zero. Unlike checking that
+/// the first feldman commitment is the identity it is verified in round 2
+/// while the commitments are still blinded. A participant that sends a proof
+/// rejects dealers that don't, except those set with
+/// [`Participant::set_secret_dealers`].
+///
+/// The challenge binds the dealer id, the generators, all the commitments
+/// and the setup entries of the transcript, so a proof can't be replayed
+/// by another dealer or in another ceremony.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZeroSecretProof<G: Group + GroupEncoding + Default> {
+    /// The proof nonce commitment
+    #[serde(serialize_with = "serialize_g", deserialize_with = "deserialize_g")]
+    pub r: G,
+    /// The proof response
+    #[serde(
+        serialize_with = "serialize_scalar",
+        deserialize_with = "deserialize_scalar"
+    )]
+    pub s: G::Scalar,
+}
+
+impl<G: Group + GroupEncoding + Default> ZeroSecretProof<G> {
+    pub(crate) fn prove(
+        dealer: usize,
+        context: &[u8; 32],
+        data: &Round1BroadcastData<G>,
+        blinder: G::Scalar,
+    ) -> DkgResult<Self> {
+        let (r, s) = primitives::prove(data.blinder_generator, blinder, |r| {
+            Self::challenge(dealer, context, data, r)
+        })?;
+        Ok(Self { r, s })
+    }
+
+    /// Check the proof for the round 1 data of `dealer`.
+    ///
+    /// `context` is the hash of the setup entries of the transcript.
+    pub fn verify(&self, dealer: usize, context: &[u8; 32], data: &Round1BroadcastData<G>) -> bool {
+        let Some(commitment) = data.pedersen_commitments.first() else {
+            return false;
+        };
+        primitives::verify(data.blinder_generator, *commitment, self.r, self.s, |r| {
+            Self::challenge(dealer, context, data, r)
+        })
+    }
+
+    fn challenge(
+        dealer: usize,
+        context: &[u8; 32],
+        data: &Round1BroadcastData<G>,
+        r: G,
+    ) -> DkgResult<G::Scalar> {
+        let points = [data.message_generator, data.blinder_generator]
+            .into_iter()
+            .chain(data.pedersen_commitments.iter().copied())
+            .chain(std::iter::once(r))
+            .collect::<Vec<_>>();
+        primitives::challenge(ZERO_SECRET_DST, &[dealer], &points, context)
+    }
+}
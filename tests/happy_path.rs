@@ -16,6 +16,10 @@ use vsss_rs::{
 #[case::ristretto25519(WrappedRistretto::default())]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY)]
+#[case::pallas(pasta_curves::pallas::Point::identity())]
+#[case::vesta(pasta_curves::vesta::Point::identity())]
+#[case::bn254(halo2curves::bn256::G1::identity())]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity())]
 fn init_dkg<G: Group + GroupEncoding + Default>(#[case] _g: G) {
     five_participants_init::<G>();
 }
@@ -27,6 +31,10 @@ fn init_dkg<G: Group + GroupEncoding + Default>(#[case] _g: G) {
 #[case::ristretto25519(WrappedRistretto::default(), 3)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 3)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 3)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 3)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 3)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 3)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 3)]
 fn add_participant_same_threshold<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -42,6 +50,10 @@ fn add_participant_same_threshold<G: Group + GroupEncoding + Default>(
 #[case::ristretto25519(WrappedRistretto::default(), 5)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 5)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 4)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 4)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 4)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 4)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 4)]
 fn add_participant_increase_threshold<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -57,6 +69,10 @@ fn add_participant_increase_threshold<G: Group + GroupEncoding + Default>(
 #[case::ristretto25519(WrappedRistretto::default(), 3)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 3)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 3)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 3)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 3)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 3)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 3)]
 fn remove_participant_same_threshold<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -72,6 +88,10 @@ fn remove_participant_same_threshold<G: Group + GroupEncoding + Default>(
 #[case::ristretto25519(WrappedRistretto::default(), 2)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 2)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 2)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 2)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 2)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 2)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 2)]
 fn remove_participant_decrease_threshold<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -86,6 +106,10 @@ fn remove_participant_decrease_threshold<G: Group + GroupEncoding + Default>(
 #[case::ristretto25519(WrappedRistretto::default(), 5)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 5)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 2)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 2)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 2)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 2)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 2)]
 fn add_and_remove_participant_increase_participant<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -100,6 +124,10 @@ fn add_and_remove_participant_increase_participant<G: Group + GroupEncoding + De
 #[case::ristretto25519(WrappedRistretto::default(), 2)]
 #[case::bls12_381_g1(blsful::inner_types::G1Projective::IDENTITY, 3)]
 #[case::bls12_381_g2(blsful::inner_types::G2Projective::IDENTITY, 4)]
+#[case::pallas(pasta_curves::pallas::Point::identity(), 4)]
+#[case::vesta(pasta_curves::vesta::Point::identity(), 4)]
+#[case::bn254(halo2curves::bn256::G1::identity(), 4)]
+#[case::ed448(ed448_goldilocks_plus::EdwardsPoint::identity(), 4)]
 fn add_and_remove_participant_decrease_participant<G: Group + GroupEncoding + Default>(
     #[case] _g: G,
     #[case] threshold: usize,
@@ -191,7 +219,7 @@ fn five_participants_init<G: Group + GroupEncoding + Default>(
         assert!(p.round4(&r3bdata).is_err());
     }
 
-    for p in &participants {
+    for p in participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
 
@@ -286,6 +314,11 @@ fn five_participants_add_participant<G: Group + GroupEncoding + Default>(thresho
         RefreshParticipant::<G>::new(NonZeroUsize::new(7).unwrap(), parameters).unwrap(),
     ];
 
+    // The existing holders re-share a nonzero secret without a zero secret proof
+    for p in new_participants.iter_mut() {
+        p.set_secret_dealers(1..=LIMIT);
+    }
+
     // Round 1
     let mut r1bdata = Vec::with_capacity(LIMIT + INCREMENT);
     let mut r1p2pdata = Vec::with_capacity(LIMIT + INCREMENT);
@@ -394,17 +427,17 @@ fn five_participants_add_participant<G: Group + GroupEncoding + Default>(thresho
         let res = p.round4(&r3bdata);
         assert!(res.is_ok());
         let bdata = res.unwrap();
-        let share = p.get_secret_share().unwrap();
+        let share = p.get_secret_share().unwrap().into_new_share();
         r4bdata.insert(p.get_id(), bdata);
         r4shares.push(<InnerShare as Share>::from_field_element(p.get_id() as u8, share).unwrap());
         assert!(p.round4(&r3bdata).is_err());
     }
 
     // Round 5
-    for p in &participants {
+    for p in participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
-    for p in &new_participants {
+    for p in new_participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
 
@@ -544,7 +577,7 @@ fn five_participants_remove_participant<G: Group + GroupEncoding + Default>(thre
         assert!(p.round4(&r3bdata).is_err());
     }
 
-    for p in &participants {
+    for p in participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
 
@@ -610,6 +643,11 @@ fn five_participants_add_and_remove_decrease_participant<G: Group + GroupEncodin
     let mut new_participants =
         [RefreshParticipant::<G>::new(NonZeroUsize::new(4).unwrap(), parameters).unwrap()];
 
+    // The existing holders re-share a nonzero secret without a zero secret proof
+    for p in new_participants.iter_mut() {
+        p.set_secret_dealers(1..=LIMIT);
+    }
+
     // Round 1
     let mut r1bdata = Vec::with_capacity(LIMIT + INCREMENT);
     let mut r1p2pdata = Vec::with_capacity(LIMIT + INCREMENT);
@@ -718,17 +756,17 @@ fn five_participants_add_and_remove_decrease_participant<G: Group + GroupEncodin
         let res = p.round4(&r3bdata);
         assert!(res.is_ok());
         let bdata = res.unwrap();
-        let share = p.get_secret_share().unwrap();
+        let share = p.get_secret_share().unwrap().into_new_share();
         r4bdata.insert(p.get_id(), bdata);
         r4shares.push(<InnerShare as Share>::from_field_element(p.get_id() as u8, share).unwrap());
         assert!(p.round4(&r3bdata).is_err());
     }
 
     // Round 5
-    for p in &participants {
+    for p in participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
-    for p in &new_participants {
+    for p in new_participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
 
@@ -805,6 +843,11 @@ fn five_participants_add_and_remove_increase_participant<G: Group + GroupEncodin
         RefreshParticipant::<G>::new(NonZeroUsize::new(6).unwrap(), parameters).unwrap(),
     ];
 
+    // The existing holders re-share a nonzero secret without a zero secret proof
+    for p in new_participants.iter_mut() {
+        p.set_secret_dealers(1..=LIMIT);
+    }
+
     // Round 1
     let mut r1bdata = Vec::with_capacity(LIMIT + INCREMENT);
     let mut r1p2pdata = Vec::with_capacity(LIMIT + INCREMENT);
@@ -913,17 +956,17 @@ fn five_participants_add_and_remove_increase_participant<G: Group + GroupEncodin
         let res = p.round4(&r3bdata);
         assert!(res.is_ok());
         let bdata = res.unwrap();
-        let share = p.get_secret_share().unwrap();
+        let share = p.get_secret_share().unwrap().into_new_share();
         r4bdata.insert(p.get_id(), bdata);
         r4shares.push(<InnerShare as Share>::from_field_element(p.get_id() as u8, share).unwrap());
         assert!(p.round4(&r3bdata).is_err());
     }
 
     // Round 5
-    for p in &participants {
+    for p in participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
-    for p in &new_participants {
+    for p in new_participants.iter_mut() {
         assert!(p.round5(&r4bdata).is_ok());
     }
 